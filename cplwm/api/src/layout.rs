@@ -0,0 +1,121 @@
+//! Tiling layouts defined as a trait
+//!
+//! A tiling window manager decides which windows are tiled and in which
+//! order, a [`Layout`] decides where these tiled windows are placed on the
+//! screen. This way the same window manager can arrange its tiled windows in
+//! different ways, see the [`LayoutSupport`] trait.
+//!
+//! [`Layout`]: trait.Layout.html
+//! [`LayoutSupport`]: ../wm/trait.LayoutSupport.html
+
+use rustc_serialize::{Decodable, Encodable};
+use std::fmt::Debug;
+
+use types::{ColumnRatio, DEFAULT_COLUMN_RATIO, DEFAULT_MASTER_COUNT, DEFAULT_MASTER_RATIO,
+            Geometry, MasterCount, MasterPosition, MasterRatio, Screen, Window};
+
+/// The settings of the window manager that are shared by all its layouts.
+///
+/// A layout can ignore the settings that make no sense for it, e.g. a layout
+/// without a master area ignores the master ratio.
+#[derive(Copy, Clone, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq, Hash)]
+pub struct LayoutSettings {
+    /// The width of the master area, in percent of the width of the screen.
+    /// When the master area is at the top or at the bottom, it is its height,
+    /// in percent of the height of the screen.
+    pub master_ratio: MasterRatio,
+    /// The number of windows in the master area.
+    pub master_count: MasterCount,
+    /// Where the master area sits, `None` when the user did not choose a
+    /// position, see `get_master_position`.
+    pub master_position: Option<MasterPosition>,
+    /// The width of the columns of the layouts with columns, in percent of
+    /// the width of the screen.
+    pub column_ratio: ColumnRatio,
+}
+
+impl LayoutSettings {
+    /// Return where the master area sits on the given screen: the chosen
+    /// position, or the default position for the screen, see
+    /// [`MasterPosition::default_for`].
+    ///
+    /// [`MasterPosition::default_for`]: ../types/enum.MasterPosition.html#method.default_for
+    pub fn get_master_position(&self, screen: Screen) -> MasterPosition {
+        self.master_position.unwrap_or(MasterPosition::default_for(screen))
+    }
+}
+
+impl Default for LayoutSettings {
+    fn default() -> LayoutSettings {
+        LayoutSettings {
+            master_ratio: DEFAULT_MASTER_RATIO,
+            master_count: DEFAULT_MASTER_COUNT,
+            master_position: None,
+            column_ratio: DEFAULT_COLUMN_RATIO,
+        }
+    }
+}
+
+/// A tiling layout.
+///
+/// A layout is part of the state of a window manager, so just like the
+/// [`WindowManager`] trait it has `Encodable + Decodable + Debug + Clone` as
+/// supertraits. This way the active layout is kept when the window manager is
+/// serialised, e.g. when restarting.
+///
+/// [`WindowManager`]: ../wm/trait.WindowManager.html
+pub trait Layout: Encodable + Decodable + Debug + Clone {
+    /// Calculate the geometries of the given tiled windows.
+    ///
+    /// The windows are given in the order of the window manager, the first
+    /// window is the master window (if the layout has one). The settings are
+    /// those of the window manager.
+    ///
+    /// **Invariant**: the returned vector contains every given window at most
+    /// once, in the same order, and nothing else. A window that is left out
+    /// is not visible, e.g. because it is scrolled out of view, the window
+    /// manager must leave it out of its window layout. Only a layout that
+    /// scrolls should leave out windows.
+    ///
+    /// **Invariant**: every returned geometry lies within the given screen.
+    fn get_geometries(&self,
+                      screen: Screen,
+                      settings: &LayoutSettings,
+                      windows: &[Window])
+                      -> Vec<(Window, Geometry)>;
+
+    /// Return the geometry of the tab bar of this layout, if it has one.
+    ///
+    /// A layout with a tab bar is a *tabbed* layout: it stacks the tiled
+    /// windows on top of each other, so the window manager must put the
+    /// focused tiled window on top of the others and report a [`TabBar`]
+    /// with the tiled windows, see [`TabBarSupport`].
+    ///
+    /// By default, a layout has no tab bar.
+    ///
+    /// **Invariant**: the tab bar lies within the given screen and does not
+    /// overlap with the geometries returned by `get_geometries`.
+    ///
+    /// [`TabBar`]: ../types/struct.TabBar.html
+    /// [`TabBarSupport`]: ../wm/trait.TabBarSupport.html
+    fn get_tab_bar_geometry(&self,
+                            _screen: Screen,
+                            _settings: &LayoutSettings)
+                            -> Option<Geometry> {
+        None
+    }
+
+    /// Let the layout follow the focus: the given window is the focused
+    /// tiled window, or the last focused one when a floating window is
+    /// focused. The window manager calls this method before
+    /// `get_geometries` with the same screen, settings and windows.
+    ///
+    /// A scrolling layout uses it to scroll the given window into view. By
+    /// default, nothing happens.
+    fn follow_focus(&mut self,
+                    _screen: Screen,
+                    _settings: &LayoutSettings,
+                    _windows: &[Window],
+                    _focused_window: Window) {
+    }
+}
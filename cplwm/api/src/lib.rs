@@ -7,10 +7,13 @@
 //!   implement in the assignments.
 //! * [`types`](./types/index.html) contains the basic types used throughout
 //!   the whole project.
+//! * [`layout`](./layout/index.html) contains the trait for the tiling
+//!   layouts a window manager can switch between.
 
 #![deny(missing_docs)]
 
 extern crate rustc_serialize;
 
+pub mod layout;
 pub mod types;
 pub mod wm;
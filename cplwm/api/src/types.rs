@@ -107,10 +107,40 @@ pub enum FloatOrTile {
     Tile,
 }
 
+/// The properties an application sets on its window, e.g. its class and
+/// title, as read by the backend.
+///
+/// Every field is optional, as an application is not required to set any of
+/// them. The backend reads them when the window is mapped and updates them
+/// when the application changes them, see
+/// [`WindowPropertiesSupport`](../wm/trait.WindowPropertiesSupport.html).
+///
+/// Unlike the other types in this module this type does not implement `Copy`,
+/// as it contains `String`s.
+#[derive(Clone, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq, Hash, Default)]
+pub struct WindowProperties {
+    /// The class of the application, the second part of `WM_CLASS`, e.g.
+    /// `"XTerm"`.
+    pub class: Option<String>,
+    /// The instance of the application, the first part of `WM_CLASS`, e.g.
+    /// `"xterm"`.
+    pub instance: Option<String>,
+    /// The role of the window, `WM_WINDOW_ROLE`, which distinguishes the
+    /// different windows of the same application.
+    pub role: Option<String>,
+    /// The title of the window, `_NET_WM_NAME` or `WM_NAME`.
+    pub title: Option<String>,
+    /// The process id of the application, `_NET_WM_PID`.
+    pub pid: Option<c_uint>,
+    /// The window for which this window is a transient window, e.g. the main
+    /// window of a dialog, `WM_TRANSIENT_FOR`.
+    pub transient_for: Option<Window>,
+}
+
 /// A `WindowWithInfo` is the combination of a `Window` with additional
 /// information: its `Geometry`, whether it should float or not
-/// (`float_or_tile`), and whether it should be displayed fullscreen or not
-/// (`fullscreen`).
+/// (`float_or_tile`), whether it should be displayed fullscreen or not
+/// (`fullscreen`), and the properties set by the application (`properties`).
 ///
 /// The `float_or_tile` field will be set to `Float` by the backend when the
 /// window is a dialog or popup, otherwise `Tile`. Window managers not
@@ -123,10 +153,14 @@ pub enum FloatOrTile {
 /// [`FullscreenSupport`](../wm/trait.FullscreenSupport.html) can safely
 /// ignore this.
 ///
+/// The `properties` field is filled in by the backend, the constructors below
+/// leave it empty. As it contains `String`s, this type does not implement
+/// `Copy`, use `clone` instead.
+///
 /// This is a separate type used by the `add_window` and `get_window_info`
 /// methods of the [`WindowManager`](../wm/trait.WindowManager.html) trait,
 /// and will also be useful when defining a window manager data type yourself.
-#[derive(Copy, Clone, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq, Hash)]
 pub struct WindowWithInfo {
     /// The window.
     pub window: Window,
@@ -136,6 +170,8 @@ pub struct WindowWithInfo {
     pub float_or_tile: FloatOrTile,
     /// Indicate whether the window should be displayed fullscreen or not.
     pub fullscreen: bool,
+    /// The properties set by the application.
+    pub properties: WindowProperties,
 }
 
 impl WindowWithInfo {
@@ -153,7 +189,8 @@ impl WindowWithInfo {
     pub fn new_fullscreen(window: Window, geometry: Geometry) -> WindowWithInfo {
         Self::new(window, geometry, FloatOrTile::Tile, true)
     }
-    /// Create a new `WindowWithInfo` with the given arguments.
+    /// Create a new `WindowWithInfo` with the given arguments and without
+    /// properties.
     pub fn new(window: Window,
                geometry: Geometry,
                float_or_tile: FloatOrTile,
@@ -164,6 +201,7 @@ impl WindowWithInfo {
             geometry: geometry,
             float_or_tile: float_or_tile,
             fullscreen: fullscreen,
+            properties: WindowProperties::default(),
         }
     }
 }
@@ -209,6 +247,54 @@ impl WindowLayout {
             windows: Vec::new(),
         }
     }
+
+    /// Return the visible window that is the nearest to the given window in
+    /// the given direction, based on their geometries in this layout.
+    ///
+    /// Only windows whose center lies in the given direction of the center
+    /// of the given window are considered. Of these, windows that overlap
+    /// with the given window on the other axis (e.g. the vertical axis when
+    /// going `Left`) are preferred, then the window with the nearest edge,
+    /// then the window with the nearest center on the other axis. Floating
+    /// windows are treated like any other window.
+    ///
+    /// Return `None` when the given window is not in this layout or when
+    /// there is no window in that direction.
+    pub fn get_window_in_direction(&self, window: Window, dir: Direction) -> Option<Window> {
+        let geometry = match self.windows.iter().find(|&&(w, _)| w == window) {
+            None => return None,
+            Some(&(_, geometry)) => geometry,
+        };
+        self.windows
+            .iter()
+            .filter(|&&(w, g)| w != window && dir.get_distance(&geometry, &g) > 0)
+            .min_by_key(|&&(_, g)| {
+                (!dir.overlaps(&geometry, &g),
+                 dir.get_edge_distance(&geometry, &g),
+                 dir.get_offset(&geometry, &g))
+            })
+            .map(|&(w, _)| w)
+    }
+}
+
+/// A tab bar listing the tiled windows of a tabbed layout.
+///
+/// In a tabbed layout, e.g. a monocle layout, the tiled windows are stacked
+/// on top of each other, so only one of them is visible. The backend draws
+/// the tab bar, a tab for each window, so the user can see which windows are
+/// hidden behind the visible one and click on a tab to focus its window.
+#[derive(Clone, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq, Hash)]
+pub struct TabBar {
+    /// Where the backend should draw the tab bar.
+    pub geometry: Geometry,
+    /// The windows that get a tab, from left to right.
+    pub windows: Vec<Window>,
+    /// The window of the active tab, i.e. the visible one, which is
+    /// highlighted.
+    ///
+    /// **Invariant**: if `active_window = Some(w)`, `w` must be in
+    /// `windows`.
+    pub active_window: Option<Window>,
 }
 
 /// A type that is either *previous* or *next*.
@@ -235,6 +321,98 @@ impl PrevOrNext {
     }
 }
 
+/// A direction on the screen.
+///
+/// Used to move the focus or a window to the window that is visually next
+/// to it, see
+/// [`DirectionalSupport`](../wm/trait.DirectionalSupport.html).
+#[derive(Copy, Clone, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Left
+    Left,
+    /// Right
+    Right,
+    /// Up
+    Up,
+    /// Down
+    Down,
+}
+
+impl Direction {
+    /// Return the opposite of the given direction, e.g. the opposite of
+    /// `Left` is `Right` and vice versa.
+    pub fn opposite(&self) -> Self {
+        use self::Direction::*;
+        match *self {
+            Left => Right,
+            Right => Left,
+            Up => Down,
+            Down => Up,
+        }
+    }
+
+    /// Return how far the center of `to` lies from the center of `from` in
+    /// this direction. Negative when it lies in the opposite direction.
+    fn get_distance(&self, from: &Geometry, to: &Geometry) -> i64 {
+        let (from_x, from_y) = get_center(from);
+        let (to_x, to_y) = get_center(to);
+        match *self {
+            Direction::Left => from_x - to_x,
+            Direction::Right => to_x - from_x,
+            Direction::Up => from_y - to_y,
+            Direction::Down => to_y - from_y,
+        }
+    }
+
+    /// Return the space between the facing edges of `from` and `to` in this
+    /// direction, 0 when they touch or overlap.
+    fn get_edge_distance(&self, from: &Geometry, to: &Geometry) -> i64 {
+        let distance = match *self {
+            Direction::Left => from.x as i64 - (to.x as i64 + to.width as i64),
+            Direction::Right => to.x as i64 - (from.x as i64 + from.width as i64),
+            Direction::Up => from.y as i64 - (to.y as i64 + to.height as i64),
+            Direction::Down => to.y as i64 - (from.y as i64 + from.height as i64),
+        };
+        if distance < 0 { 0 } else { distance }
+    }
+
+    /// Return the distance between the centers of `from` and `to` on the
+    /// other axis than the one of this direction.
+    fn get_offset(&self, from: &Geometry, to: &Geometry) -> i64 {
+        let (from_x, from_y) = get_center(from);
+        let (to_x, to_y) = get_center(to);
+        match *self {
+            Direction::Left | Direction::Right => (from_y - to_y).abs(),
+            Direction::Up | Direction::Down => (from_x - to_x).abs(),
+        }
+    }
+
+    /// Return whether `from` and `to` overlap on the other axis than the one
+    /// of this direction.
+    fn overlaps(&self, from: &Geometry, to: &Geometry) -> bool {
+        let (from_start, from_end, to_start, to_end) = match *self {
+            Direction::Left | Direction::Right => {
+                (from.y as i64,
+                 from.y as i64 + from.height as i64,
+                 to.y as i64,
+                 to.y as i64 + to.height as i64)
+            }
+            Direction::Up | Direction::Down => {
+                (from.x as i64,
+                 from.x as i64 + from.width as i64,
+                 to.x as i64,
+                 to.x as i64 + to.width as i64)
+            }
+        };
+        from_start < to_end && to_start < from_end
+    }
+}
+
+/// Return the center of the given geometry.
+fn get_center(geometry: &Geometry) -> (i64, i64) {
+    (geometry.x as i64 + geometry.width as i64 / 2, geometry.y as i64 + geometry.height as i64 / 2)
+}
+
 /// The size of a gap.
 ///
 /// Note that a gap cannot be negative.
@@ -252,3 +430,143 @@ pub type WorkspaceIndex = usize;
 /// As this is an index (starting from 0), this means there will be
 /// `MAX_WORKSPACE_INDEX + 1` workspaces.
 pub static MAX_WORKSPACE_INDEX: WorkspaceIndex = 3;
+
+/// The ratio of the screen width taken by the master area, in percent.
+///
+/// Used by the [`MasterRatioSupport`](../wm/trait.MasterRatioSupport.html)
+/// trait and by the layouts with a master area.
+pub type MasterRatio = c_uint;
+
+/// The initial `MasterRatio`: the master area takes half of the screen.
+pub static DEFAULT_MASTER_RATIO: MasterRatio = 50;
+
+/// The lowest `MasterRatio`, so the master area never collapses.
+pub static MIN_MASTER_RATIO: MasterRatio = 10;
+
+/// The highest `MasterRatio`, so the other windows never collapse.
+pub static MAX_MASTER_RATIO: MasterRatio = 90;
+
+/// The step by which the `MasterRatio` grows or shrinks.
+pub static MASTER_RATIO_STEP: MasterRatio = 5;
+
+/// The width of the columns of a layout with columns, in percent of the
+/// width of the screen.
+///
+/// Used by the [`ColumnRatioSupport`](../wm/trait.ColumnRatioSupport.html)
+/// trait and by the layouts with columns.
+pub type ColumnRatio = c_uint;
+
+/// The initial `ColumnRatio`: two columns fit on the screen.
+pub static DEFAULT_COLUMN_RATIO: ColumnRatio = 50;
+
+/// The lowest `ColumnRatio`, so a column never collapses.
+pub static MIN_COLUMN_RATIO: ColumnRatio = 10;
+
+/// The highest `ColumnRatio`: a column is as wide as the screen.
+pub static MAX_COLUMN_RATIO: ColumnRatio = 100;
+
+/// The step by which the `ColumnRatio` grows or shrinks.
+pub static COLUMN_RATIO_STEP: ColumnRatio = 5;
+
+/// The number of windows in the master area.
+///
+/// Used by the [`MultiMasterSupport`](../wm/trait.MultiMasterSupport.html)
+/// trait and by the layouts with a master area.
+pub type MasterCount = usize;
+
+/// The initial `MasterCount`: a single master window.
+pub static DEFAULT_MASTER_COUNT: MasterCount = 1;
+
+/// The side of the screen where the master area of a layout sits.
+///
+/// Used by the
+/// [`MasterPositionSupport`](../wm/trait.MasterPositionSupport.html) trait
+/// and by the layouts with a master area.
+#[derive(Copy, Clone, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq, Hash)]
+pub enum MasterPosition {
+    /// The master area is on the left, the other windows on the right.
+    Left,
+    /// The master area is at the top, the other windows at the bottom.
+    Top,
+    /// The master area is on the right, the other windows on the left.
+    Right,
+    /// The master area is at the bottom, the other windows at the top.
+    Bottom,
+}
+
+impl MasterPosition {
+    /// Return the position that is used when none was chosen: `Top` on a
+    /// portrait screen, i.e. when the height is larger than the width,
+    /// `Left` otherwise.
+    pub fn default_for(screen: Screen) -> MasterPosition {
+        if screen.height > screen.width {
+            MasterPosition::Top
+        } else {
+            MasterPosition::Left
+        }
+    }
+
+    /// Return the previous or next position, clockwise: `Left`, `Top`,
+    /// `Right`, `Bottom` and back to `Left`.
+    pub fn cycle(&self, dir: PrevOrNext) -> MasterPosition {
+        use self::MasterPosition::*;
+        match (*self, dir) {
+            (Left, PrevOrNext::Next) | (Right, PrevOrNext::Prev) => Top,
+            (Top, PrevOrNext::Next) | (Bottom, PrevOrNext::Prev) => Right,
+            (Right, PrevOrNext::Next) | (Left, PrevOrNext::Prev) => Bottom,
+            (Bottom, PrevOrNext::Next) | (Top, PrevOrNext::Prev) => Left,
+        }
+    }
+
+    /// Return the mirrored position, e.g. the mirror of `Left` is `Right`
+    /// and vice versa.
+    pub fn mirror(&self) -> MasterPosition {
+        use self::MasterPosition::*;
+        match *self {
+            Left => Right,
+            Top => Bottom,
+            Right => Left,
+            Bottom => Top,
+        }
+    }
+
+    /// Return true if the master area is at the top or at the bottom, so the
+    /// master ratio applies to the height of the screen instead of its
+    /// width.
+    pub fn is_vertical(&self) -> bool {
+        *self == MasterPosition::Top || *self == MasterPosition::Bottom
+    }
+}
+
+/// The type of an output index.
+///
+/// An output is a physical monitor, it covers a rectangle of the screen
+/// described by a `Geometry`. Used by the
+/// [`MultiOutputSupport`](../wm/trait.MultiOutputSupport.html) trait as
+/// indices for outputs.
+pub type OutputIndex = usize;
+
+/// A change of the state of a window manager.
+///
+/// Used by the [`EventSupport`](../wm/trait.EventSupport.html) trait to
+/// tell the backend, and the callbacks of the user, what changed.
+#[derive(Copy, Clone, RustcDecodable, RustcEncodable, Debug, PartialEq, Eq, Hash)]
+pub enum WMEvent {
+    /// The window is now managed by the window manager.
+    WindowAdded(Window),
+    /// The window is no longer managed by the window manager.
+    WindowRemoved(Window),
+    /// Another window, or no window, is focused.
+    FocusChanged(Option<Window>),
+    /// The window started (`true`) or stopped (`false`) floating.
+    FloatingChanged(Window, bool),
+    /// The window was minimised (`true`) or unminimised (`false`).
+    MinimisedChanged(Window, bool),
+    /// Another window, or no window, is fullscreen.
+    FullscreenChanged(Option<Window>),
+    /// The workspace with the given index is now the current workspace.
+    WorkspaceSwitched(WorkspaceIndex),
+    /// The layout of the tiled windows was changed, e.g. from a vertical to
+    /// a horizontal layout.
+    LayoutChanged,
+}
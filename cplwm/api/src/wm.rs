@@ -25,8 +25,9 @@ use rustc_serialize::{Decodable, Encodable};
 use std::error;
 use std::fmt::Debug;
 
-use types::{GapSize, Geometry, PrevOrNext, Screen, Window, WindowLayout, WindowWithInfo,
-            WorkspaceIndex};
+use types::{COLUMN_RATIO_STEP, ColumnRatio, Direction, GapSize, Geometry, MASTER_RATIO_STEP,
+            MasterCount, MasterPosition, MasterRatio, OutputIndex, PrevOrNext, Screen, TabBar,
+            WMEvent, Window, WindowLayout, WindowProperties, WindowWithInfo, WorkspaceIndex};
 
 /// A basic window manager.
 ///
@@ -700,6 +701,579 @@ pub trait GapSupport: WindowManager {
     fn set_gap(&mut self, GapSize);
 }
 
+/// A window manager that keeps track of the properties of its windows.
+///
+/// The properties of a window, e.g. its class and title, are passed to the
+/// window manager as part of the `WindowWithInfo` given to `add_window`. An
+/// application can change them later on, e.g. a terminal changes its title
+/// when a different command is run. The backend then calls
+/// `set_window_properties` with the new properties.
+///
+/// This way the window manager can make decisions per application.
+pub trait WindowPropertiesSupport: WindowManager {
+    /// Return the properties of the given window.
+    ///
+    /// A default implementation is provided in terms of `get_window_info`.
+    fn get_window_properties(&self, window: Window) -> Result<WindowProperties, Self::Error> {
+        self.get_window_info(window).map(|info| info.properties)
+    }
+
+    /// Replace the properties of the given window.
+    ///
+    /// This function *should* return an appropriate error when the window is
+    /// not managed by the window manager.
+    ///
+    /// **Invariant**: after `set_window_properties(w, p)` succeeds,
+    /// `get_window_properties(w) == Ok(p)`. Nothing else changes, in
+    /// particular not the window layout.
+    fn set_window_properties(&mut self,
+                             window: Window,
+                             properties: WindowProperties)
+                             -> Result<(), Self::Error>;
+}
+
+/// A window manager with an [i3-style scratchpad].
+///
+/// The scratchpad is a place for windows that should not clutter the
+/// screen, but should be at hand, e.g. a terminal or a music player. A
+/// window moved to the scratchpad becomes floating and is hidden. Toggling
+/// the scratchpad shows a scratchpad window centered on the screen, on top
+/// of the other windows, toggling it again hides it again.
+///
+/// Hidden scratchpad windows are minimised windows, so they are not visible
+/// according to `get_window_layout`, but unlike other minimised windows they
+/// must not be focused by `cycle_focus`.
+///
+/// [i3-style scratchpad]: https://i3wm.org/docs/userguide.html#_scratchpad
+pub trait ScratchpadSupport: MinimiseSupport + FloatSupport {
+    /// Return the windows in the scratchpad, hidden or not.
+    ///
+    /// The window that will be shown by the next `toggle_scratchpad` comes
+    /// first.
+    fn get_scratchpad_windows(&self) -> Vec<Window>;
+
+    /// Move the given window to the scratchpad.
+    ///
+    /// The window becomes floating and is hidden. Nothing happens when the
+    /// window is already in the scratchpad and hidden.
+    ///
+    /// This function *should* return an appropriate error when the window is
+    /// not managed by the window manager.
+    ///
+    /// **Invariant**: if `move_to_scratchpad(w)` succeeds,
+    /// `get_scratchpad_windows()` contains `w`, `is_minimised(w)` returns
+    /// `true` and `get_window_info(w)` returns a floating window.
+    fn move_to_scratchpad(&mut self, window: Window) -> Result<(), Self::Error>;
+
+    /// Show or hide the scratchpad.
+    ///
+    /// * When the focused window is a visible scratchpad window, hide it.
+    /// * Otherwise, when a scratchpad window is visible, focus it.
+    /// * Otherwise, show the first hidden scratchpad window, centered on the
+    ///   screen, and focus it.
+    ///
+    /// Nothing happens when the scratchpad is empty. By hiding a window it
+    /// goes to the back of the scratchpad, so toggling the scratchpad
+    /// repeatedly cycles through its windows.
+    fn toggle_scratchpad(&mut self);
+}
+
+/// A window manager that keeps track of urgent windows.
+///
+/// An application marks its window as urgent when it needs the attention of
+/// the user, e.g. a chat application that receives a message. The backend
+/// reads the urgency hint of `WM_HINTS` and the
+/// `_NET_WM_STATE_DEMANDS_ATTENTION` state, calls `set_urgent` when they
+/// change, and draws the border of urgent windows in a distinct color.
+///
+/// Focusing a window clears its urgency, as the user is now looking at it,
+/// so the focused window is never urgent. Urgent windows can be hidden, e.g.
+/// minimised or on another workspace, `focus_urgent_window` brings the user
+/// to them.
+pub trait UrgencySupport: WindowManager {
+    /// Return the urgent windows, the window that became urgent most
+    /// recently comes first.
+    ///
+    /// **Invariant**: the returned windows are managed, the focused window
+    /// is not one of them.
+    fn get_urgent_windows(&self) -> Vec<Window>;
+
+    /// Return true if the given window is urgent.
+    ///
+    /// A default implementation is provided in terms of
+    /// `get_urgent_windows()`.
+    fn is_urgent(&self, window: Window) -> bool {
+        self.get_urgent_windows().contains(&window)
+    }
+
+    /// Mark the given window as urgent or not.
+    ///
+    /// Marking the focused window as urgent does nothing. Marking an urgent
+    /// window as urgent again makes it the most recent one. Nothing else
+    /// changes, in particular not the window layout.
+    ///
+    /// This function *should* return an appropriate error when the window is
+    /// not managed by the window manager.
+    ///
+    /// **Invariant**: after `set_urgent(w, true)` succeeds with a window `w`
+    /// that is not focused, `get_urgent_windows().first() == Some(&w)`.
+    ///
+    /// **Invariant**: after `set_urgent(w, false)` succeeds, `is_urgent(w)`
+    /// returns false.
+    fn set_urgent(&mut self, window: Window, urgent: bool) -> Result<(), Self::Error>;
+
+    /// Focus the window that became urgent most recently, which clears its
+    /// urgency. A window manager with workspaces switches to the workspace
+    /// of the window.
+    ///
+    /// Nothing happens when there are no urgent windows.
+    ///
+    /// A default implementation is provided in terms of
+    /// `get_urgent_windows()` and `focus_window()`.
+    fn focus_urgent_window(&mut self) -> Result<(), Self::Error> {
+        match self.get_urgent_windows().first() {
+            None => Ok(()),
+            Some(window) => self.focus_window(Some(*window)),
+        }
+    }
+}
+
+/// A window manager with sticky windows.
+///
+/// A sticky window is a floating window that is visible on every workspace,
+/// e.g. a video call or a clock. It still belongs to a single workspace, so
+/// `get_windows` contains it once, but the `WindowLayout` of every workspace
+/// contains it, with the same geometry and in the same stacking order. The
+/// backend makes windows sticky with `toggle_sticky` when a key is pressed
+/// or when they ask for it with `_NET_WM_STATE_STICKY`.
+///
+/// A window manager without workspaces only remembers which windows are
+/// sticky.
+pub trait StickySupport: FloatSupport {
+    /// Return the sticky windows.
+    ///
+    /// **Invariant**: the returned windows are managed and floating.
+    fn get_sticky_windows(&self) -> Vec<Window>;
+
+    /// Return true if the given window is sticky.
+    ///
+    /// A default implementation is provided in terms of
+    /// `get_sticky_windows()`.
+    fn is_sticky(&self, window: Window) -> bool {
+        self.get_sticky_windows().contains(&window)
+    }
+
+    /// Make the given window sticky or not sticky anymore.
+    ///
+    /// A tiled window becomes floating first. Making a sticky window tiled
+    /// with `toggle_floating` makes it not sticky anymore.
+    ///
+    /// This function *should* return an appropriate error when the window is
+    /// not managed by the window manager.
+    ///
+    /// **Invariant**: if `toggle_sticky(w)` succeeds, `is_sticky(w)` is
+    /// toggled, and when `w` is sticky `get_window_info(w)` returns a
+    /// floating window.
+    fn toggle_sticky(&mut self, window: Window) -> Result<(), Self::Error>;
+}
+
+/// A window manager that remembers the order in which windows were focused.
+///
+/// `cycle_focus` follows the order of the windows in the window manager,
+/// `alt_tab` goes back to the window that was used before the focused one,
+/// like Alt-Tab does in most desktop environments. The focus history also
+/// decides which window gets the focus when the focused window is removed
+/// or minimised: the most recently focused window that is still visible.
+///
+/// A window manager with workspaces keeps a focus history per workspace.
+pub trait FocusHistorySupport: WindowManager {
+    /// Return the windows in the order they were focused, the most recently
+    /// focused window comes first. When a window is focused, it is the
+    /// first one.
+    ///
+    /// Windows that were never focused are not in the history.
+    ///
+    /// **Invariant**: the returned windows are managed.
+    fn get_focus_history(&self) -> Vec<Window>;
+
+    /// Focus the most recently focused window other than the focused one.
+    ///
+    /// Pressing Alt-Tab repeatedly switches between the last two windows.
+    /// Nothing happens when there is no such window.
+    ///
+    /// A default implementation is provided in terms of
+    /// `get_focus_history()` and `focus_window()`.
+    fn alt_tab(&mut self) -> Result<(), Self::Error> {
+        let focused_window = self.get_focused_window();
+        match self.get_focus_history().into_iter().find(|w| Some(*w) != focused_window) {
+            None => Ok(()),
+            Some(window) => self.focus_window(Some(window)),
+        }
+    }
+}
+
+/// A window manager that can undo and redo the changes made by the user.
+///
+/// Only the operations a user performs on the layout are remembered, e.g.
+/// swapping, floating, minimising or changing the gap. Adding, removing and
+/// focusing windows are not operations that can be undone: windows come
+/// and go when applications open and close them.
+///
+/// Undoing an operation must never bring back a window that was removed in
+/// the meantime, and must keep the windows that were added in the meantime.
+pub trait UndoSupport: WindowManager {
+    /// Undo the most recent operation that was not undone yet.
+    ///
+    /// Nothing happens when there is nothing to undo.
+    ///
+    /// **Invariant**: `undo` does not change the result of `get_windows`,
+    /// except for the order of the windows.
+    fn undo(&mut self);
+
+    /// Redo the most recently undone operation.
+    ///
+    /// Nothing happens when there is nothing to redo, every operation that
+    /// can be undone makes it impossible to redo the undone operations.
+    ///
+    /// **Invariant**: `redo` does not change the result of `get_windows`,
+    /// except for the order of the windows.
+    fn redo(&mut self);
+}
+
+/// A window manager that reports the changes of its state as events.
+///
+/// The backend takes the events after every change and passes them to the
+/// callbacks of the user, e.g. to log them or to update a status bar. The
+/// events are kept in the order in which they happened.
+pub trait EventSupport: WindowManager {
+    /// Return the events that happened since the previous call, the oldest
+    /// event comes first, and forget them.
+    ///
+    /// **Invariant**: calling `take_events()` twice in a row returns an
+    /// empty `Vec` the second time.
+    fn take_events(&mut self) -> Vec<WMEvent>;
+}
+
+/// A window manager that can check the invariants of its current state.
+///
+/// The invariants are the ones documented on the methods of the other
+/// traits that only depend on the state, e.g. the focused window must be
+/// managed and the window layout may only contain managed windows. The
+/// backend checks them after every event in debug builds, so a bug shows up
+/// as a report in the log instead of windows that are silently tiled wrong.
+pub trait InvariantCheck: WindowManager {
+    /// Return a description of every invariant that does not hold, an empty
+    /// `Vec` when all of them hold.
+    ///
+    /// Every description is a single line that mentions the windows
+    /// involved.
+    fn check_invariants(&self) -> Vec<String>;
+}
+
+/// A window manager that can move the focus and windows in a direction.
+///
+/// Unlike `cycle_focus` and `swap_windows`, which follow the order of the
+/// windows in the window manager, these methods follow what is on the
+/// screen: the window in a direction is the nearest visible window in that
+/// direction according to the geometries of `get_window_layout`, see
+/// [`get_window_in_direction`]. Floating windows are included.
+///
+/// [`get_window_in_direction`]: ../types/struct.WindowLayout.html#method.get_window_in_direction
+pub trait DirectionalSupport: TilingSupport {
+    /// Focus the window in the given direction of the focused window.
+    ///
+    /// Nothing happens when no window is focused or when there is no window
+    /// in that direction.
+    ///
+    /// A default implementation is provided in terms of `get_window_layout`
+    /// and `focus_window`.
+    fn focus_direction(&mut self, dir: Direction) -> Result<(), Self::Error> {
+        let layout = self.get_window_layout();
+        match layout.focused_window.and_then(|w| layout.get_window_in_direction(w, dir)) {
+            None => Ok(()),
+            Some(window) => self.focus_window(Some(window)),
+        }
+    }
+
+    /// Swap the focused window with the window in the given direction.
+    ///
+    /// When both windows are tiled, they swap their position in the tiling
+    /// layout. When both windows are floating, they swap their geometry. The
+    /// focused window stays focused.
+    ///
+    /// Nothing happens when no window is focused, when there is no window
+    /// in that direction, or when one window is tiled and the other one is
+    /// floating.
+    fn swap_direction(&mut self, dir: Direction);
+}
+
+/// A tiling window manager with an adjustable master area.
+///
+/// The master ratio is the width of the master area, in percent of the width
+/// of the screen. The remaining width is used by the other tiled windows.
+/// When the master area sits at the top or at the bottom, see
+/// [`MasterPositionSupport`](trait.MasterPositionSupport.html), the ratio
+/// applies to the height instead.
+/// The ratio is part of the state of the window manager: it is kept when the
+/// screen is resized and when the window manager is serialised.
+///
+/// The ratio is always kept between
+/// [`MIN_MASTER_RATIO`](../types/static.MIN_MASTER_RATIO.html) and
+/// [`MAX_MASTER_RATIO`](../types/static.MAX_MASTER_RATIO.html), so neither
+/// the master area nor the other windows collapse. When there is only a
+/// single tiled window, it still takes the whole screen.
+pub trait MasterRatioSupport: TilingSupport {
+    /// Return the current master ratio.
+    ///
+    /// Initially
+    /// [`DEFAULT_MASTER_RATIO`](../types/static.DEFAULT_MASTER_RATIO.html).
+    fn get_master_ratio(&self) -> MasterRatio;
+
+    /// Set the master ratio.
+    ///
+    /// A ratio lower than `MIN_MASTER_RATIO` or higher than
+    /// `MAX_MASTER_RATIO` is clamped.
+    ///
+    /// **Invariant**: after `set_master_ratio(r)` with a ratio `r` between
+    /// `MIN_MASTER_RATIO` and `MAX_MASTER_RATIO`, `get_master_ratio() == r`.
+    fn set_master_ratio(&mut self, ratio: MasterRatio);
+
+    /// Grow the master area by
+    /// [`MASTER_RATIO_STEP`](../types/static.MASTER_RATIO_STEP.html).
+    ///
+    /// A default implementation is provided in terms of `get_master_ratio`
+    /// and `set_master_ratio`.
+    fn grow_master(&mut self) {
+        let ratio = self.get_master_ratio();
+        self.set_master_ratio(ratio.saturating_add(MASTER_RATIO_STEP));
+    }
+
+    /// Shrink the master area by
+    /// [`MASTER_RATIO_STEP`](../types/static.MASTER_RATIO_STEP.html).
+    ///
+    /// A default implementation is provided in terms of `get_master_ratio`
+    /// and `set_master_ratio`.
+    fn shrink_master(&mut self) {
+        let ratio = self.get_master_ratio();
+        self.set_master_ratio(ratio.saturating_sub(MASTER_RATIO_STEP));
+    }
+}
+
+/// A tiling window manager with several master windows.
+///
+/// The first `get_master_count()` tiled windows are the master windows, they
+/// are stacked on top of each other in the master area. The other tiled
+/// windows are stacked in the remaining area. For example with two master
+/// windows:
+///
+/// ```text
+/// +----------+----------+
+/// |    1     |    3     |
+/// |          +----------+
+/// +----------+    4     |
+/// |    2     +----------+
+/// |          |    5     |
+/// +----------+----------+
+/// ```
+///
+/// `get_master_window` returns the first master window, `swap_with_master`
+/// swaps the given window with the first master window. `swap_windows` moves
+/// the focused window through all the tiled windows, so a window can move
+/// from the master area to the other area and back.
+///
+/// There is always at least one master window (when there are tiled
+/// windows). The number of master windows is part of the state of the
+/// window manager: it is kept when the screen is resized and when the window
+/// manager is serialised.
+pub trait MultiMasterSupport: TilingSupport {
+    /// Return the master windows, in the order in which they are stacked.
+    ///
+    /// **Invariant**: `get_master_windows().first()` corresponds to
+    /// `get_master_window()`.
+    ///
+    /// **Invariant**: there are never more than `get_master_count()` master
+    /// windows, there are fewer only when there are not enough tiled windows.
+    fn get_master_windows(&self) -> Vec<Window>;
+
+    /// Return the current number of master windows.
+    ///
+    /// Initially
+    /// [`DEFAULT_MASTER_COUNT`](../types/static.DEFAULT_MASTER_COUNT.html).
+    fn get_master_count(&self) -> MasterCount;
+
+    /// Set the number of master windows.
+    ///
+    /// A count of 0 is replaced by 1.
+    ///
+    /// **Invariant**: after `set_master_count(n)` with some `n > 0`,
+    /// `get_master_count() == n`.
+    fn set_master_count(&mut self, count: MasterCount);
+
+    /// Add a window to the master area.
+    ///
+    /// A default implementation is provided in terms of `get_master_count`
+    /// and `set_master_count`.
+    fn increase_master_count(&mut self) {
+        let count = self.get_master_count();
+        self.set_master_count(count.saturating_add(1));
+    }
+
+    /// Remove a window from the master area, the last master window is never
+    /// removed.
+    ///
+    /// A default implementation is provided in terms of `get_master_count`
+    /// and `set_master_count`.
+    fn decrease_master_count(&mut self) {
+        let count = self.get_master_count();
+        self.set_master_count(count.saturating_sub(1));
+    }
+}
+
+/// A tiling window manager whose master area can sit on any side of the
+/// screen.
+///
+/// The master area sits on the left by default, or at the top on a portrait
+/// screen, i.e. when the height of the screen is larger than its width. This
+/// default follows the screen: it changes when `resize_screen` turns the
+/// screen from landscape to portrait or back. Once a position is chosen with
+/// `set_master_position`, it is kept until `None` is set again.
+///
+/// With the master area at the top or at the bottom, the master windows are
+/// placed side by side, the other windows as well, and the master ratio
+/// applies to the height of the screen. The position is part of the state of
+/// the window manager: it is kept when the window manager is serialised.
+pub trait MasterPositionSupport: TilingSupport {
+    /// Return where the master area currently sits.
+    fn get_master_position(&self) -> MasterPosition;
+
+    /// Choose where the master area sits, `None` returns to the default
+    /// position for the screen.
+    ///
+    /// **Invariant**: after `set_master_position(Some(p))`,
+    /// `get_master_position() == p`, also after `resize_screen`.
+    fn set_master_position(&mut self, position: Option<MasterPosition>);
+
+    /// Move the master area to the previous or next side of the screen,
+    /// clockwise, see [`MasterPosition::cycle`].
+    ///
+    /// A default implementation is provided in terms of
+    /// `get_master_position` and `set_master_position`.
+    ///
+    /// [`MasterPosition::cycle`]: ../types/enum.MasterPosition.html#method.cycle
+    fn cycle_master_position(&mut self, dir: PrevOrNext) {
+        let position = self.get_master_position().cycle(dir);
+        self.set_master_position(Some(position));
+    }
+
+    /// Mirror the layout: move the master area to the opposite side of the
+    /// screen.
+    ///
+    /// A default implementation is provided in terms of
+    /// `get_master_position` and `set_master_position`.
+    fn mirror_master_position(&mut self) {
+        let position = self.get_master_position().mirror();
+        self.set_master_position(Some(position));
+    }
+}
+
+/// A window manager that can switch between several tiling layouts.
+///
+/// The window manager keeps a list of [`Layout`]s, one of which is active.
+/// The active layout determines the geometries of the tiled windows, which
+/// windows are tiled and in which order stays the responsibility of the
+/// window manager. Floating windows and the fullscreen window are unaffected
+/// by the layout.
+///
+/// As the layouts are part of the state of the window manager, the active
+/// layout is kept when the window manager is serialised.
+///
+/// [`Layout`]: ../layout/trait.Layout.html
+pub trait LayoutSupport: WindowManager {
+    /// Activate the previous or next layout in the list of layouts.
+    ///
+    /// The list wraps around: the next layout of the last layout is the
+    /// first one. With a single layout nothing happens.
+    ///
+    /// The tiled windows are given the geometries of the newly activated
+    /// layout, the focused window and the order of the windows stay the
+    /// same.
+    fn cycle_layout(&mut self, dir: PrevOrNext);
+}
+
+/// A window manager with layouts that arrange the tiled windows in columns
+/// of an adjustable width, e.g. a scrolling layout.
+///
+/// The column ratio is the width of every column, in percent of the width of
+/// the screen. It is shared by all the layouts with columns and is part of
+/// the state of the window manager: it is kept when the active layout
+/// changes, when the screen is resized and when the window manager is
+/// serialised. The other layouts ignore it.
+///
+/// The ratio is always kept between
+/// [`MIN_COLUMN_RATIO`](../types/static.MIN_COLUMN_RATIO.html) and
+/// [`MAX_COLUMN_RATIO`](../types/static.MAX_COLUMN_RATIO.html).
+pub trait ColumnRatioSupport: LayoutSupport {
+    /// Return the current column ratio.
+    ///
+    /// Initially
+    /// [`DEFAULT_COLUMN_RATIO`](../types/static.DEFAULT_COLUMN_RATIO.html).
+    fn get_column_ratio(&self) -> ColumnRatio;
+
+    /// Set the column ratio.
+    ///
+    /// A ratio lower than `MIN_COLUMN_RATIO` or higher than
+    /// `MAX_COLUMN_RATIO` is clamped.
+    ///
+    /// **Invariant**: after `set_column_ratio(r)` with a ratio `r` between
+    /// `MIN_COLUMN_RATIO` and `MAX_COLUMN_RATIO`, `get_column_ratio() == r`.
+    fn set_column_ratio(&mut self, ratio: ColumnRatio);
+
+    /// Widen the columns by
+    /// [`COLUMN_RATIO_STEP`](../types/static.COLUMN_RATIO_STEP.html).
+    ///
+    /// A default implementation is provided in terms of `get_column_ratio`
+    /// and `set_column_ratio`.
+    fn grow_columns(&mut self) {
+        let ratio = self.get_column_ratio();
+        self.set_column_ratio(ratio.saturating_add(COLUMN_RATIO_STEP));
+    }
+
+    /// Narrow the columns by
+    /// [`COLUMN_RATIO_STEP`](../types/static.COLUMN_RATIO_STEP.html).
+    ///
+    /// A default implementation is provided in terms of `get_column_ratio`
+    /// and `set_column_ratio`.
+    fn shrink_columns(&mut self) {
+        let ratio = self.get_column_ratio();
+        self.set_column_ratio(ratio.saturating_sub(COLUMN_RATIO_STEP));
+    }
+}
+
+/// A window manager that reports the tab bars of its tabbed layouts.
+///
+/// When the active layout is tabbed, i.e. it has a tab bar (see
+/// [`get_tab_bar_geometry`]), only one tiled window is visible, the others
+/// are stacked behind it. The backend draws the returned tab bars, so the
+/// user can see and click on the hidden tiled windows.
+///
+/// [`get_tab_bar_geometry`]: ../layout/trait.Layout.html#method.get_tab_bar_geometry
+pub trait TabBarSupport: LayoutSupport {
+    /// Return the tab bars to draw.
+    ///
+    /// A window manager returns a tab bar when its active layout is tabbed
+    /// and there are visible tiled windows. The tabs are the visible tiled
+    /// windows in the order of the window manager, the active tab is the
+    /// focused window when it is tiled. When a floating window is focused,
+    /// the active tab is the tiled window that stays visible.
+    ///
+    /// When there is a fullscreen window, there are no tab bars.
+    ///
+    /// **Invariant**: the active window of a tab bar is the only window of
+    /// the tab bar that is not completely covered by another tiled window in
+    /// `get_window_layout`.
+    fn get_tab_bars(&self) -> Vec<TabBar>;
+}
+
 
 /// A window manager that has multiple workspaces.
 ///
@@ -765,3 +1339,56 @@ pub trait MultiWorkspaceSupport<WM: WindowManager>: WindowManager {
     /// MAX_WORKSPACE_INDEX` is not true.
     fn switch_workspace(&mut self, index: WorkspaceIndex) -> Result<(), Self::Error>;
 }
+
+/// A window manager that supports multiple outputs (monitors).
+///
+/// The screen can be covered by several outputs, each output is a rectangle
+/// of the screen with its own origin, see
+/// [`OutputIndex`](../types/type.OutputIndex.html). Every output has its own
+/// window manager of type `WM`, with a screen that has the size of the
+/// output, so every output gets its own layout area and focused window.
+///
+/// One of the outputs is the current output, new windows are added to it and
+/// the methods that have no window argument concern this output. The
+/// `WindowLayout` of this window manager contains the windows of all the
+/// outputs, with geometries relative to the screen instead of to the output.
+///
+/// Initially there is a single output that covers the whole screen.
+pub trait MultiOutputSupport<WM: WindowManager>: WindowManager {
+    /// Return the outputs, the index in the `Vec` is the `OutputIndex`.
+    ///
+    /// **Invariant**: there is always at least one output.
+    fn get_outputs(&self) -> Vec<Geometry>;
+
+    /// Replace the outputs, e.g. after a monitor was (dis)connected.
+    ///
+    /// The windows of outputs that no longer exist are moved to a remaining
+    /// output. An empty `Vec` is ignored.
+    ///
+    /// **Invariant**: after `set_outputs(outputs)` with a non-empty
+    /// `outputs`, `get_outputs() == outputs`, and `get_windows()` contains
+    /// the same windows as before.
+    fn set_outputs(&mut self, outputs: Vec<Geometry>);
+
+    /// Return the index of the current output.
+    fn get_current_output_index(&self) -> OutputIndex;
+
+    /// Get an immutable borrow of the window manager of the output at the
+    /// given index.
+    ///
+    /// Return an error when the index is invalid.
+    fn get_output(&self, index: OutputIndex) -> Result<&WM, Self::Error>;
+
+    /// Get a mutable borrow of the window manager of the output at the given
+    /// index.
+    ///
+    /// Return an error when the index is invalid.
+    fn get_output_mut(&mut self, index: OutputIndex) -> Result<&mut WM, Self::Error>;
+
+    /// Make the output at the given index the current output.
+    ///
+    /// The focused window of that output becomes the focused window.
+    ///
+    /// Return an error when the index is invalid.
+    fn focus_output(&mut self, index: OutputIndex) -> Result<(), Self::Error>;
+}
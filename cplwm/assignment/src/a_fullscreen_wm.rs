@@ -45,6 +45,9 @@ use std::fmt;
 use std::collections::VecDeque;
 use cplwm_api::types::{PrevOrNext, Screen, Window, WindowLayout, WindowWithInfo};
 use cplwm_api::wm::WindowManager;
+use cplwm_api::wm::InvariantCheck;
+
+use n_invariants::check_window_manager;
 
 /// Window manager aliase
 pub type WMName = FullscreenWM;
@@ -138,7 +141,9 @@ impl WindowManager for FullscreenWM {
     /// If list is not empty and focused=true I get the last element focused
     /// window, otherwise None
     fn get_focused_window(&self) -> Option<Window> {
-        if !self.windows.is_empty() || !self.focused {
+        // without windows there is nothing to focus, even when focused =
+        // false
+        if !self.windows.is_empty() {
             // I use unwrap() because the if test ensure that *windows* has
             // *window*s
             if self.windows.len() > 1 {
@@ -165,6 +170,7 @@ impl WindowManager for FullscreenWM {
                 geometry: self.screen.to_geometry(),
                 float_or_tile: window_with_info.float_or_tile,
                 fullscreen: window_with_info.fullscreen,
+                properties: window_with_info.properties,
             };
             self.focused = true;
             Ok(self.windows.push_back(fullscreen_window))
@@ -193,7 +199,7 @@ impl WindowManager for FullscreenWM {
     /// if there is an empty vec or the focused = false (hidden windows) it
     /// is return an new WindowLayout
     fn get_window_layout(&self) -> WindowLayout {
-        if !self.windows.is_empty() || !self.focused {
+        if !self.windows.is_empty() {
             let last_index = self.windows.len() - 1;
             // I used unwrap because it is already tested that there is at
             // least one element in Vec
@@ -311,15 +317,26 @@ impl WindowManager for FullscreenWM {
 
 
 
+impl InvariantCheck for FullscreenWM {
+    /// checks the invariants of the window manager.
+    fn check_invariants(&self) -> Vec<String> {
+        check_window_manager(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
     // We have to import `TillingWM` from the super module.
     use super::FullscreenWM;
+
     // We have to repeat the imports we did in the super module.
     use cplwm_api::wm::WindowManager;
     use cplwm_api::types::*;
 
+    // The generic tests of the traits that this window manager implements.
+    conformance_tests!(FullscreenWM: window_manager);
+
     // We define a static variable for the screen we will use in the tests.
     // You can just as well define it as a local variable in your tests.
     static SCREEN: Screen = Screen {
@@ -561,4 +578,16 @@ mod tests {
         wm.resize_screen(SCREEN2);
         assert_eq!(wm.get_screen(), SCREEN2);
     }
+
+    #[test]
+    fn test_empty_without_focus() {
+
+        let mut wm = FullscreenWM::new(SCREEN);
+
+        // Without windows and without focus there is nothing to show, this
+        // used to panic because the index of the last window was computed.
+        wm.focus_window(None).unwrap();
+        assert_eq!(None, wm.get_focused_window());
+        assert_eq!(WindowLayout::new(), wm.get_window_layout());
+    }
 }
@@ -55,6 +55,9 @@ use std::fmt;
 use cplwm_api::types::{Geometry, PrevOrNext, Screen, Window, WindowLayout, WindowWithInfo};
 use cplwm_api::wm::WindowManager;
 use cplwm_api::wm::TilingSupport;
+use cplwm_api::wm::InvariantCheck;
+
+use n_invariants::{check_tiling, check_window_manager};
 
 
 /// Window manager aliase.
@@ -490,16 +493,29 @@ impl TilingSupport for TillingWM {
 }
 
 
+impl InvariantCheck for TillingWM {
+    /// checks the invariants of the window manager and the tiling.
+    fn check_invariants(&self) -> Vec<String> {
+        let mut violations = check_window_manager(self);
+        violations.extend(check_tiling(self));
+        violations
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
     // We have to import `TillingWM` from the super module.
     use super::TillingWM;
+
     // We have to repeat the imports we did in the super module.
     use cplwm_api::wm::WindowManager;
     use cplwm_api::wm::TilingSupport;
     use cplwm_api::types::*;
 
+    // The generic tests of the traits that this window manager implements.
+    conformance_tests!(TillingWM: window_manager, tiling);
+
     // We define a static variable for the screen we will use in the tests.
     // You can just as well define it as a local variable in your tests.
     static SCREEN: Screen = Screen {
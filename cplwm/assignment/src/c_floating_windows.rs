@@ -30,755 +30,46 @@
 //!
 //! ## General approach
 //!
-//! The approach now is quite similar to b_tillin_vm, but now
-//! update_geometries is changed. First processing tiled windows
-//! and then floating windows.
+//! The floating windows are added by the *Floating* layer of the
+//! [`m_layers`] module on top of the tiling window manager of the previous
+//! assignment, so the tiling logic is not copied anymore. The layer keeps
+//! the floating windows and their geometries itself, the tiled windows are
+//! laid out by *TillingWM*.
 //!
-//! Now the WindowWithInfo structure is decomposed and a new structured is
-//! created since now it is important to save the floating geometry
-//! of the window, even when it is a tiled one.
+//! The tiled windows come first in the window layout, then the floating
+//! windows in the order in which they became floating, the focused floating
+//! window is the last one so it is drawn on top. A floating window that is
+//! tiled is added at the end of the tiles and keeps its floating geometry
+//! for when it floats again.
 //!
-//! In case a floating window is changed to tiled window, the order is kept
-//! it so get_window_layout will return such windows in the order
-//! of the current *windows* vec.
+//! [`m_layers`]: ../m_layers/index.html
 
 // Add imports here
-use std::error;
-use std::fmt;
-use cplwm_api::types::{FloatOrTile, Geometry, PrevOrNext, Screen, Window, WindowLayout,
-                       WindowWithInfo};
-use cplwm_api::wm::WindowManager;
-use cplwm_api::wm::TilingSupport;
-use cplwm_api::wm::FloatSupport;
+use b_tiling_wm::TillingWM;
+use m_layers::Floating;
 
 
 /// Window manager aliase.
 pub type WMName = FloatingWM;
 
-/// The FloatingWindow struct
-#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
-pub struct FloatingWindow {
-    /// The window.
-    pub window: Window,
-    /// The geometry of the window.
-    pub geometry: Geometry,
-    /// The saved floating geometry of the window.
-    pub saved_geometry: Geometry,
-    /// Indicate whether the window should float or tile.
-    pub float_or_tile: FloatOrTile,
-    /// Indicate whether the window should be displayed fullscreen or not.
-    /// Although it is not supported in this WM
-    pub fullscreen: bool,
-}
-
-/// The FloatingWM struct
-#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
-pub struct FloatingWM {
-    /// A vector of FloatingWindow, now the first part contains tiled windows
-    /// while the second
-    /// part contains floatign windows
-    pub windows: Vec<FloatingWindow>,
-    /// The size of the screen.
-    pub screen: Screen,
-    /// The index of the focused window in the *windows*, if there is no
-    /// focused window a None is found.
-    pub index_foused_window: Option<usize>,
-}
-
-/// Supported functions
-impl FloatingWM {
-    /// return the next tiled window from the given index
-    ///
-    /// it loops over the *windows* vec in its current order, it loops over
-    /// it until either the next right side tile index is found or
-    /// it reached the given index (in which a None is returned)
-    pub fn get_next_tile_index(&self, index: usize, saved: usize) -> Option<usize> {
-        let mut next_index = 0;
-        if self.windows.len() - 1 > index {
-            next_index = index + 1;
-        }
-        if next_index == saved {
-            None
-        } else {
-            if self.windows.get(next_index).unwrap().float_or_tile == FloatOrTile::Tile {
-                Some(next_index)
-            } else {
-                self.get_next_tile_index(next_index, saved)
-            }
-        }
-    }
-
-    /// return the previous tiled window from the given index
-    ///
-    /// it loops over the *windows* vec in its current order, it loops over
-    /// it until either the next left side tile index is found or
-    /// it reached the given index (in which a None is returned)
-    pub fn get_prev_tile_index(&self, index: usize, saved: usize) -> Option<usize> {
-        let mut prev_index = self.windows.len() - 1;
-        if index != 0 {
-            prev_index = index - 1;
-        };
-        if prev_index == saved {
-            None
-        } else {
-            if self.windows.get(prev_index).unwrap().float_or_tile == FloatOrTile::Tile {
-                Some(prev_index)
-            } else {
-                self.get_prev_tile_index(prev_index, saved)
-            }
-        }
-    }
-
-    /// get the master window index
-    ///
-    /// the logic of the b_tilling_wm reminds, the first tiled element of the
-    /// list is the master window,
-    fn get_master_index(&self) -> Option<usize> {
-        if !self.windows.is_empty() {
-            self.windows.iter().position(|w| (*w).float_or_tile == FloatOrTile::Tile)
-        } else {
-            None
-        }
-    }
-
-    /// returns the index where the first floating window starts
-    ///
-    /// if there is no  floating window, a None element is returns
-    fn get_partion_index(&self) -> Option<usize> {
-        if !self.windows.is_empty() {
-            self.windows.iter().position(|w| (*w).float_or_tile == FloatOrTile::Float)
-        } else {
-            None
-        }
-    }
-
-    /// This method calculated the geometries of windows.
-    fn update_geometries(&mut self) {
-        if !self.windows.is_empty() {
-
-            // Divisor now should be update to just the tiled windows
-            let non_tiled_windows = self.get_floating_windows().len() + 1;
-            let total_windows = self.windows.len();
-            // let divisor = self.windows.len() - non_tiled_windows;
-
-            // if the divisor is greater than 0 we need to calculate slave
-            // windows
-            // if divisor > 0{
-            if total_windows > non_tiled_windows {
-                let divisor = total_windows - non_tiled_windows;
-                let divisor_2 = divisor as u32;
-                let height_side = self.screen.height / divisor_2;
-                let width_side = self.screen.width / 2;
-                let x_point = (self.screen.width / 2) as i32;
-                // It is already tested that there is more than 1 window,
-                // hence one can use unwrap method being sure that a
-                // Some intance of option will be returned
-                let master_window = self.get_master_window().unwrap();
-
-                let mut y_point = 0 as i32;
-
-                for floating_window in self.windows
-                    .iter_mut()
-                    .filter(|x| (*x).float_or_tile == FloatOrTile::Tile) {
-                    if master_window != floating_window.window {
-                        // I calculate the values of the secondary windows
-                        // (right windows)
-                        let rigth_geometry = Geometry {
-                            x: x_point,
-                            y: y_point,
-                            width: width_side,
-                            height: height_side,
-                        };
-                        floating_window.geometry = rigth_geometry;
-                        y_point += (height_side) as i32;
-
-                    } else {
-                        // I calculate the values for master window
-                        let master_geometry = Geometry {
-                            x: 0,
-                            y: 0,
-                            width: width_side,
-                            height: self.screen.height,
-                        };
-                        floating_window.geometry = master_geometry;
-                    }
-                }
-            } else {
-                // It could be the posibility that there is just one window
-                // (the master window)
-                match self.get_master_index() {
-                    None => (),
-                    Some(i) => {
-                        // unwrap() is used because I ensure there is at
-                        // least one element
-                        let window = self.windows.get_mut(i).unwrap();
-                        window.geometry = self.screen.to_geometry();
-                    }
-                }
-            }
-        };
-    }
-}
-
-/// The errors that this window manager can return.
-#[derive(Debug)]
-pub enum FloatingWMError {
-    /// This window is not known by the window manager.
-    UnknownWindow(Window),
-    /// This window is already managed by this window manager
-    ManagedWindow(Window),
-    /// This window is not a floating window
-    NoFloatingWindow(Window),
-    /// This window is not a tiled window
-    NoTiledWindow(Window),
-}
-
-impl fmt::Display for FloatingWMError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            FloatingWMError::UnknownWindow(ref window) => write!(f, "Unknown window: {}", window),
-            FloatingWMError::ManagedWindow(ref window) => {
-                write!(f, "Window {} is already managed", window)
-            }
-            FloatingWMError::NoFloatingWindow(ref window) => {
-                write!(f, "Window {} is not floating", window)
-            }
-            FloatingWMError::NoTiledWindow(ref window) => {
-                write!(f, "Window {} is not tiled", window)
-            }
-        }
-    }
-}
-
-impl error::Error for FloatingWMError {
-    fn description(&self) -> &'static str {
-        match *self {
-            FloatingWMError::UnknownWindow(_) => "Unknown window",
-            FloatingWMError::ManagedWindow(_) => "Window is already managed",
-            FloatingWMError::NoFloatingWindow(_) => "Window is not floating",
-            FloatingWMError::NoTiledWindow(_) => "Window is not tiled",
-        }
-    }
-}
-
-
-impl WindowManager for FloatingWM {
-    type Error = FloatingWMError;
-
-    /// The FloatingWM constructor.
-    ///
-    /// windows is initialised as empty vec, screen as the given screen and
-    ///  focused index as None.
-    fn new(screen: Screen) -> FloatingWM {
-        FloatingWM {
-            windows: Vec::new(),
-            screen: screen,
-            index_foused_window: None,
-        }
-    }
-
-    /// Returns all the managed windows in the window manager.
-    fn get_windows(&self) -> Vec<Window> {
-        let mut temp_windows = Vec::new();
-        for window_with_info in self.windows.iter() {
-            temp_windows.push(window_with_info.window.clone());
-        }
-        temp_windows
-    }
-
-    /// gets the current focused window.
-    fn get_focused_window(&self) -> Option<Window> {
-        if !self.windows.is_empty() {
-            match self.index_foused_window {
-                None => None,
-                Some(index) => Some(self.windows.get(index).unwrap().window),
-            }
-        } else {
-            None
-        }
-    }
-
-    /// adds new window_with_info to the vec windows
-    ///
-    /// the fullscreen windows are accepted and added, but they are treated
-    /// as tiled or floating window. The given geometry by the
-    /// window_with_info is set as the saved_geometry and geometry attributes
-    /// of the FloatingWindow structure.
-    ///
-    /// if the given window has an attribute FloatOrTile::Tile then the
-    /// geometries for tiled windows should be updated.
-    ///
-    /// Now add_window adds the tiled and floating windows in order, so the
-    /// first part of the vec contains
-    /// the tiled windows while the second part are the floating windows
-    ///
-    /// returns an ManagedWindow error if the given window_with_info is
-    /// already managed by the window manager.
-    fn add_window(&mut self, window_with_info: WindowWithInfo) -> Result<(), Self::Error> {
-        if !self.is_managed(window_with_info.window) {
-            let floating_window = FloatingWindow {
-                window: window_with_info.window,
-                geometry: window_with_info.geometry,
-                saved_geometry: window_with_info.geometry,
-                float_or_tile: window_with_info.float_or_tile,
-                fullscreen: window_with_info.fullscreen,
-            };
-            if window_with_info.float_or_tile == FloatOrTile::Float {
-                // if there is a floating window, it is inserted at the end
-                // of the vec
-                self.windows.push(floating_window);
-                let temp = self.windows.len() - 1;
-                self.index_foused_window = Some(temp);
-            } else {
-                // if not, I get the index where the first floating window
-                // start and insert the tiled window there, also the
-                // geometries should be updated.
-                match self.get_partion_index() {
-                    None => {
-                        self.windows.push(floating_window);
-                        let temp = self.windows.len() - 1;
-                        self.index_foused_window = Some(temp);
-                    }
-                    Some(partion_index) => {
-                        self.windows.insert(partion_index, floating_window);
-                        self.index_foused_window = Some(partion_index);
-                    }
-                };
-                self.update_geometries();
-            };
-            Ok(())
-        } else {
-            Err(FloatingWMError::ManagedWindow(window_with_info.window))
-        }
-    }
-
-    /// removes the given window from the window manager.
-    ///
-    /// Every time that an element is remove the index_foused_window should
-    /// be updated if it is necessary.
-    /// Important to noticy here is that when the focused element is the same
-    /// as the removed element, no focused window is set.
-    fn remove_window(&mut self, window: Window) -> Result<(), Self::Error> {
-        match self.windows.iter().position(|w| (*w).window == window) {
-            None => Err(FloatingWMError::UnknownWindow(window)),
-            Some(i) => {
-                // unwrap() is used because the some(i) statement ensures at
-                // least one element
-                let temp_window = self.windows.get(i).unwrap().clone();
-                self.windows.remove(i);
-
-                if temp_window.float_or_tile == FloatOrTile::Tile {
-                    self.update_geometries();
-                };
-                match self.index_foused_window {
-                    None => Ok(()),
-
-                    Some(index) => {
-                        // If the focused_element is the one that is erased,
-                        // None focused element
-                        if index == i {
-                            self.index_foused_window = None;
-                            Ok(())
-                        } else {
-                            // If the focused_element in the right side of
-                            // the vector the focused elemente is kept it,
-                            // otherwise the focused index is decreased by
-                            // one.
-                            let mut temp = index;
-                            if index > i {
-                                temp = index - 1;
-                            }
-                            self.index_foused_window = Some(temp);
-                            Ok(())
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-
-
-
-    /// returns the layout of all managed windows.
-    ///
-    /// The array is already order so, no need to rearrange the vector except
-    /// when there is a focused floating window
-    fn get_window_layout(&self) -> WindowLayout {
-
-        if !self.windows.is_empty() {
-
-            let mut temp_windows = Vec::new();
-
-            for window_info in self.windows.iter() {
-                temp_windows.push((window_info.window.clone(), window_info.geometry.clone()))
-            }
-
-            let temp_focused_window = match self.index_foused_window {
-                None => None,
-                Some(index) => {
-                    // unwrap() is used because the some(i) statement ensures
-                    // at least one element
-                    let window_type = self.windows.get(index).unwrap();
-                    match self.get_partion_index() {
-                        None => Some(self.windows.get(index).unwrap().window),
-                        // if a focused window is a floating one, then the
-                        // temp_windows is reorder to put the focused window
-                        // at the very end of the vec
-                        Some(partion_index) => {
-                            if partion_index >= index &&
-                               window_type.float_or_tile == FloatOrTile::Float {
-                                match temp_windows.iter().position(|w| (*w)
-                                    == (window_type.window,window_type.
-                                        geometry)) {
-										None => (),
-                                        Some(real_index) => {
-											let (temp_window,temp_geometry)
-                                            = temp_windows.get(real_index).
-                                            unwrap().clone();
-											temp_windows.remove(real_index);
-											temp_windows.push((temp_window,
-                                                temp_geometry));
-										},
-								};
-                            };
-                            // unwrap() is used because the
-                            // some(partion_index) statement ensures at least
-                            // one element
-                            Some(self.windows.get(index).unwrap().window)
-                        }
-                    }
-                }
-            };
-
-            WindowLayout {
-                focused_window: temp_focused_window,
-                windows: temp_windows,
-            }
-
-        } else {
-            WindowLayout::new()
-        }
-    }
-
-    /// set the focused window in the window manager with the given window.
-    fn focus_window(&mut self, window: Option<Window>) -> Result<(), Self::Error> {
-        match window {
-            None => {
-                self.index_foused_window = None;
-                Ok(())
-            }
-
-            Some(gw) => {
-                match self.windows.iter().position(|w| (*w).window == gw) {
-                    None => Err(FloatingWMError::UnknownWindow(gw)),
-
-                    Some(i) => {
-                        self.index_foused_window = Some(i);
-                        Ok(())
-                    }
-                }
-            }
-        }
-    }
-
-    /// back/forth to the next window from the current focused window.
-    ///
-    /// the iteration in this function is over the current order of the
-    /// *windows* vec, that means that the client can jump over floating
-    /// and tiled windows if the order of *windows* vec is in such way.
-    fn cycle_focus(&mut self, dir: PrevOrNext) {
-        if self.windows.len() > 1 {
-            match self.index_foused_window {
-
-                None => self.index_foused_window = Some(0),
-
-                Some(index) => {
-                    match dir {
-                        PrevOrNext::Prev => {
-                            if index != 0 {
-                                let temp = index - 1;
-                                self.index_foused_window = Some(temp);
-                            } else {
-                                let temp = self.windows.len() - 1;
-                                self.index_foused_window = Some(temp);
-                            }
-                        }
-
-                        PrevOrNext::Next => {
-                            let last_index = self.windows.len() - 1;
-                            if index != last_index {
-                                let temp = index + 1;
-                                self.index_foused_window = Some(temp);
-                            } else {
-                                self.index_foused_window = Some(0);
-                            }
-                        }
-                    }
-                }
-            }
-        } else {
-            if self.windows.len() == 1 {
-                self.index_foused_window = Some(0);
-            }
-        }
-    }
-
-    /// gets the complete current information of the given window.
-    ///
-    /// If the given window is not managed by the window manager,
-    /// UnknownWindow error is shown.
-    fn get_window_info(&self, window: Window) -> Result<WindowWithInfo, Self::Error> {
-        match self.windows.iter().position(|w| (*w).window == window) {
-            None => Err(FloatingWMError::UnknownWindow(window)),
-            Some(i) => {
-                // unwrap() is used because the some(i) statement ensures at
-                // least one element
-                let floating_window = self.windows.get(i).unwrap().clone();
-                let window_with_info = WindowWithInfo {
-                    window: floating_window.window,
-                    geometry: floating_window.geometry,
-                    float_or_tile: floating_window.float_or_tile,
-                    fullscreen: floating_window.fullscreen,
-                };
-                Ok(window_with_info)
-            }
-        }
-    }
-
-    /// gets the current window screen size.
-    fn get_screen(&self) -> Screen {
-        self.screen
-    }
-
-    /// set the given screen as new screen size.
-    ///
-    /// The geometries should be updated accordingly with the new given
-    /// screen.
-    fn resize_screen(&mut self, screen: Screen) {
-        self.screen = screen;
-        self.update_geometries()
-    }
-}
-
-
-impl TilingSupport for FloatingWM {
-    /// if *windows* is not empty it returns the master window, otherwise
-    /// return None.
-    ///
-    /// In this window manager the first tiled element of the *windows* vec
-    /// is always the master window.
-    fn get_master_window(&self) -> Option<Window> {
-        if !self.windows.is_empty() {
-            match self.get_master_index() {
-                None => None,
-                Some(index) => Some(self.windows.get(index).unwrap().window),
-            }
-        } else {
-            None
-        }
-    }
-
-    /// swap the position of the given window with the master window.
-    ///
-    /// This functions actually affects the order in which the windows were
-    /// added, also the geometries should be updated accordingly.
-    ///
-    /// In case the given window is the master window  and the window master
-    /// is not focused, the only effect of this funtions is
-    /// changing the focused windows to the master window.
-    fn swap_with_master(&mut self, window: Window) -> Result<(), Self::Error> {
-        match self.windows.iter().position(|w| (*w).window == window) {
-            None => Err(FloatingWMError::UnknownWindow(window)),
-            Some(window_index) => {
-                // unwrap() is used because the some(window_index) statement
-                // ensures at least one element
-                if self.windows.get(window_index).unwrap().float_or_tile == FloatOrTile::Tile {
-                    match self.get_master_index() {
-                        None => Ok(()),
-                        Some(master_index) => {
-                            self.windows.swap(master_index, window_index);
-                            self.update_geometries();
-                            self.focus_window(Some(window))
-                        }
-                    }
-                } else {
-                    Err(FloatingWMError::NoTiledWindow(window))
-                }
-            }
-        }
-    }
-
-
-    /// Simlar approach than cycle_focus, but now the Vec is affected, hence
-    /// the order in which the windows were added is changed.
-    ///
-    /// If there is no focused window or if the number of tiled windows is
-    /// less than 2 the funtion has no effects.
-    ///
-    /// After the sucessful swap, the geometries should be updated
-    /// accordingly.
-    fn swap_windows(&mut self, dir: PrevOrNext) {
-        let tiled_windows = self.windows.len() - self.get_floating_windows().len();
-        if tiled_windows > 1 {
-            match self.index_foused_window {
-                // no focused window = nothing
-                None => (),
-
-                Some(index) => {
-                    if self.windows.get(index).unwrap().float_or_tile == FloatOrTile::Tile {
-                        match dir {
-                            PrevOrNext::Prev => {
-                                match self.get_prev_tile_index(index, index) {
-                                    Some(prev_index) => {
-                                        self.index_foused_window = Some(prev_index);
-                                        self.windows.swap(index, prev_index);
-                                        self.update_geometries();
-                                    }
-                                    None => (),
-                                }
-                            }
-
-                            PrevOrNext::Next => {
-                                match self.get_next_tile_index(index, index) {
-                                    Some(next_index) => {
-                                        self.index_foused_window = Some(next_index);
-                                        self.windows.swap(index, next_index);
-                                        self.update_geometries();
-                                    }
-                                    None => (),
-                                }
-                            }
-                        }
-                    } else {
-                        ()
-                    }
-                }
-            }
-        }
-    }
-}
-
-impl FloatSupport for FloatingWM {
-    /// return a vector that contains the floating windows managed by this
-    /// window manager
-    ///
-    /// I have to iterate over the whole collection to filter out the
-    /// non-floating windows.
-    fn get_floating_windows(&self) -> Vec<Window> {
-        let mut temp_windows = Vec::new();
-
-        for window_with_info_floating in
-            self.windows.iter().filter(|x| (*x).float_or_tile == FloatOrTile::Float) {
-            temp_windows.push(window_with_info_floating.window.clone());
-        }
-
-        temp_windows
-    }
-
-    /// set the given window to a floating window if it was a tiled window or
-    /// viceversa
-    ///
-    /// after a sucessful toogle, the geometries of the tiled windows should
-    /// be updated
-    fn toggle_floating(&mut self, window: Window) -> Result<(), Self::Error> {
-        match self.windows.iter().position(|w| (*w).window == window) {
-            None => Err(FloatingWMError::UnknownWindow(window)),
-
-            Some(i) => {
-
-                let win_info = self.windows.get(i).unwrap().clone();
-                let mut focused_window = None;
-                match self.index_foused_window {
-                    None => (),
-                    Some(index) => {
-                        // unwrap() is used because the some(index) statement
-                        // ensures at least one element
-                        let window_info = self.windows.get(index).unwrap().clone();
-                        focused_window = Some(window_info.window);
-                    }
-                };
-
-                if win_info.float_or_tile == FloatOrTile::Tile {
-                    // unwrap() is used because the some(index) statement
-                    // ensures at least one element
-                    self.remove_window(win_info.window).unwrap();
-                    let window_info = WindowWithInfo {
-                        window: win_info.window,
-                        geometry: win_info.saved_geometry,
-                        float_or_tile: FloatOrTile::Float,
-                        fullscreen: win_info.fullscreen,
-                    };
-                    // unwrap() is used because the some(index) statement
-                    // ensures at least one element
-                    self.add_window(window_info).unwrap();
-                } else {
-                    // unwrap() is used because the some(index) statement
-                    // ensures at least one element
-                    self.remove_window(win_info.window).unwrap();
-                    let window_info = WindowWithInfo {
-                        window: win_info.window,
-                        geometry: win_info.saved_geometry,
-                        float_or_tile: FloatOrTile::Tile,
-                        fullscreen: win_info.fullscreen,
-                    };
-                    // unwrap() is used because the some(index) statement
-                    // ensures at least one element
-                    self.add_window(window_info).unwrap();
-                };
-                // after toogle the window, the geometries and the focused
-                // window should be updated if that is the case
-                // focus_window will always succes since the window is
-                // already managed and it was removed in this toggle process
-                self.focus_window(focused_window).unwrap();
-                self.update_geometries();
-                Ok(())
-            }
-        }
-    }
-
-    /// set the given window to a floating window if it was a tiled window or
-    /// viceversa
-    ///
-    /// this function iterates over the windows until the given window is
-    /// found, then if it is a floating window
-    /// the corresponding FloatingWindow structure is mutated, otherwise a
-    /// NoFloatingWindow error is thrown.
-    fn set_window_geometry(&mut self,
-                           window: Window,
-                           new_geometry: Geometry)
-                           -> Result<(), Self::Error> {
-        match self.windows.iter().position(|w| (*w).window == window) {
-            None => Err(FloatingWMError::UnknownWindow(window)),
-
-            Some(i) => {
-                // it was already check that there is a window, so unwrap can
-                // be used
-                let window_with_info = self.windows.get_mut(i).unwrap();
-
-                if window_with_info.float_or_tile == FloatOrTile::Tile {
-                    Err(FloatingWMError::NoFloatingWindow(window_with_info.window))
-                } else {
-                    (*window_with_info).geometry = new_geometry;
-                    Ok(())
-                }
-            }
-
-        }
-    }
-}
+/// The tiling window manager with the floating layer on top of it.
+pub type FloatingWM = Floating<TillingWM>;
 
 #[cfg(test)]
 mod tests {
 
     // We have to import `FloatingWM` from the super module.
     use super::FloatingWM;
+
     // We have to repeat the imports we did in the super module.
     use cplwm_api::wm::WindowManager;
     use cplwm_api::wm::TilingSupport;
     use cplwm_api::wm::FloatSupport;
     use cplwm_api::types::*;
 
+    // The generic tests of the traits that this window manager implements.
+    conformance_tests!(FloatingWM: window_manager, tiling, floating);
+
     // We define a static variable for the screen we will use in the tests.
     // You can just as well define it as a local variable in your tests.
     static SCREEN: Screen = Screen {
@@ -27,1144 +27,640 @@
 //! COMMENTS:
 //!
 //! ## General approach
-//! I take advantage of the boolean attribute already stored in the
-//! MinisedWindow structure used in the last excersice, the major change now
-//! are in *get_window_layout* where now it should hide the windows and show
-//! just the fullscreen one if there is one. I updated the name of the
-//! structure to make it consistent with the name of the window manager
 //!
-//! Fucntions *add_window*, *focus_window*, *swap_with_master*,
-//! *swap_windows*, *toggle_minimised* and *toggle_floating*, *remove_window*,
-//! *resize_screen* and *toggle_fullscreen* affect the state
-//! of the fullscreen.
+//! The floating, minimised and fullscreen windows are added by the layers
+//! of the [`m_layers`] module, like in the previous assignments, so the
+//! tiling logic is not copied anymore: *FullscreenWM* wraps
+//! `Fullscreen<Minimising<Floating<LayoutWM>>>`, where *LayoutWM* is the
+//! tiling window manager with layouts of assignment H.
 //!
-//! The *add_window*, *focus_window*, *swap_with_master*, *swap_windows*
-//! funtions involve to change the focused window, the focused window is
-//! always the fullscreen if there is one. Hence one has to "remove"
-//! the fullscreen window in order to navigate to the new focused element.
-//! The fullscreen is setting back to the previous window type (tiled or
-//! floating).
+//! The order of the layers decides how the fullscreen window interacts with
+//! the others. *Fullscreen* is the outer layer, so a floating window can be
+//! fullscreen too, and a fullscreen window that is minimised is not
+//! fullscreen anymore when it comes back. The fullscreen window is always
+//! the focused window, so the fullscreen mode is left when another window
+//! is added, focused or swapped with the master window. Removing,
+//! minimising or moving another window keeps the fullscreen window.
 //!
-//! Funtions *toggle_minimised*, *toggle_floating*, *remove_window* affect
-//! the fullscreen window only if it is applied to the fullscreen window
-//! itself. If other non-fullscreen window is used, then it is done in a
-//! second plane and it will only visible (unhide) by *get_window_layout*
-//! when the fullscreen is disable or when you call *get_window_info* funtion.
+//! The wrapper only keeps what the layers don't know about: the
+//! scratchpad, the urgent and sticky windows and the focus history.
 //!
-//! Funtion *resize_screen* only affects the fullscreen in terms of geometry,
-//! since the screen is updated the fullscreen screen should be updated as
-//! well as the other tiled windows.
+//! ## Layouts
 //!
-//! All get funtions do not affect fullscreen since they are only retriving
-//! information of the current state of the window manager.
+//! The geometries of the tiled windows are calculated by the active layout
+//! of *LayoutWM* (see assignment H), *cycle_layout* changes the active
+//! layout, the fullscreen window keeps the screen geometry. The layers
+//! forward the layout traits to it, the fullscreen layer hides the tab bar
+//! while there is a fullscreen window.
 //!
-//! I see *set_window_geometry* funtion as a speacial case, it can be applied
-//! to a floating windows and they will be updated in a second plane,
-//! but if it is applied to the fullscren window a NoFloatingWindow error is
-//! thrown, instead.
+//! ## Scratchpad
+//!
+//! The scratchpad reuses the floating and minimised windows: a window moved
+//! to the scratchpad is made floating and then minimised, the
+//! *scratchpad_windows* vec only remembers which windows are in the
+//! scratchpad and in which order they are shown. *cycle_focus* skips the
+//! minimised windows, so also the hidden scratchpad windows.
+//!
+//! A scratchpad window that is toggled to tiled leaves the scratchpad.
+//!
+//! ## Urgency
+//!
+//! The urgent windows are kept in *urgent_windows*, the most recent one
+//! first. Every operation that can move the focus removes the focused
+//! window from it, so the focused window is never urgent, and
+//! *focus_urgent_window* relies on *focus_window* to unminimise the window.
+//!
+//! ## Focus history
+//!
+//! *focus_history* is updated by *remember_focus* after every operation
+//! that can move the focus. When the focused window is removed, minimised
+//! or hidden in the scratchpad, *focus_most_recent* focuses the first
+//! window of *focus_history* that is not minimised, instead of leaving
+//! nothing focused like the layers do.
+//!
+//! ## Sticky windows
+//!
+//! There is only one workspace, so a sticky window is a floating window
+//! that is remembered in *sticky_windows*. A sticky window that is toggled
+//! to tiled is not sticky anymore.
+//!
+//! [`m_layers`]: ../m_layers/index.html
 
 // Add imports here
-use std::error;
-use std::fmt;
+use std::cmp::min;
+use std::os::raw::c_int;
 
-use cplwm_api::types::{FloatOrTile, Geometry, PrevOrNext, Screen, Window, WindowLayout,
-                       WindowWithInfo};
+use cplwm_api::types::{ColumnRatio, Direction, FloatOrTile, Geometry, MasterCount, MasterPosition,
+                       MasterRatio, PrevOrNext, Screen, TabBar, Window, WindowLayout,
+                       WindowProperties, WindowWithInfo};
 use cplwm_api::wm::WindowManager;
 use cplwm_api::wm::TilingSupport;
 use cplwm_api::wm::FloatSupport;
 use cplwm_api::wm::MinimiseSupport;
 use cplwm_api::wm::FullscreenSupport;
-
-/// **TODO**: Documentation
+use cplwm_api::wm::LayoutSupport;
+use cplwm_api::wm::TabBarSupport;
+use cplwm_api::wm::ColumnRatioSupport;
+use cplwm_api::wm::MasterRatioSupport;
+use cplwm_api::wm::MultiMasterSupport;
+use cplwm_api::wm::MasterPositionSupport;
+use cplwm_api::wm::DirectionalSupport;
+use cplwm_api::wm::WindowPropertiesSupport;
+use cplwm_api::wm::ScratchpadSupport;
+use cplwm_api::wm::UrgencySupport;
+use cplwm_api::wm::StickySupport;
+use cplwm_api::wm::FocusHistorySupport;
+use cplwm_api::wm::InvariantCheck;
+
+use h_different_tiling_layout::LayoutWM;
+use m_layers::{Floating, Fullscreen, LayerError, Minimising};
+use n_invariants::{check_floating, check_fullscreen, check_minimising, check_tiling,
+                   check_window_manager, prefix_violations};
+
+/// Window manager aliase, the wrappers of the next assignments are built
+/// around this window manager.
 pub type WMName = FullscreenWM;
 
-/// The FullscreenWindow struct
-#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
-pub struct FullscreenWindow {
-    /// The window.
-    pub window: Window,
-    /// The geometry of the window.
-    pub geometry: Geometry,
-    /// The saved floating geometry of the window.
-    pub saved_geometry: Geometry,
-    /// Indicate whether the window should float or tile.
-    pub float_or_tile: FloatOrTile,
-    /// Indicate whether the window should be displayed fullscreen or not.
-    pub fullscreen: bool,
-    /// Indicate whether the window is minimised or not.
-    pub minimised: bool,
-}
-
+/// The layers of [`m_layers`] on top of the tiling window manager with
+/// layouts, the fullscreen layer comes last so a floating window can be
+/// fullscreen and a minimised window is not fullscreen anymore.
+///
+/// [`m_layers`]: ../m_layers/index.html
+pub type LayeredWM = Fullscreen<Minimising<Floating<LayoutWM>>>;
+
+/// A tiling window manager with floating, minimised and fullscreen windows.
+///
+/// The windows are managed by the layers of *wm*, this wrapper adds the
+/// scratchpad, the urgent and sticky windows and the focus history.
 #[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
-/// TODO: *documentation*
 pub struct FullscreenWM {
-    /// Vector where the elementes are minimised windows
-    pub windows: Vec<FullscreenWindow>,
-    /// Vector that stores the order in which the windows were minimised
-    pub minimised_windows: Vec<Window>,
-    /// The size of the screen.
-    pub screen: Screen,
-    /// The index of the focused window in the collection, if there is no
-    /// focused window a None is placed
-    pub index_foused_window: Option<usize>,
+    /// The wrapped window manager, it manages the windows.
+    pub wm: LayeredWM,
+    /// The windows in the scratchpad, the one that is shown next comes first
+    pub scratchpad_windows: Vec<Window>,
+    /// The urgent windows, the one that became urgent most recently comes
+    /// first
+    pub urgent_windows: Vec<Window>,
+    /// The sticky windows, in the order they were made sticky
+    pub sticky_windows: Vec<Window>,
+    /// The windows in the order they were focused, the most recently focused
+    /// one comes first
+    pub focus_history: Vec<Window>,
 }
 
+/// The errors of the layers, with the errors of the tiling window manager
+/// inside.
+pub type FullscreenWMError = <LayeredWM as WindowManager>::Error;
+
 /// Supported functions
 impl FullscreenWM {
-    /// The method was upgraded to skip not only floating but also minimised
-    /// windows
-    pub fn get_next_tile_index(&self, index: usize, saved: usize) -> Option<usize> {
-        let mut next_index = 0;
-        if self.windows.len() - 1 > index {
-            next_index = index + 1;
-        }
-        if next_index == saved {
-            None
-        } else {
-            match self.windows.get(next_index) {
-                None => None,
-                Some(window) => {
-                    if window.float_or_tile == FloatOrTile::Tile {
-                        Some(next_index)
-                    } else {
-                        self.get_next_tile_index(next_index, saved)
-                    }
-                }
-            }
-        }
-    }
-
-    /// The method was upgraded to skip not only floating but also minimised
-    /// windows
-    pub fn get_prev_tile_index(&self, index: usize, saved: usize) -> Option<usize> {
-        let mut prev_index = self.windows.len() - 1;
-        if index != 0 {
-            prev_index = index - 1;
-        };
-        if prev_index == saved {
-            None
-        } else {
-            match self.windows.get(prev_index) {
-                None => None,
-                Some(window) => {
-                    if window.float_or_tile == FloatOrTile::Tile {
-                        Some(prev_index)
-                    } else {
-                        self.get_prev_tile_index(prev_index, saved)
-                    }
-                }
-            }
-        }
-    }
-
-    /// get the master window index
-    ///
-    /// The method was upgraded to skip not only floating but also minimised
-    /// windows
-    fn get_master_index(&self) -> Option<usize> {
-        if !self.windows.is_empty() {
-            self.windows
-                .iter()
-                .position(|w| (*w).float_or_tile == FloatOrTile::Tile && !(*w).minimised)
-        } else {
-            None
-        }
-    }
-
-    /// 	returns the index where the first floating window starts
+    /// Moves the focused window to the front of *focus_history*, the user is
+    /// looking at it now, so it is not urgent anymore.
     ///
-    /// if there is no  floating window, a None element is returns
-    fn get_partion_index(&self) -> Option<usize> {
-        if !self.windows.is_empty() {
-            self.windows.iter().position(|w| (*w).float_or_tile == FloatOrTile::Float)
-        } else {
-            None
+    /// Called after every operation that can move the focus.
+    fn remember_focus(&mut self) {
+        if let Some(window) = self.get_focused_window() {
+            self.focus_history.retain(|w| *w != window);
+            self.focus_history.insert(0, window);
+            self.urgent_windows.retain(|w| *w != window);
         }
     }
 
-    /// This method calculated the geometries of windows.
-    ///
-    /// if there is a fullscreen it is updated wiht the screen geometry
-    fn update_geometries(&mut self) {
-        if !self.windows.is_empty() {
-
-
-            let mut fullscreen = 0;
-
-            for f_w in self.windows.iter_mut().filter(|x| (*x).fullscreen) {
-                f_w.geometry = self.screen.to_geometry();
-                fullscreen = 1;
-            }
-
-            // Divisor now should be update to just the tiled windows
-            let non_tiled_windows =
-                self.get_floating_windows().len() + self.get_minimised_tiled_windows().len() +
-                self.get_minimised_floating_windows().len() + 1 + fullscreen;
-            let total_windows = self.windows.len();
-
-            // if the divisor is greater than 0 we need to calculate slave windows
-            if total_windows > non_tiled_windows {
-                let divisor = total_windows - non_tiled_windows;
-                let divisor_2 = divisor as u32;
-                let height_side = self.screen.height / divisor_2;
-                let width_side = self.screen.width / 2;
-                let x_point = (self.screen.width / 2) as i32;
-                // It is already tested that there is more than 1 window,
-                // hence one can use unwrap method being sure that a
-                // Some intance of option will be returned
-                let master_window = self.get_master_window().unwrap();
-
-                let mut y_point = 0 as i32;
-
-                for fullscreen_window in
-                    self.windows.iter_mut().filter(|x| {
-                        (*x).float_or_tile == FloatOrTile::Tile && !(*x).minimised &&
-                        !(*x).fullscreen
-                    }) {
-                    if master_window != fullscreen_window.window {
-                        // I calculate the values of the secondary windows
-                        // (right windows)
-                        let rigth_geometry = Geometry {
-                            x: x_point,
-                            y: y_point,
-                            width: width_side,
-                            height: height_side,
-                        };
-                        fullscreen_window.geometry = rigth_geometry;
-                        y_point += (height_side) as i32;
-
-                    } else {
-                        // I calculate the values for master window
-                        let master_geometry = Geometry {
-                            x: 0,
-                            y: 0,
-                            width: width_side,
-                            height: self.screen.height,
-                        };
-
-                        fullscreen_window.geometry = master_geometry;
-                    }
-                }
-            } else {
-                // It could be the posibility that there is just one window
-                // (the master window)
-                match self.get_master_index() {
-                    None => (),
-                    Some(i) => {
-                        // unwrap() is used because I ensure there is at
-                        // least one element
-                        let window = self.windows.get_mut(i).unwrap();
-                        window.geometry = self.screen.to_geometry();
-                    }
-                }
-            }
-        };
+    /// Focuses the most recently focused window that is not minimised, or
+    /// nothing if there is no such window. Used when the focused window
+    /// disappears.
+    fn focus_most_recent(&mut self) {
+        let window = self.focus_history.iter().cloned().find(|w| !self.is_minimised(*w));
+        // unwrap() is used because the windows of the history are managed
+        self.wm.focus_window(window).unwrap();
+        self.remember_focus();
     }
 
-    /// gets a vector with windows that are both minised and tiled windows
-    fn get_minimised_tiled_windows(&self) -> Vec<Window> {
-        let mut temp_windows = Vec::new();
-        for fullscreen_window in
-            self.windows
-                .iter()
-                .filter(|x| (*x).float_or_tile == FloatOrTile::Tile && (*x).minimised) {
-            temp_windows.push(fullscreen_window.window.clone())
-        }
-        temp_windows
+    /// Returns the tiling window manager below the layers, e.g. to look at
+    /// its layouts.
+    pub fn get_layout_wm(&self) -> &LayoutWM {
+        &self.wm.wm.wm.wm
     }
 
-    /// gets a vector with windows that are both minised and floating windows
-    fn get_minimised_floating_windows(&self) -> Vec<Window> {
-        let mut temp_windows = Vec::new();
-        for minimised_window in self.windows
-            .iter()
-            .filter(|x| (*x).float_or_tile == FloatOrTile::Float && (*x).minimised) {
-            temp_windows.push(minimised_window.window.clone())
-        }
-        temp_windows
-    }
-
-    /// Removes the given window from the *minimised_windows* vector and set
-    /// its attribute to false in the corresponding
-    /// window of *windows* vector
-    fn remove_minimised_window(&mut self, window: Window) {
-        match self.minimised_windows.iter().position(|w| *w == window) {
-            None => (),
-            Some(i) => {
-                self.minimised_windows.remove(i);
-                match self.windows.iter().position(|w| (*w).window == window) {
-                    None => (),
-                    Some(i_2) => {
-                        // unwrap() is used because Some(i_2) ensures there
-                        // is at least one element
-                        self.windows.get_mut(i_2).unwrap().minimised = false;
-                    }
-                }
-            }
-        }
+    /// Returns the tiling window manager below the layers, e.g. to choose
+    /// its layouts.
+    pub fn get_layout_wm_mut(&mut self) -> &mut LayoutWM {
+        &mut self.wm.wm.wm.wm
     }
 
-    /// set the given window to the *minimised_windows* vector and set its
-    /// attribute to true in the corresponding
-    /// window of *windows* vector
-    fn set_minimised_window(&mut self, window: Window) {
-        match self.windows.iter().position(|w| (*w).window == window) {
-            None => (),
-            Some(i) => {
-                // unwrap() is used because Some(i) ensures there is at least
-                // one element
-                let window_min = self.windows.get_mut(i).unwrap();
-                window_min.minimised = true;
-                self.minimised_windows.push(window_min.window.clone());
-            }
-        }
-    }
-    /// Set the fullscreen attribute of the given window to true
-    fn set_fullscreen_window(&mut self, window: Window) {
-        for f_w in self.windows.iter_mut().filter(|x| (*x).window == window) {
-            f_w.fullscreen = true
-        }
+    /// Returns true if the given window is a hidden scratchpad window, i.e.
+    /// it is in the scratchpad and minimised
+    fn is_hidden_scratchpad_window(&self, window: Window) -> bool {
+        self.scratchpad_windows.contains(&window) && self.is_minimised(window)
     }
 
-    /// Set the fullscreen attribute of the given window to false
-    fn remove_fullscreen_window(&mut self, window: Window) {
-        for f_w in self.windows.iter_mut().filter(|x| (*x).window == window) {
-            f_w.fullscreen = false
+    /// Returns the given geometry centered on the screen, it is shrunk if it
+    /// does not fit on the screen
+    fn get_centered_geometry(&self, geometry: Geometry) -> Geometry {
+        let screen = self.get_screen();
+        let width = min(geometry.width, screen.width);
+        let height = min(geometry.height, screen.height);
+        Geometry {
+            x: ((screen.width - width) / 2) as c_int,
+            y: ((screen.height - height) / 2) as c_int,
+            width: width,
+            height: height,
         }
     }
-}
-
-/// The errors that this window manager can return.
-#[derive(Debug)]
-pub enum FullscreenWMError {
-    /// This window is not known by the window manager.
-    UnknownWindow(Window),
-    /// This window is not known by the window manager.
-    ManagedWindow(Window),
-    /// This window is not a floating window.
-    NoFloatingWindow(Window),
-    /// This window is not a tiled window
-    NoTiledWindow(Window),
-}
 
-impl fmt::Display for FullscreenWMError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            FullscreenWMError::UnknownWindow(ref window) => write!(f, "Unknown window: {}", window),
-            FullscreenWMError::ManagedWindow(ref window) => {
-                write!(f, "Window {} is already managed", window)
+    /// Hides the given scratchpad window and moves it to the back of the
+    /// *scratchpad_windows* vec, the most recently focused window is
+    /// focused if it was focused
+    fn hide_scratchpad_window(&mut self, window: Window) -> Result<(), FullscreenWMError> {
+        self.scratchpad_windows.retain(|w| *w != window);
+        self.scratchpad_windows.push(window);
+        if !self.is_minimised(window) {
+            let focused = self.get_focused_window() == Some(window);
+            try!(self.wm.toggle_minimised(window));
+            if focused {
+                self.focus_most_recent();
             }
-            FullscreenWMError::NoFloatingWindow(ref window) => {
-                write!(f, "Window {} is not floating", window)
-            }
-            FullscreenWMError::NoTiledWindow(ref window) => {
-                write!(f, "Window {} is not tiled", window)
-            }
-        }
-    }
-}
-
-impl error::Error for FullscreenWMError {
-    fn description(&self) -> &'static str {
-        match *self {
-            FullscreenWMError::UnknownWindow(_) => "Unknown window",
-            FullscreenWMError::ManagedWindow(_) => "Window is already managed",
-            FullscreenWMError::NoFloatingWindow(_) => "Window is not floating",
-            FullscreenWMError::NoTiledWindow(_) => "Window is not tiled",
         }
+        Ok(())
     }
 }
 
-
 impl WindowManager for FullscreenWM {
+    /// The errors of the layers.
     type Error = FullscreenWMError;
 
     /// The FullscreenWM constructor.
     ///
-    /// windows and minimised_windows are initialised as empty vec, screen
-    /// as the given screen and focused index as None.
+    /// The layers are created for the given screen, the scratchpad, the
+    /// urgent and sticky windows and the focus history are empty.
     fn new(screen: Screen) -> FullscreenWM {
         FullscreenWM {
-            windows: Vec::new(),
-            minimised_windows: Vec::new(),
-            screen: screen,
-            index_foused_window: None,
+            wm: LayeredWM::new(screen),
+            scratchpad_windows: Vec::new(),
+            urgent_windows: Vec::new(),
+            sticky_windows: Vec::new(),
+            focus_history: Vec::new(),
         }
     }
 
-    /// Returns all the managed windows in the window manager.
+    /// returns the windows of the layers: the tiled windows, the floating
+    /// windows and the minimised windows.
     fn get_windows(&self) -> Vec<Window> {
-        let mut temp_windows = Vec::new();
-        for window_with_info in self.windows.iter() {
-            temp_windows.push(window_with_info.window.clone());
-        }
-        temp_windows
+        self.wm.get_windows()
     }
 
-    /// gets the current focused window.
-    ///
-    /// The list can be no empty and no focused window simultaneously.
+    /// returns the focused window of the layers.
     fn get_focused_window(&self) -> Option<Window> {
-        if !self.windows.is_empty() {
-            match self.index_foused_window {
-                None => None,
-                Some(index) => Some(self.windows.get(index).unwrap().window),
-            }
-        } else {
-            None
-        }
+        self.wm.get_focused_window()
     }
 
-    /// adds new window_with_info to the vec windows and set the geometry to
-    /// fullscreen.
-    ///
-    /// the fullscreen windows are accepted and added, but they are treated
-    /// as tiled or floating window. The given geometry by the
-    /// window_with_info is set as the saved_geometry and geometry
-    /// attributes of the FloatingWindow structure.
-    ///
-    /// if the given window has an attribute FloatOrTile::Tile then the
-    /// geometries for tiled windows should be updated.
-    ///
-    /// add_window adds the tiled and floating windows in order, so the
-    /// first part of the vec contains
-    /// the tiled windows while the second part are the floating windows
-    ///
-    /// add_window converts every window_with_info to fullscreen_window,
-    /// nsetting the minimised
-    /// attribute as false
-    ///
-    /// returns an ManagedWindow error if the given window_with_info is
-    /// already managed by the window manager.
+    /// adds the window to the layers, it is focused.
     fn add_window(&mut self, window_with_info: WindowWithInfo) -> Result<(), Self::Error> {
-        if !self.is_managed(window_with_info.window) {
-            for f_w in self.windows.iter_mut().filter(|x| (*x).fullscreen) {
-                f_w.fullscreen = false;
-            }
-            // All new added windows are set minimised = false by default
-            let fullscreen_window = FullscreenWindow {
-                window: window_with_info.window,
-                geometry: window_with_info.geometry,
-                saved_geometry: window_with_info.geometry,
-                float_or_tile: window_with_info.float_or_tile,
-                fullscreen: window_with_info.fullscreen,
-                minimised: false,
-            };
-            if window_with_info.float_or_tile == FloatOrTile::Float {
-                // if there is a floating window, it is insert at the end
-                // of the vec
-                self.windows.push(fullscreen_window);
-                let temp = self.windows.len() - 1;
-                self.index_foused_window = Some(temp);
-            } else {
-                // if not, I get the index where the first floating window
-                //  start and insert the tiled window there, also the
-                // geometries should be updated.
-                match self.get_partion_index() {
-                    None => {
-                        self.windows.push(fullscreen_window);
-                        let temp = self.windows.len() - 1;
-                        self.index_foused_window = Some(temp);
-                    }
-                    Some(partion_index) => {
-                        self.windows.insert(partion_index, fullscreen_window);
-                        self.index_foused_window = Some(partion_index);
-                    }
-                };
-                self.update_geometries();
-            };
-            Ok(())
-        } else {
-            Err(FullscreenWMError::ManagedWindow(window_with_info.window))
-        }
+        try!(self.wm.add_window(window_with_info));
+        self.remember_focus();
+        Ok(())
     }
 
-    /// removes the given window from the window manager.
-    ///
-    /// Every time that a element is removed the index_foused_window should
-    /// be updated if it is necessary.
-    /// Important to noticy here is that when the focused element is the
-    /// same as the removed element, no focused window is set.
+    /// removes the window from the layers, the scratchpad, the urgent and
+    /// sticky windows and the focus history.
     ///
-    /// There is no effect in the fullscreen window if a window is removed
-    /// unless the fullscreen itself is removed.
+    /// When the focused window is removed, the most recently focused window
+    /// that is not minimised is focused.
     fn remove_window(&mut self, window: Window) -> Result<(), Self::Error> {
-        match self.windows.iter().position(|w| (*w).window == window) {
-            None => Err(FullscreenWMError::UnknownWindow(window)),
-            Some(i) => {
-                // unwrap() is used because Some(i) ensures there is at
-                // least one element
-                let temp_window = self.windows.get(i).unwrap().clone();
-                self.windows.remove(i);
-
-                if temp_window.minimised {
-                    self.remove_fullscreen_window(temp_window.window);
-                };
-
-                if temp_window.float_or_tile == FloatOrTile::Tile {
-                    self.update_geometries();
-                };
-                match self.index_foused_window {
-                    None => Ok(()),
-
-                    Some(index) => {
-                        // If the focused_element is the one that is erased,
-                        // None focused elemente
-                        if index == i {
-                            self.index_foused_window = None;
-                            Ok(())
-                        } else {
-                            // If the focused_element is in the right side
-                            // of the vector
-                            // the focused elemente is keep it, otherwise
-                            // the focused index is decreased by one.
-                            let mut temp = index;
-                            if index > i {
-                                temp = index - 1;
-                            }
-                            self.index_foused_window = Some(temp);
-                            Ok(())
-                        }
-                    }
-                }
-            }
+        let focused = self.get_focused_window() == Some(window);
+        try!(self.wm.remove_window(window));
+        self.scratchpad_windows.retain(|w| *w != window);
+        self.urgent_windows.retain(|w| *w != window);
+        self.sticky_windows.retain(|w| *w != window);
+        self.focus_history.retain(|w| *w != window);
+        if focused {
+            self.focus_most_recent();
         }
+        Ok(())
     }
 
-    /// returns the layout of all managed windows.
-    ///
-    /// The array is already order thus no need to rearrange the vector.
-    /// The minised windows are filtered out.
-    ///
-    /// Now get_window_layout hides the windows when there is a fullscreen
-    /// window
+    /// returns the layout of the layers.
     fn get_window_layout(&self) -> WindowLayout {
-
-        if !self.windows.is_empty() {
-
-            let mut fullscreen_window = None;
-
-            for fs_w in self.windows.iter().filter(|x| (*x).fullscreen) {
-                fullscreen_window = Some(fs_w.clone())
-            }
-
-            match fullscreen_window {
-                // if there is a fullscreen, I hide the others
-                Some(window) => {
-                    WindowLayout {
-                        focused_window: Some(window.window),
-                        windows: vec![(window.window, window.geometry)],
-                    }
-                }
-                None => {
-                    let mut temp_windows = Vec::new();
-
-                    for window_info in self.windows.iter().filter(|x| !(*x).minimised) {
-                        temp_windows.push((window_info.window.clone(),
-                        window_info.geometry.clone()))
-                    }
-
-                    let temp_focused_window = match self.index_foused_window {
-                        None => None,
-                        Some(index) => {
-                            // unwrap() is used because Some(index) ensures
-                            // there is at least one element
-                            let window_type = self.windows.get(index).unwrap();
-                            match self.get_partion_index() {
-                                // unwrap() is used because
-                                // self.get_partion_index ensures there is
-                                // at least one element
-                                None => Some(self.windows.get(index).unwrap().window),
-                                // if a focused window is a floating one,
-                                // then the temp_windows is reorder to
-                                // put the focused window at the very end
-                                // of the vec
-                                Some(partion_index) => {
-                                    if partion_index >= index &&
-                                       window_type.float_or_tile == FloatOrTile::Float {
-                                        match temp_windows.iter().position(|w| {
-                                            (*w) == (window_type.window, window_type.geometry)
-                                        }) {
-                                            None => (),
-
-                                            Some(real_index) => {
-                                                // unwrap() is used because
-                                                // Some(real_index) ensures
-                                                // there is at least one element
-                                                let (temp_window, temp_geometry) =
-                                                    temp_windows.get(real_index)
-                                                        .unwrap()
-                                                        .clone();
-                                                temp_windows.remove(real_index);
-                                                temp_windows.push((temp_window, temp_geometry));
-                                            }
-                                        };
-                                    };
-                                    // unwrap() is used because
-                                    // Some(partion_index) ensures there is
-                                    // at least one element
-                                    Some(self.windows
-                                        .get(index)
-                                        .unwrap()
-                                        .window)
-                                }
-                            }
-                        }
-                    };
-
-                    WindowLayout {
-                        focused_window: temp_focused_window,
-                        windows: temp_windows,
-                    }
-                }
-            }
-        } else {
-            WindowLayout::new()
-        }
+        self.wm.get_window_layout()
     }
 
-    /// set the focused window in the window manager with the given window.
-    ///
-    /// if the given window is minised, after focus_window succeeds, such
-    /// windows is unminimised.
-    ///
-    /// One can focused the fullscreen, if that is the case, there is no
-    /// effect.
+    /// focuses the window in the layers, a minimised window is unminimised.
     fn focus_window(&mut self, window: Option<Window>) -> Result<(), Self::Error> {
-        match window {
-            None => {
-                self.index_foused_window = None;
-                Ok(())
-            }
-
-            Some(gw) => {
-                match self.windows.iter().position(|w| (*w).window == gw) {
-                    None => Err(FullscreenWMError::UnknownWindow(gw)),
-
-                    Some(i) => {
-                        // unwrap() is used because Some(i) ensures there
-                        // is at least one element
-                        let fullscreen_window = self.windows.get(i).unwrap().clone();
-
-                        if !fullscreen_window.fullscreen {
-                            self.index_foused_window = Some(i);
-                            for fullscreen_window in
-                                self.windows.iter_mut().filter(|x| (*x).fullscreen) {
-                                fullscreen_window.fullscreen = false
-                            }
-                            if fullscreen_window.minimised {
-                                // unwrap() is used because Some(i) ensures
-                                // there is at least one element
-                                Ok(self.remove_minimised_window(window.unwrap()))
-                            } else {
-                                Ok(())
-                            }
-                        } else {
-                            Ok(())
-                        }
-                    }
-                }
-            }
-        }
+        try!(self.wm.focus_window(window));
+        self.remember_focus();
+        Ok(())
     }
 
-    /// back/forth to the next window from the current focused window.
-    ///
-    /// the iteration in this function is over the current order of the
-    /// *windows* vec, that means that the client can jump over floating
-    /// and tiled windows if the order of *windows* vec is in such way.
-    /// If the PrevOrNext is a minised window, it is unminimised.
-    /// Whenever it is used, the fullscreen is disable
+    /// cycles the focus over the visible windows of the layers, the
+    /// minimised windows, like the hidden scratchpad windows, are skipped.
     fn cycle_focus(&mut self, dir: PrevOrNext) {
-        if self.windows.len() > 1 {
-            match self.index_foused_window {
-
-                None => self.index_foused_window = Some(0),
-
-                Some(index) => {
-                    match dir {
-                        PrevOrNext::Prev => {
-                            if index != 0 {
-                                let temp = index - 1;
-                                self.index_foused_window = Some(temp);
-                            } else {
-                                let temp = self.windows.len() - 1;
-                                self.index_foused_window = Some(temp);
-                            }
-                        }
-
-                        PrevOrNext::Next => {
-                            let last_index = self.windows.len() - 1;
-                            if index != last_index {
-                                let temp = index + 1;
-                                self.index_foused_window = Some(temp);
-                            } else {
-                                self.index_foused_window = Some(0);
-                            }
-                        }
-                    }
-                }
-            }
-        } else {
-            if self.windows.len() == 1 {
-                self.index_foused_window = Some(0);
-            }
-        };
-
-        match self.index_foused_window {
-            Some(index) => {
-                // unwrap() is used because Some(index) ensures there is
-                // at least one element
-                let minimised_window = self.windows.get(index).unwrap().clone();
-                // I know that the given minised_window already exists
-                // in the manager no need to catch the output
-                self.remove_minimised_window(minimised_window.window);
-            }
-            None => (),
-        }
-
-        let mut fullscreen_window = false;
-        for f_w in self.windows.iter_mut().filter(|x| (*x).fullscreen) {
-            f_w.fullscreen = false;
-            fullscreen_window = true
-        }
-
-        if fullscreen_window {
-            self.update_geometries()
-        }
+        self.wm.cycle_focus(dir);
+        self.remember_focus();
     }
 
-    /// gets the complete current information of the given window.
-    ///
-    /// If the given window is not managed by the window manager,
-    /// UnknownWindow error is shown.
+    /// returns the information of the window in the layers, the
+    /// fullscreen window has the geometry of the screen, as in the window
+    /// layout.
     fn get_window_info(&self, window: Window) -> Result<WindowWithInfo, Self::Error> {
-        match self.windows.iter().position(|w| (*w).window == window) {
-            None => Err(FullscreenWMError::UnknownWindow(window)),
-            Some(i) => {
-                // unwrap() is used because Some(i) ensures there is at
-                // least one element
-                let fullscreen_window = self.windows.get(i).unwrap().clone();
-                let window_with_info = WindowWithInfo {
-                    window: fullscreen_window.window,
-                    geometry: fullscreen_window.geometry,
-                    float_or_tile: fullscreen_window.float_or_tile,
-                    fullscreen: fullscreen_window.fullscreen,
-                };
-                Ok(window_with_info)
-            }
+        let mut window_with_info = try!(self.wm.get_window_info(window));
+        if window_with_info.fullscreen {
+            window_with_info.geometry = self.get_screen().to_geometry();
         }
+        Ok(window_with_info)
     }
 
-    /// gets the current window screen size.
+    /// returns the screen of the layers.
     fn get_screen(&self) -> Screen {
-        self.screen
+        self.wm.get_screen()
     }
 
-    // When the scren is resized, the tiled windows should be updated
-    // accordingly
+    /// resizes the screen of the layers.
     fn resize_screen(&mut self, screen: Screen) {
-        self.screen = screen;
-        self.update_geometries()
+        self.wm.resize_screen(screen)
     }
 }
 
 impl TilingSupport for FullscreenWM {
-    /// if *windows* is not empty it returns the master window, otherwise
-    /// return None.
-    ///
-    /// In this window manager the first tiled element of the *windows* vec
-    /// is always the master window.
+    /// returns the master window of the layers.
     fn get_master_window(&self) -> Option<Window> {
-        if !self.windows.is_empty() {
-            match self.get_master_index()
-            {None => None,
-			Some(index) =>
-				// unwrap() is used because Some(index) ensures there
-                // is at least one element
-				Some(self.windows.get(index).unwrap().window),
-			}
-        } else {
-            None
-        }
+        self.wm.get_master_window()
     }
 
-    /// swap the position of the given window with the master window.
-    ///
-    /// This functions actually affects the order in which the windows were
-    /// added, also the geomtries should be accordingly.
-    ///
-    /// In case the given window is the master window  and the window master
-    /// is not focused, the only effect of this funtions is
-    /// changing the focused windows to the master window.
-    ///
-    /// If the given window is minised, this funtion should unminimised it
-    ///
-    /// If the the fullscreen is removed, if any.
+    /// swaps the window with the master window and focuses it.
     fn swap_with_master(&mut self, window: Window) -> Result<(), Self::Error> {
-        for f_w in self.windows.iter_mut().filter(|x| (*x).fullscreen) {
-            f_w.fullscreen = false;
-        }
-        match self.windows.iter().position(|w| (*w).window == window) {
-            None => Err(FullscreenWMError::UnknownWindow(window)),
-            Some(window_index) => {
-                // unwrap() is used because Some(window_index) ensures
-                // there is at least one element
-                let minised_window = self.windows.get(window_index).unwrap().clone();
-                if minised_window.float_or_tile == FloatOrTile::Tile {
-                    match self.get_master_index() {
-                        None => Ok(()),
-                        Some(master_index) => {
-                            if minised_window.minimised {
-                                self.remove_minimised_window(window);
-                            };
-                            self.windows.swap(master_index, window_index);
-                            self.update_geometries();
-                            self.focus_window(Some(window))
-                        }
-                    }
-                } else {
-                    Err(FullscreenWMError::NoTiledWindow(window))
-                }
-            }
-        }
+        try!(self.wm.swap_with_master(window));
+        self.remember_focus();
+        Ok(())
     }
 
-
-    /// Simlar approach than cycle_focus, but now the Vec is affect, hence
-    /// the order in which the windows were added is changed.
-    ///
-    /// If there is no focused window or if the number of tiled windows is
-    /// less than 2 the funtion has no effects.
-    ///
-    /// The minimised windows are hidden in this function.
-    ///
-    /// After the sucessful swap, the geometries should be updated
-    /// accordingly.
-    ///
-    /// The fulscreen is removed whe this method is used
+    /// swaps the focused window with a neighbouring tile.
     fn swap_windows(&mut self, dir: PrevOrNext) {
-        // remove fullscreen, if any
-        for f_w in self.windows.iter_mut().filter(|x| (*x).fullscreen) {
-            f_w.fullscreen = false;
-        }
-        if self.windows.len() > 1 {
-            match self.index_foused_window {
-                // no focused window = nothing
-                None => (),
-
-                Some(index) => {
-                    // unwrap() is used because Some(index) ensures there is
-                    // at least one element
-                    if self.windows.get(index).unwrap().float_or_tile == FloatOrTile::Tile {
-                        match dir {
-                            PrevOrNext::Prev => {
-                                match self.get_prev_tile_index(index, index) {
-                                    Some(prev_index) => {
-                                        self.index_foused_window = Some(prev_index);
-                                        self.windows.swap(index, prev_index);
-                                        self.update_geometries();
-                                    }
-                                    None => (),
-                                }
-                            }
-
-                            PrevOrNext::Next => {
-                                match self.get_next_tile_index(index, index) {
-                                    Some(next_index) => {
-                                        self.index_foused_window = Some(next_index);
-                                        self.windows.swap(index, next_index);
-                                        self.update_geometries();
-                                    }
-                                    None => (),
-                                }
-                            }
-                        };
-                        // remove minised window, if applied
-                        match self.index_foused_window {
-                            None => (),
-                            Some(index_f_w) => {
-                                // unwrap() is used because Some(index_f_w)
-                                // ensures there is at least one element
-                                let focused_window = self.windows.get(index_f_w).unwrap().clone();
-                                self.remove_minimised_window(focused_window.window);
-                            }
-                        }
-                    } else {
-                        ()
-                    }
-                }
-            }
-        }
+        self.wm.swap_windows(dir)
     }
 }
 
 impl FloatSupport for FullscreenWM {
-    /// return a vector that contains the floating windows managed by
-    /// this window manager
-    ///
-    /// I have to iterate over the whole collection to filter out the
-    /// non-floating windows.
-    ///
-    /// The minised and floating windows are filtered out too.
+    /// returns the floating windows that are not minimised.
     fn get_floating_windows(&self) -> Vec<Window> {
-        let mut temp_windows = Vec::new();
-
-        for window_with_info_floating in
-            self.windows
-                .iter()
-                .filter(|x| (*x).float_or_tile == FloatOrTile::Float && !(*x).minimised) {
-            temp_windows.push(window_with_info_floating.window.clone());
-        }
-
-        temp_windows
+        self.wm.get_floating_windows()
     }
 
-
-    /// set the given window to a floating window if it was a tiled window
-    /// or viceversa
+    /// toggles the floating mode of the window in the layers.
     ///
-    /// if the given window was minised, after toggle_floating succeeds,
-    /// such window should be unminimised
-    ///
-    /// after the a sucessful toogle, the geometries of the tiled windows
-    /// should be updated
-    ///
-    /// if the given window is the fullscreen window, the it is removed
+    /// A window that is tiled leaves the scratchpad and is not sticky
+    /// anymore. A minimised window is unminimised and focused.
     fn toggle_floating(&mut self, window: Window) -> Result<(), Self::Error> {
-        match self.windows.iter().position(|w| (*w).window == window) {
-            None => Err(FullscreenWMError::UnknownWindow(window)),
-
-            Some(i) => {
-                // unwrap() is used because Some(i) ensures there is at
-                // least one element
-                let win_info = self.windows.get(i).unwrap().clone();
-                // if the given structure is fullscreen then it should be
-                // removed and
-                // added as floating or tiled window depending of the
-                // previous window type
-                // and focused
-                if win_info.fullscreen {
-                    self.remove_window(win_info.window).unwrap();
-                    let window_info;
-                    if win_info.float_or_tile == FloatOrTile::Tile {
-
-                        window_info = WindowWithInfo {
-                            window: win_info.window,
-                            geometry: win_info.saved_geometry,
-                            float_or_tile: FloatOrTile::Float,
-                            fullscreen: false,
-                        };
-
-                    } else {
-                        window_info = WindowWithInfo {
-                            window: win_info.window,
-                            geometry: win_info.saved_geometry,
-                            float_or_tile: FloatOrTile::Tile,
-                            fullscreen: false,
-                        };
-                    };
-                    self.add_window(window_info).unwrap();
-                    Ok(())
-                } else {
-                    if win_info.minimised {
-                        self.remove_minimised_window(win_info.window);
-                    };
-
-                    // store the current focused and
-                    let mut focused_window = None;
-                    let fullscreen_window = self.get_fullscreen_window();
-                    match self.index_foused_window {
-                        None => (),
-                        Some(index) => {
-                            // unwrap() is used because Some(index) ensures
-                            // there is at least one element
-                            let window_info = self.windows.get(index).unwrap().clone();
-                            focused_window = Some(window_info.window);
-                        }
-                    };
-                    self.remove_window(win_info.window).unwrap();
-                    let window_info;
-                    if win_info.float_or_tile == FloatOrTile::Tile {
-
-                        window_info = WindowWithInfo {
-                            window: win_info.window,
-                            geometry: win_info.saved_geometry,
-                            float_or_tile: FloatOrTile::Float,
-                            fullscreen: win_info.fullscreen,
-                        };
-
-                    } else {
-                        window_info = WindowWithInfo {
-                            window: win_info.window,
-                            geometry: win_info.saved_geometry,
-                            float_or_tile: FloatOrTile::Tile,
-                            fullscreen: win_info.fullscreen,
-                        };
-                    };
-                    self.add_window(window_info).unwrap();
-
-                    // after toogle the window, the geometries and the
-                    // focused window should be updated if that it the case
-                    // focus_window will always sucees since the window is
-                    // already managed and it was removed in this toggle
-                    // process
-                    self.focus_window(focused_window).unwrap();
-                    if !win_info.fullscreen {
-                        match fullscreen_window {
-                            None => (),
-                            Some(windows_id) => self.set_fullscreen_window(windows_id),
-                        }
-                    };
-                    self.update_geometries();
-                    Ok(())
-                }
-            }
+        try!(self.wm.toggle_floating(window));
+        if !self.is_floating(window) {
+            self.scratchpad_windows.retain(|w| *w != window);
+            self.sticky_windows.retain(|w| *w != window);
         }
+        self.remember_focus();
+        Ok(())
     }
 
-    /// set the given window to a floating window if it was a tiled window
-    ///  or viceversa
-    ///
-    /// this function iterates over the windows until the given window is
-    /// found, then if it is a floating window
-    /// the corresponding FloatingWindow structure is mutated, otherwise
-    /// a NoFloatingWindow error is thrown.
-    ///
-    /// if the given window is minised, it should be unminimised
-    ///
-    /// The fullscreen window it is not affected by this function
+    /// changes the geometry of a floating window, also when it is minimised.
     fn set_window_geometry(&mut self,
                            window: Window,
                            new_geometry: Geometry)
                            -> Result<(), Self::Error> {
-        match self.windows.iter().position(|w| (*w).window == window) {
-            None => Err(FullscreenWMError::UnknownWindow(window)),
-
-            Some(i) => {
-                // it was already check that there is a window, so unwrap
-                // can be used
-                let window_min = self.windows.get(i).unwrap().clone();
-
-                if window_min.float_or_tile == FloatOrTile::Tile {
-                    Err(FullscreenWMError::NoFloatingWindow(window_min.window))
-                } else {
-                    if window_min.minimised {
-                        self.remove_minimised_window(window_min.window);
-                    };
-                    let window_with_info = self.windows.get_mut(i).unwrap();
-                    (*window_with_info).saved_geometry = new_geometry;
-                    (*window_with_info).geometry = new_geometry;
-                    Ok(())
-                }
-            }
-        }
+        self.wm.set_window_geometry(window, new_geometry)
     }
 }
 
 impl MinimiseSupport for FullscreenWM {
-    /// Returns a vector of all the minimised windows.
-    ///
-    /// the order in which the vec is returned as the order in whih minised
-    /// elements were minised
+    /// returns the minimised windows, in the order in which they were
+    /// minimised.
     fn get_minimised_windows(&self) -> Vec<Window> {
-        self.minimised_windows.clone()
+        self.wm.get_minimised_windows()
     }
 
-
-    /// Returns `true` if the given window is minimised.
+    /// checks the layers.
     fn is_minimised(&self, window: Window) -> bool {
-        self.get_minimised_windows().contains(&window)
+        self.wm.is_minimised(window)
     }
 
-    /// if the given window is minimised is unminimised or viceversa
-    ///
-    /// after toggle_minimised succeeds, the geometries should be updated
-    ///
-    /// if the given window is the fullscreen, the it is removed
+    /// toggles the minimised mode of the window in the layers.
     ///
-    /// if the given window was focused and should be minised, then
-    /// focused_window is set to None
+    /// An unminimised window is focused. When the focused window is
+    /// minimised, the most recently focused window that is not minimised is
+    /// focused.
     fn toggle_minimised(&mut self, window: Window) -> Result<(), Self::Error> {
-        if let Some(index_window) = self.windows.iter().position(|w| (*w).window == window) {
-            // remove fullscreen if the given window is a fullscreen
-            self.remove_fullscreen_window(window);
-            if self.is_minimised(window) {
-                self.remove_minimised_window(window)
-            } else {
-                // Non focused if is minised the current focused_window
-                match self.index_foused_window {
-                    None => (),
-                    Some(index_focused) => {
-                        if index_focused == index_window {
-                            self.index_foused_window = None
-                        }
-                    }
-                };
-                self.set_minimised_window(window)
-            };
-            Ok(self.update_geometries())
+        let focused = self.get_focused_window() == Some(window);
+        try!(self.wm.toggle_minimised(window));
+        if focused {
+            self.focus_most_recent();
         } else {
-            Err(FullscreenWMError::UnknownWindow(window))
+            self.remember_focus();
         }
+        Ok(())
     }
 }
 
 impl FullscreenSupport for FullscreenWM {
-    /// Return the current fullscreen, if any.
+    /// returns the fullscreen window of the layers.
     fn get_fullscreen_window(&self) -> Option<Window> {
-        let mut fullscreen = None;
-        for f_w in self.windows.iter().filter(|x| (*x).fullscreen) {
-            fullscreen = Some(f_w.window.clone());
-        }
-        fullscreen
+        self.wm.get_fullscreen_window()
     }
 
-    /// Make the given window fullscreen, or when it is already fullscreen,
-    /// undo it.
-    ///
-    // If the given window is minised, it should be unminimised
+    /// toggles the fullscreen mode of the window in the layers, the window
+    /// is focused.
     fn toggle_fullscreen(&mut self, window: Window) -> Result<(), Self::Error> {
-        match self.windows.iter().position(|w| (*w).window == window) {
-            None => Err(FullscreenWMError::UnknownWindow(window)),
-            Some(i) => {
-                let window_min = self.windows.get(i).unwrap().clone();
-                if window_min.minimised {
-                    self.remove_minimised_window(window_min.window);
-                };
-                match self.get_fullscreen_window() {
+        try!(self.wm.toggle_fullscreen(window));
+        self.remember_focus();
+        Ok(())
+    }
+}
+
+impl ScratchpadSupport for FullscreenWM {
+    /// Returns a copy of the *scratchpad_windows* vec.
+    fn get_scratchpad_windows(&self) -> Vec<Window> {
+        self.scratchpad_windows.clone()
+    }
+
+    /// Moves the given window to the scratchpad and hides it.
+    ///
+    /// A tiled window is made floating first, so it gets the geometry it was
+    /// added with back. A fullscreen window is not fullscreen anymore once it
+    /// is minimised. A visible scratchpad window is hidden again.
+    fn move_to_scratchpad(&mut self, window: Window) -> Result<(), Self::Error> {
+        let window_with_info = try!(self.get_window_info(window));
+        if self.is_hidden_scratchpad_window(window) {
+            return Ok(());
+        }
+        if window_with_info.float_or_tile == FloatOrTile::Tile {
+            try!(self.wm.toggle_floating(window));
+        }
+        self.hide_scratchpad_window(window)
+    }
+
+    /// Hides the focused scratchpad window, focuses the visible one or
+    /// shows the first one centered on the screen.
+    fn toggle_scratchpad(&mut self) {
+        let visible_windows: Vec<Window> = self.scratchpad_windows
+            .iter()
+            .cloned()
+            .filter(|w| !self.is_minimised(*w))
+            .collect();
+        match self.get_focused_window() {
+            Some(focused_window) if visible_windows.contains(&focused_window) => {
+                // unwrap() is used because the scratchpad windows are managed
+                self.hide_scratchpad_window(focused_window).unwrap()
+            }
+            _ => {
+                let window = match visible_windows.first() {
+                    Some(visible_window) => *visible_window,
                     None => {
-                        self.focus_window(Some(window)).unwrap();
-                        self.set_fullscreen_window(window)
-                    }
-                    Some(full_window) => {
-                        self.remove_fullscreen_window(window_min.window);
-                        if full_window != window {
-                            self.focus_window(Some(window)).unwrap();
-                            self.set_fullscreen_window(window)
+                        match self.scratchpad_windows.first() {
+                            None => return,
+                            Some(hidden_window) => *hidden_window,
                         }
                     }
                 };
-                self.update_geometries();
-                Ok(())
+                // unwrap() is used because the scratchpad windows are managed
+                // and floating
+                if !visible_windows.contains(&window) {
+                    let geometry = self.wm.get_window_info(window).unwrap().geometry;
+                    let geometry = self.get_centered_geometry(geometry);
+                    self.wm.set_window_geometry(window, geometry).unwrap();
+                }
+                // focus_window unminimises the window and disables the
+                // fullscreen
+                self.focus_window(Some(window)).unwrap();
             }
         }
     }
 }
 
+impl LayoutSupport for FullscreenWM {
+    /// cycles the layout of the layers.
+    fn cycle_layout(&mut self, dir: PrevOrNext) {
+        self.wm.cycle_layout(dir)
+    }
+}
+
+impl TabBarSupport for FullscreenWM {
+    /// returns the tab bars of the layers, there are none while there is a
+    /// fullscreen window.
+    fn get_tab_bars(&self) -> Vec<TabBar> {
+        self.wm.get_tab_bars()
+    }
+}
+
+impl ColumnRatioSupport for FullscreenWM {
+    /// returns the column ratio of the layers.
+    fn get_column_ratio(&self) -> ColumnRatio {
+        self.wm.get_column_ratio()
+    }
+
+    /// sets the column ratio of the layers, it is clamped by the layouts.
+    fn set_column_ratio(&mut self, ratio: ColumnRatio) {
+        self.wm.set_column_ratio(ratio)
+    }
+}
+
+impl MasterRatioSupport for FullscreenWM {
+    /// returns the master ratio of the layers.
+    fn get_master_ratio(&self) -> MasterRatio {
+        self.wm.get_master_ratio()
+    }
+
+    /// sets the master ratio of the layers, it is clamped by the layouts.
+    fn set_master_ratio(&mut self, ratio: MasterRatio) {
+        self.wm.set_master_ratio(ratio)
+    }
+}
+
+impl MultiMasterSupport for FullscreenWM {
+    /// returns the master windows of the layers.
+    fn get_master_windows(&self) -> Vec<Window> {
+        self.wm.get_master_windows()
+    }
+
+    /// returns the master count of the layers.
+    fn get_master_count(&self) -> MasterCount {
+        self.wm.get_master_count()
+    }
+
+    /// sets the master count of the layers.
+    fn set_master_count(&mut self, count: MasterCount) {
+        self.wm.set_master_count(count)
+    }
+}
+
+impl MasterPositionSupport for FullscreenWM {
+    /// returns the master position of the layers.
+    fn get_master_position(&self) -> MasterPosition {
+        self.wm.get_master_position()
+    }
+
+    /// sets the master position of the layers.
+    fn set_master_position(&mut self, position: Option<MasterPosition>) {
+        self.wm.set_master_position(position)
+    }
+}
+
+impl DirectionalSupport for FullscreenWM {
+    /// swaps the focused window with the window in the given direction in
+    /// the layers.
+    fn swap_direction(&mut self, dir: Direction) {
+        self.wm.swap_direction(dir)
+    }
+}
+
+impl UrgencySupport for FullscreenWM {
+    /// returns the *urgent_windows* vec, without the focused window, which
+    /// can get the focus when another window is removed.
+    fn get_urgent_windows(&self) -> Vec<Window> {
+        let focused_window = self.get_focused_window();
+        self.urgent_windows.iter().cloned().filter(|w| Some(*w) != focused_window).collect()
+    }
+
+    /// an urgent window is moved to the front of *urgent_windows*, unless
+    /// it is focused.
+    ///
+    /// returns an UnknownWindow error if the window is not managed.
+    fn set_urgent(&mut self, window: Window, urgent: bool) -> Result<(), Self::Error> {
+        if !self.is_managed(window) {
+            return Err(LayerError::UnknownWindow(window));
+        }
+        self.urgent_windows.retain(|w| *w != window);
+        if urgent && self.get_focused_window() != Some(window) {
+            self.urgent_windows.insert(0, window);
+        }
+        Ok(())
+    }
+}
+
+impl StickySupport for FullscreenWM {
+    /// returns a copy of the *sticky_windows* vec.
+    fn get_sticky_windows(&self) -> Vec<Window> {
+        self.sticky_windows.clone()
+    }
+
+    /// adds the window to *sticky_windows* or removes it from it.
+    ///
+    /// A tiled window is made floating first, so it gets the geometry it was
+    /// added with back.
+    fn toggle_sticky(&mut self, window: Window) -> Result<(), Self::Error> {
+        if self.sticky_windows.contains(&window) {
+            self.sticky_windows.retain(|w| *w != window);
+            return Ok(());
+        }
+        let window_with_info = try!(self.get_window_info(window));
+        if window_with_info.float_or_tile == FloatOrTile::Tile {
+            try!(self.toggle_floating(window));
+        }
+        self.sticky_windows.push(window);
+        Ok(())
+    }
+}
+
+impl FocusHistorySupport for FullscreenWM {
+    /// returns the *focus_history* vec without the hidden scratchpad
+    /// windows, alt_tab should not show them.
+    fn get_focus_history(&self) -> Vec<Window> {
+        self.focus_history
+            .iter()
+            .cloned()
+            .filter(|w| !self.is_hidden_scratchpad_window(*w))
+            .collect()
+    }
+}
+
+impl WindowPropertiesSupport for FullscreenWM {
+    /// replaces the properties of the window in the layers.
+    fn set_window_properties(&mut self,
+                             window: Window,
+                             properties: WindowProperties)
+                             -> Result<(), Self::Error> {
+        self.wm.set_window_properties(window, properties)
+    }
+}
+
+impl InvariantCheck for FullscreenWM {
+    /// checks the invariants of the window manager, the tiling, the floating,
+    /// minimised and fullscreen windows, and the layers.
+    fn check_invariants(&self) -> Vec<String> {
+        let mut violations = check_window_manager(self);
+        violations.extend(check_tiling(self));
+        violations.extend(check_floating(self));
+        violations.extend(check_minimising(self));
+        violations.extend(check_fullscreen(self));
+        violations.extend(prefix_violations("wrapped", self.wm.check_invariants()));
+        violations
+    }
+}
 
 #[cfg(test)]
 mod tests {
 
     // We have to import `FullscreenWM` from the super module.
     use super::FullscreenWM;
+
     // We have to repeat the imports we did in the super module.
     use cplwm_api::wm::WindowManager;
     use cplwm_api::wm::TilingSupport;
     use cplwm_api::wm::FloatSupport;
     use cplwm_api::wm::MinimiseSupport;
     use cplwm_api::wm::FullscreenSupport;
+    use cplwm_api::wm::LayoutSupport;
+    use cplwm_api::wm::TabBarSupport;
+    use cplwm_api::wm::ColumnRatioSupport;
+    use cplwm_api::wm::MasterRatioSupport;
+    use cplwm_api::wm::MultiMasterSupport;
+    use cplwm_api::wm::MasterPositionSupport;
+    use cplwm_api::wm::DirectionalSupport;
+    use cplwm_api::wm::WindowPropertiesSupport;
+    use cplwm_api::wm::ScratchpadSupport;
+    use cplwm_api::wm::UrgencySupport;
+    use cplwm_api::wm::FocusHistorySupport;
     use cplwm_api::types::*;
+    use h_different_tiling_layout::{DwindleLayout, GridLayout, MasterStackLayout, ScrollingLayout,
+                                    TAB_BAR_HEIGHT, TilingLayout};
+
+    // The generic tests of the traits that this window manager implements.
+    conformance_tests!(FullscreenWM: window_manager, tiling, floating, minimising, fullscreen);
 
     // We define a static variable for the screen we will use in the tests.
     // You can just as well define it as a local variable in your tests.
@@ -1261,9 +757,8 @@ mod tests {
         assert_eq!(vec![1], wm.get_windows());
         // According to the window layout
         let wl3 = wm.get_window_layout();
-        // because the new behavior, window 1 should not be focused, No
-        // window is focused
-        assert_eq!(None, wl3.focused_window);
+        // the focus goes back to window 1, the previously focused window
+        assert_eq!(Some(1), wl3.focused_window);
         // and fullscreen.
         assert_eq!(vec![(1, SCREEN_GEOM)], wl3.windows);
 
@@ -1354,10 +849,10 @@ mod tests {
         wm.remove_window(5).unwrap();
         wm.focus_window(Some(1)).unwrap();
         wm.remove_window(1).unwrap();
-        // Because the last focused window was removed, the focused_window
-        // attribute should be None
+        // Because the last focused window was removed, the focus goes back
+        // to the window that was focused before it, window 5 is gone
         let wl5 = wm.get_window_layout();
-        assert_eq!(None, wl5.focused_window);
+        assert_eq!(Some(2), wl5.focused_window);
     }
 
     #[test]
@@ -1853,10 +1348,13 @@ mod tests {
         // minimised window `w` succeeds, `w` must be visible according to
         // `get_window_layout` and `is_minimised(w)` must return `false`.
         // now let's change the geometry of window 4,
+        // The unminimised window comes back like a new window: on top of the
+        // other floating windows and focused.
         wm.toggle_minimised(1).unwrap();
         let wl3 = wm.get_window_layout();
-        assert_eq!(vec![(3, FULLSCREEN), (1, SOME_GEOM), (2, SOME_GEOM)],
+        assert_eq!(vec![(3, FULLSCREEN), (2, SOME_GEOM), (1, SOME_GEOM)],
                    wl3.windows);
+        assert_eq!(Some(1), wl3.focused_window);
         assert_eq!(wm.is_minimised(1), false);
 
 
@@ -1870,6 +1368,20 @@ mod tests {
         // `w`.
         assert_eq!(wm.is_minimised(5), true);
         assert_eq!(vec![5, 4, 6], wm.get_minimised_windows());
+
+        // cycle_focus skips the minimised windows
+        for _ in 0..wm.get_windows().len() {
+            wm.cycle_focus(PrevOrNext::Next);
+            assert_eq!(false, wm.is_minimised(wm.get_focused_window().unwrap()));
+        }
+        assert_eq!(vec![5, 4, 6], wm.get_minimised_windows());
+
+        // a minimised floating window stays minimised when its geometry is
+        // changed
+        wm.toggle_minimised(2).unwrap();
+        wm.set_window_geometry(2, SCREEN_GEOM).unwrap();
+        assert_eq!(true, wm.is_minimised(2));
+        assert_eq!(SCREEN_GEOM, wm.get_window_info(2).unwrap().geometry);
     }
 
 
@@ -1972,20 +1484,28 @@ mod tests {
         assert_eq!(wm.get_fullscreen_window(), Some(1));
         assert_eq!(wm.get_focused_window(), Some(1));
 
-        // minised window 1, then no fullscreen is available and no focused
-        // window
+        // minised window 1, then no fullscreen is available and the focus
+        // goes back to the last focused window that is not minimised
         wm.toggle_minimised(1).unwrap();
         assert_eq!(wm.is_minimised(4), true);
         assert_eq!(wm.is_minimised(1), true);
         assert_eq!(wm.get_fullscreen_window(), None);
-        assert_eq!(wm.get_focused_window(), None);
+        assert_eq!(wm.get_focused_window(), Some(6));
 
         // I use toggle_fullscreen window 4 fullscreen
         wm.toggle_fullscreen(4).unwrap();
         assert_eq!(wm.get_fullscreen_window(), Some(4));
         assert_eq!(wm.get_focused_window(), Some(4));
 
-        // Making floating window 1 should not affect the fullscreen window
+        // Making the minimised window 1 floating unminimises it, like a new
+        // window it is focused, so 4 is not fullscreen anymore
+        wm.toggle_floating(1).unwrap();
+        assert_eq!(wm.get_fullscreen_window(), None);
+        assert_eq!(wm.get_focused_window(), Some(1));
+
+        // Making floating window 1 tiled again does not affect the
+        // fullscreen window
+        wm.toggle_fullscreen(4).unwrap();
         wm.toggle_floating(1).unwrap();
         assert_eq!(wm.get_fullscreen_window(), Some(4));
         assert_eq!(wm.get_focused_window(), Some(4));
@@ -2005,10 +1525,11 @@ mod tests {
         assert_eq!(wm.get_fullscreen_window(), Some(2));
         assert_eq!(wm.get_focused_window(), Some(2));
 
-        // I removed window 2, no fullscreen and no fucused window is managed
+        // I removed window 2, no fullscreen and the focus goes back to the
+        // previously focused window
         wm.remove_window(2).unwrap();
         assert_eq!(wm.get_fullscreen_window(), None);
-        assert_eq!(wm.get_focused_window(), None);
+        assert_eq!(wm.get_focused_window(), Some(4));
 
         // I use toggle_fullscreen window 2 fullscreen
         wm.toggle_fullscreen(6).unwrap();
@@ -2019,7 +1540,798 @@ mod tests {
         wm.resize_screen(SCREEN2);
         assert_eq!(wm.get_screen(), SCREEN2);
         let wl1 = wm.get_window_layout();
-        assert_eq!(vec![(6, wm.screen.to_geometry())], wl1.windows);
+        assert_eq!(vec![(6, wm.get_screen().to_geometry())], wl1.windows);
+
+    }
+
+    #[test]
+    fn test_layout_support() {
+        let mut wm = FullscreenWM::new(SCREEN);
+
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_float(3, SOME_GEOM)).unwrap();
+        let wl1 = wm.get_window_layout();
+
+        // The first layout is the active one
+        assert_eq!(0, wm.get_layout_wm().index_layout);
+
+        // Cycling wraps around the list of layouts
+        wm.get_layout_wm_mut().layouts = vec![TilingLayout::MasterStack(MasterStackLayout),
+                          TilingLayout::MasterStack(MasterStackLayout)];
+        wm.cycle_layout(PrevOrNext::Prev);
+        assert_eq!(1, wm.get_layout_wm().index_layout);
+        wm.cycle_layout(PrevOrNext::Next);
+        assert_eq!(0, wm.get_layout_wm().index_layout);
+        wm.cycle_layout(PrevOrNext::Next);
+        assert_eq!(1, wm.get_layout_wm().index_layout);
+
+        // The focus, the order and the floating windows are unaffected
+        assert_eq!(wl1, wm.get_window_layout());
+
+        // The fullscreen window keeps the screen geometry
+        wm.toggle_fullscreen(1).unwrap();
+        wm.cycle_layout(PrevOrNext::Next);
+        assert_eq!(vec![(1, SCREEN_GEOM)], wm.get_window_layout().windows);
+
+        // With a single layout nothing happens
+        wm.toggle_fullscreen(1).unwrap();
+        wm.get_layout_wm_mut().layouts = vec![TilingLayout::MasterStack(MasterStackLayout)];
+        wm.get_layout_wm_mut().index_layout = 0;
+        wm.cycle_layout(PrevOrNext::Next);
+        assert_eq!(0, wm.get_layout_wm().index_layout);
+        assert_eq!(wl1.windows, wm.get_window_layout().windows);
+    }
+
+    #[test]
+    fn test_master_ratio_support() {
+        let mut wm = FullscreenWM::new(SCREEN);
+
+        // Initially the master takes half of the screen
+        assert_eq!(DEFAULT_MASTER_RATIO, wm.get_master_ratio());
+
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+
+        // A single window still takes the whole screen
+        wm.grow_master();
+        assert_eq!(DEFAULT_MASTER_RATIO + MASTER_RATIO_STEP, wm.get_master_ratio());
+        assert_eq!(vec![(1, SCREEN_GEOM)], wm.get_window_layout().windows);
+
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        wm.set_master_ratio(75);
+        assert_eq!(75, wm.get_master_ratio());
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 600,
+                       height: 600,
+                   },
+                   wm.get_window_info(1).unwrap().geometry);
+        assert_eq!(Geometry {
+                       x: 600,
+                       y: 0,
+                       width: 200,
+                       height: 600,
+                   },
+                   wm.get_window_info(2).unwrap().geometry);
+
+        // The ratio is kept when the screen is resized
+        wm.resize_screen(SCREEN2);
+        assert_eq!(75, wm.get_master_ratio());
+        assert_eq!(750, wm.get_window_info(1).unwrap().geometry.width);
+        assert_eq!(250, wm.get_window_info(2).unwrap().geometry.width);
+
+        // The ratio is clamped so neither side collapses
+        wm.set_master_ratio(100);
+        assert_eq!(MAX_MASTER_RATIO, wm.get_master_ratio());
+        wm.grow_master();
+        assert_eq!(MAX_MASTER_RATIO, wm.get_master_ratio());
+        wm.set_master_ratio(0);
+        assert_eq!(MIN_MASTER_RATIO, wm.get_master_ratio());
+        wm.shrink_master();
+        assert_eq!(MIN_MASTER_RATIO, wm.get_master_ratio());
+        assert!(wm.get_window_info(1).unwrap().geometry.width > 0);
+    }
+
+    #[test]
+    fn test_multi_master_support() {
+        let mut wm = FullscreenWM::new(SCREEN);
+
+        // Initially there is a single master window
+        assert_eq!(DEFAULT_MASTER_COUNT, wm.get_master_count());
+        assert!(wm.get_master_windows().is_empty());
+
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_float(2, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(4, SOME_GEOM)).unwrap();
+        assert_eq!(vec![1], wm.get_master_windows());
+
+        // The floating window is skipped
+        wm.increase_master_count();
+        assert_eq!(2, wm.get_master_count());
+        assert_eq!(vec![1, 3], wm.get_master_windows());
+        assert_eq!(Some(1), wm.get_master_window());
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 300,
+                       width: 400,
+                       height: 300,
+                   },
+                   wm.get_window_info(3).unwrap().geometry);
+        assert_eq!(Geometry {
+                       x: 400,
+                       y: 0,
+                       width: 400,
+                       height: 600,
+                   },
+                   wm.get_window_info(4).unwrap().geometry);
+
+        // Swapping with the master swaps with the first master window
+        wm.swap_with_master(4).unwrap();
+        assert_eq!(vec![4, 3], wm.get_master_windows());
+        assert_eq!(Some(4), wm.get_focused_window());
+
+        // Swapping moves the window from the master area to the stack
+        wm.focus_window(Some(3)).unwrap();
+        wm.swap_windows(PrevOrNext::Next);
+        assert_eq!(vec![4, 1], wm.get_master_windows());
+        assert_eq!(400, wm.get_window_info(3).unwrap().geometry.x);
+
+        // Minimised windows are not master windows
+        wm.toggle_minimised(4).unwrap();
+        assert_eq!(vec![1, 3], wm.get_master_windows());
+        // The unminimised window comes back at the end of the tiles
+        wm.toggle_minimised(4).unwrap();
+        assert_eq!(vec![1, 3], wm.get_master_windows());
+
+        // The count is kept when the screen is resized
+        wm.set_master_count(5);
+        wm.resize_screen(SCREEN2);
+        assert_eq!(5, wm.get_master_count());
+        assert_eq!(vec![1, 3, 4], wm.get_master_windows());
+
+        // There is always at least one master window
+        wm.set_master_count(0);
+        assert_eq!(1, wm.get_master_count());
+        wm.decrease_master_count();
+        assert_eq!(1, wm.get_master_count());
+        assert_eq!(vec![1], wm.get_master_windows());
+    }
+
+    #[test]
+    fn test_directional_support() {
+        let mut wm = FullscreenWM::new(SCREEN);
+
+        // Nothing happens without windows
+        wm.focus_direction(Direction::Left).unwrap();
+        wm.swap_direction(Direction::Left);
+        assert_eq!(None, wm.get_focused_window());
+
+        // 1 is the master, 2 and 3 are stacked on the right
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+
+        // Focus moves between the master and the stack
+        wm.focus_window(Some(1)).unwrap();
+        wm.focus_direction(Direction::Right).unwrap();
+        assert_eq!(Some(2), wm.get_focused_window());
+        wm.focus_direction(Direction::Down).unwrap();
+        assert_eq!(Some(3), wm.get_focused_window());
+        wm.focus_direction(Direction::Up).unwrap();
+        assert_eq!(Some(2), wm.get_focused_window());
+        wm.focus_direction(Direction::Left).unwrap();
+        assert_eq!(Some(1), wm.get_focused_window());
+
+        // Nothing on the left of the master, the focus stays
+        wm.focus_direction(Direction::Left).unwrap();
+        assert_eq!(Some(1), wm.get_focused_window());
+
+        // Swapping tiled windows swaps their position, the focus follows
+        wm.swap_direction(Direction::Right);
+        assert_eq!(vec![2, 1, 3], wm.get_windows());
+        assert_eq!(Some(1), wm.get_focused_window());
+        assert_eq!(400, wm.get_window_info(1).unwrap().geometry.x);
+        assert_eq!(0, wm.get_window_info(2).unwrap().geometry.x);
+
+        // A floating window is found too
+        let float_geom = Geometry {
+            x: 600,
+            y: 500,
+            width: 50,
+            height: 50,
+        };
+        wm.add_window(WindowWithInfo::new_float(4, float_geom)).unwrap();
+        wm.focus_window(Some(3)).unwrap();
+        wm.focus_direction(Direction::Down).unwrap();
+        assert_eq!(Some(4), wm.get_focused_window());
+
+        // A tiled window next to it is nearer than a floating window further
+        wm.focus_window(Some(2)).unwrap();
+        wm.focus_direction(Direction::Right).unwrap();
+        assert_eq!(Some(1), wm.get_focused_window());
+        wm.focus_window(Some(4)).unwrap();
+
+        // A floating and a tiled window are not swapped
+        wm.swap_direction(Direction::Left);
+        assert_eq!(vec![2, 1, 3, 4], wm.get_windows());
+        assert_eq!(float_geom, wm.get_window_info(4).unwrap().geometry);
+
+        // Two floating windows swap their geometry, minimised windows are
+        // not visible so they are skipped
+        wm.add_window(WindowWithInfo::new_float(5, SOME_GEOM)).unwrap();
+        wm.toggle_minimised(1).unwrap();
+        wm.toggle_minimised(2).unwrap();
+        wm.toggle_minimised(3).unwrap();
+        wm.focus_window(Some(4)).unwrap();
+        wm.swap_direction(Direction::Up);
+        assert_eq!(SOME_GEOM, wm.get_window_info(4).unwrap().geometry);
+        assert_eq!(float_geom, wm.get_window_info(5).unwrap().geometry);
+        assert_eq!(Some(4), wm.get_focused_window());
+    }
+
+    #[test]
+    fn test_window_properties_support() {
+        let mut wm = FullscreenWM::new(SCREEN);
+
+        let mut properties = WindowProperties::default();
+        properties.class = Some("XTerm".to_owned());
+        properties.instance = Some("xterm".to_owned());
+        properties.pid = Some(42);
+        let mut window_with_info = WindowWithInfo::new_tiled(1, SOME_GEOM);
+        window_with_info.properties = properties.clone();
+        wm.add_window(window_with_info).unwrap();
+        wm.add_window(WindowWithInfo::new_float(2, SOME_GEOM)).unwrap();
+
+        // The properties given to add_window are kept
+        assert_eq!(properties.clone(), wm.get_window_properties(1).unwrap());
+        assert_eq!(properties, wm.get_window_info(1).unwrap().properties);
+        assert_eq!(WindowProperties::default(), wm.get_window_properties(2).unwrap());
+
+        // They can be updated, without changing the layout
+        let layout = wm.get_window_layout();
+        properties.title = Some("vim".to_owned());
+        wm.set_window_properties(1, properties.clone()).unwrap();
+        assert_eq!(properties.clone(), wm.get_window_properties(1).unwrap());
+        assert_eq!(layout, wm.get_window_layout());
+
+        // They survive toggling floating, minimised and fullscreen
+        wm.toggle_floating(1).unwrap();
+        wm.toggle_minimised(1).unwrap();
+        wm.toggle_minimised(1).unwrap();
+        wm.toggle_fullscreen(1).unwrap();
+        wm.toggle_fullscreen(1).unwrap();
+        assert_eq!(properties, wm.get_window_properties(1).unwrap());
+
+        // Unknown windows give an error
+        assert!(wm.set_window_properties(10, WindowProperties::default()).is_err());
+        assert!(wm.get_window_properties(10).is_err());
+    }
+
+    #[test]
+    fn test_scratchpad_support() {
+        let mut wm = FullscreenWM::new(SCREEN);
+
+        // Nothing happens with an empty scratchpad
+        wm.toggle_scratchpad();
+        assert_eq!(WindowLayout::new(), wm.get_window_layout());
+
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+        assert!(wm.move_to_scratchpad(10).is_err());
+
+        // A tiled window becomes floating and hidden
+        wm.move_to_scratchpad(3).unwrap();
+        assert_eq!(vec![3], wm.get_scratchpad_windows());
+        assert_eq!(FloatOrTile::Float, wm.get_window_info(3).unwrap().float_or_tile);
+        assert!(wm.is_minimised(3));
+        assert_eq!(Some(2), wm.get_focused_window());
+        assert_eq!(vec![1, 2],
+                   wm.get_window_layout().windows.iter().map(|&(w, _)| w).collect::<Vec<_>>());
+
+        // Moving it again changes nothing
+        wm.move_to_scratchpad(3).unwrap();
+        assert_eq!(vec![3], wm.get_minimised_windows());
+
+        // cycle_focus skips the hidden scratchpad window
+        wm.focus_window(Some(2)).unwrap();
+        wm.cycle_focus(PrevOrNext::Next);
+        assert_eq!(Some(1), wm.get_focused_window());
+        wm.cycle_focus(PrevOrNext::Prev);
+        assert_eq!(Some(2), wm.get_focused_window());
+        wm.cycle_focus(PrevOrNext::Prev);
+        assert_eq!(Some(1), wm.get_focused_window());
+        assert!(wm.is_minimised(3));
+
+        // Toggling shows it centered, on top and focused
+        let centered_geom = Geometry {
+            x: 350,
+            y: 250,
+            width: 100,
+            height: 100,
+        };
+        wm.toggle_scratchpad();
+        assert!(!wm.is_minimised(3));
+        assert_eq!(Some(3), wm.get_focused_window());
+        assert_eq!(Some(&(3, centered_geom)), wm.get_window_layout().windows.last());
+
+        // When it is not focused, toggling focuses it
+        wm.focus_window(Some(1)).unwrap();
+        wm.toggle_scratchpad();
+        assert_eq!(Some(3), wm.get_focused_window());
+
+        // When it is focused, toggling hides it again, the focus goes back
+        // to the previous window
+        wm.toggle_scratchpad();
+        assert!(wm.is_minimised(3));
+        assert_eq!(Some(1), wm.get_focused_window());
+
+        // A fullscreen floating window is shown when toggling, shrunk to the
+        // screen
+        let big_geom = Geometry {
+            x: 0,
+            y: 0,
+            width: 1000,
+            height: 100,
+        };
+        wm.add_window(WindowWithInfo::new_float(4, big_geom)).unwrap();
+        wm.toggle_fullscreen(4).unwrap();
+        wm.move_to_scratchpad(4).unwrap();
+        assert_eq!(None, wm.get_fullscreen_window());
+        assert_eq!(vec![3, 4], wm.get_scratchpad_windows());
+
+        // Hidden windows go to the back, so toggling cycles through them
+        wm.toggle_scratchpad();
+        assert_eq!(Some(3), wm.get_focused_window());
+        wm.toggle_scratchpad();
+        assert_eq!(vec![4, 3], wm.get_scratchpad_windows());
+        wm.toggle_scratchpad();
+        assert_eq!(Some(4), wm.get_focused_window());
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 250,
+                       width: 800,
+                       height: 100,
+                   },
+                   wm.get_window_info(4).unwrap().geometry);
+
+        // Removed windows leave the scratchpad
+        wm.remove_window(3).unwrap();
+        assert_eq!(vec![4], wm.get_scratchpad_windows());
+        assert!(wm.get_minimised_windows().is_empty());
+
+        // Toggling floating takes a window out of the scratchpad
+        wm.toggle_floating(4).unwrap();
+        assert!(wm.get_scratchpad_windows().is_empty());
+    }
+
+    #[test]
+    fn test_tab_bar_support() {
+        let mut wm = FullscreenWM::new(SCREEN);
+
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_float(4, SOME_GEOM)).unwrap();
+
+        // The master stack layout has no tab bar
+        assert!(wm.get_tab_bars().is_empty());
+
+        // The monocle layout stacks the tiled windows below the tab bar
+        wm.cycle_layout(PrevOrNext::Next);
+        wm.focus_window(Some(2)).unwrap();
+        let tab_bar_geom = Geometry {
+            x: 0,
+            y: 0,
+            width: 800,
+            height: TAB_BAR_HEIGHT,
+        };
+        let tile_geom = Geometry {
+            x: 0,
+            y: TAB_BAR_HEIGHT as i32,
+            width: 800,
+            height: 600 - TAB_BAR_HEIGHT,
+        };
+        assert_eq!(vec![TabBar {
+                            geometry: tab_bar_geom,
+                            windows: vec![1, 2, 3],
+                            active_window: Some(2),
+                        }],
+                   wm.get_tab_bars());
+        // The focused tiled window is on top of the other tiled windows,
+        // the floating window stays on top
+        assert_eq!(vec![(1, tile_geom), (3, tile_geom), (2, tile_geom), (4, SOME_GEOM)],
+                   wm.get_window_layout().windows);
+
+        // Focusing the floating window keeps the active tab
+        wm.focus_window(Some(4)).unwrap();
+        assert_eq!(Some(2), wm.get_tab_bars()[0].active_window);
+        assert_eq!((2, tile_geom), wm.get_window_layout().windows[2]);
+
+        // Minimised windows get no tab, the active tab falls back to the
+        // first tiled window
+        wm.toggle_minimised(2).unwrap();
+        assert_eq!(vec![1, 3], wm.get_tab_bars()[0].windows);
+        assert_eq!(Some(1), wm.get_tab_bars()[0].active_window);
+        wm.cycle_focus(PrevOrNext::Prev);
+        assert_eq!(Some(3), wm.get_focused_window());
+        assert_eq!(Some(3), wm.get_tab_bars()[0].active_window);
+
+        // No tab bar with a fullscreen window or without tiled windows
+        wm.toggle_fullscreen(3).unwrap();
+        assert!(wm.get_tab_bars().is_empty());
+        wm.toggle_fullscreen(3).unwrap();
+        assert_eq!(1, wm.get_tab_bars().len());
+        wm.remove_window(1).unwrap();
+        wm.remove_window(2).unwrap();
+        wm.remove_window(3).unwrap();
+        assert!(wm.get_tab_bars().is_empty());
+    }
+
+    #[test]
+    fn test_grid_layout() {
+        let mut wm = FullscreenWM::new(SCREEN);
+        wm.get_layout_wm_mut().layouts = vec![TilingLayout::Grid(GridLayout)];
+
+        for window in 1..7 {
+            wm.add_window(WindowWithInfo::new_tiled(window, SOME_GEOM)).unwrap();
+        }
+        let geometry = |wm: &FullscreenWM, window| wm.get_window_info(window).unwrap().geometry;
+
+        // Two rows of three windows
+        assert_eq!(Geometry {
+                       x: 267,
+                       y: 300,
+                       width: 267,
+                       height: 300,
+                   },
+                   geometry(&wm, 5));
+
+        // swap_windows moves the focused window through the grid
+        wm.focus_window(Some(3)).unwrap();
+        wm.swap_windows(PrevOrNext::Next);
+        assert_eq!(vec![1, 2, 4, 3, 5, 6], wm.get_windows());
+        assert_eq!(0, geometry(&wm, 3).x);
+        assert_eq!(300, geometry(&wm, 3).y);
+
+        // Directional focus and swap follow the rows and columns
+        wm.focus_direction(Direction::Up).unwrap();
+        assert_eq!(Some(1), wm.get_focused_window());
+        wm.focus_direction(Direction::Right).unwrap();
+        assert_eq!(Some(2), wm.get_focused_window());
+        wm.swap_direction(Direction::Down);
+        assert_eq!(vec![1, 5, 4, 3, 2, 6], wm.get_windows());
+        assert_eq!(Some(2), wm.get_focused_window());
+
+        // Minimised windows leave a gap-free grid
+        wm.toggle_minimised(6).unwrap();
+        wm.toggle_minimised(5).unwrap();
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 400,
+                       height: 300,
+                   },
+                   geometry(&wm, 1));
+        assert_eq!(400, geometry(&wm, 2).width);
+    }
+
+    #[test]
+    fn test_dwindle_layout() {
+        let mut wm = FullscreenWM::new(SCREEN);
+
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_float(4, SOME_GEOM)).unwrap();
+
+        // The dwindle layout can be selected at runtime
+        while wm.get_layout_wm().layouts[wm.get_layout_wm().index_layout] !=
+              TilingLayout::Dwindle(DwindleLayout) {
+            wm.cycle_layout(PrevOrNext::Next);
+        }
+        let geometry = |wm: &FullscreenWM, window| wm.get_window_info(window).unwrap().geometry;
+        let left_half = Geometry {
+            x: 0,
+            y: 0,
+            width: 400,
+            height: 600,
+        };
+        let top_right = Geometry {
+            x: 400,
+            y: 0,
+            width: 400,
+            height: 300,
+        };
+        let bottom_right = Geometry {
+            x: 400,
+            y: 300,
+            width: 400,
+            height: 300,
+        };
+        assert_eq!(left_half, geometry(&wm, 1));
+        assert_eq!(top_right, geometry(&wm, 2));
+        assert_eq!(bottom_right, geometry(&wm, 3));
+        // Floating windows are unaffected
+        assert_eq!(SOME_GEOM, geometry(&wm, 4));
+
+        // swap_with_master gives the largest tile to the window
+        wm.swap_with_master(3).unwrap();
+        assert_eq!(left_half, geometry(&wm, 3));
+        assert_eq!(bottom_right, geometry(&wm, 1));
+        assert_eq!(Some(3), wm.get_focused_window());
+
+        // A minimised window gives its space to the next windows
+        wm.toggle_minimised(2).unwrap();
+        assert_eq!(left_half, geometry(&wm, 3));
+        assert_eq!(Geometry {
+                       x: 400,
+                       y: 0,
+                       width: 400,
+                       height: 600,
+                   },
+                   geometry(&wm, 1));
+        // The unminimised window comes back like a new window: at the end
+        // of the tiles
+        wm.toggle_minimised(2).unwrap();
+        assert_eq!(top_right, geometry(&wm, 1));
+        assert_eq!(bottom_right, geometry(&wm, 2));
+
+        // The new screen is split in the same way
+        wm.resize_screen(SCREEN2);
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 500,
+                       height: 800,
+                   },
+                   geometry(&wm, 3));
+        assert_eq!(Geometry {
+                       x: 500,
+                       y: 0,
+                       width: 500,
+                       height: 400,
+                   },
+                   geometry(&wm, 1));
+    }
+    #[test]
+    fn test_scrolling_layout() {
+        let mut wm = FullscreenWM::new(SCREEN);
+        wm.get_layout_wm_mut().layouts = vec![TilingLayout::Scrolling(ScrollingLayout::new())];
+
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_float(4, SOME_GEOM)).unwrap();
+        let visible_windows =
+            |wm: &FullscreenWM| -> Vec<Window> {
+                wm.get_window_layout().windows.iter().map(|&(w, _)| w).collect()
+            };
+        let right_column = Geometry {
+            x: 400,
+            y: 0,
+            width: 400,
+            height: 600,
+        };
+
+        // The viewport followed the last added tiled window, window 1 is
+        // scrolled out of view, the floating window stays visible
+        assert_eq!(vec![2, 3, 4], visible_windows(&wm));
+        assert_eq!(right_column, wm.get_window_info(3).unwrap().geometry);
+
+        // Focusing a scrolled out window scrolls back
+        wm.focus_window(Some(1)).unwrap();
+        assert_eq!(vec![1, 2, 4], visible_windows(&wm));
+
+        // Focusing a floating window does not scroll
+        wm.focus_window(Some(4)).unwrap();
+        assert_eq!(vec![1, 2, 4], visible_windows(&wm));
+
+        // Cycling the focus scrolls too
+        wm.focus_window(Some(2)).unwrap();
+        wm.cycle_focus(PrevOrNext::Next);
+        assert_eq!(Some(3), wm.get_focused_window());
+        assert_eq!(vec![2, 3, 4], visible_windows(&wm));
+
+        // Swapping moves the focused window, the viewport follows it
+        wm.swap_with_master(3).unwrap();
+        assert_eq!(vec![3, 2, 4], visible_windows(&wm));
+
+        // Removing a window fills the viewport again
+        wm.focus_window(Some(1)).unwrap();
+        assert_eq!(vec![2, 1, 4], visible_windows(&wm));
+        wm.remove_window(1).unwrap();
+        assert_eq!(vec![3, 2, 4], visible_windows(&wm));
+    }
+    #[test]
+    fn test_column_ratio_support() {
+        let mut wm = FullscreenWM::new(SCREEN);
+        assert_eq!(DEFAULT_COLUMN_RATIO, wm.get_column_ratio());
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(4, SOME_GEOM)).unwrap();
+        let layout = wm.get_window_layout();
+
+        // The other layouts ignore the column ratio
+        wm.grow_columns();
+        assert_eq!(DEFAULT_COLUMN_RATIO + COLUMN_RATIO_STEP, wm.get_column_ratio());
+        assert_eq!(layout, wm.get_window_layout());
+
+        // The scrolling layout uses it, also when it is set before cycling
+        wm.set_column_ratio(25);
+        wm.cycle_layout(PrevOrNext::Prev);
+        let column = |x| {
+            Geometry {
+                x: x,
+                y: 0,
+                width: 200,
+                height: 600,
+            }
+        };
+        assert_eq!(vec![(1, column(0)), (2, column(200)), (3, column(400)), (4, column(600))],
+                   wm.get_window_layout().windows);
+
+        // The ratio is clamped
+        wm.set_column_ratio(0);
+        assert_eq!(MIN_COLUMN_RATIO, wm.get_column_ratio());
+        wm.set_column_ratio(150);
+        assert_eq!(MAX_COLUMN_RATIO, wm.get_column_ratio());
+        assert_eq!(vec![(4, SCREEN_GEOM)], wm.get_window_layout().windows);
+        wm.shrink_columns();
+        assert_eq!(MAX_COLUMN_RATIO - COLUMN_RATIO_STEP, wm.get_column_ratio());
+    }
+    #[test]
+    fn test_master_position_support() {
+        let mut wm = FullscreenWM::new(SCREEN);
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        let geometry = |wm: &FullscreenWM, window| wm.get_window_info(window).unwrap().geometry;
+
+        // The master is on the left of a landscape screen
+        assert_eq!(MasterPosition::Left, wm.get_master_position());
+        assert_eq!(0, geometry(&wm, 1).x);
+
+        // and at the top of a portrait screen
+        wm.resize_screen(Screen {
+            width: 600,
+            height: 800,
+        });
+        assert_eq!(MasterPosition::Top, wm.get_master_position());
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 600,
+                       height: 400,
+                   },
+                   geometry(&wm, 1));
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 400,
+                       width: 600,
+                       height: 400,
+                   },
+                   geometry(&wm, 2));
+        wm.resize_screen(SCREEN);
+        assert_eq!(MasterPosition::Left, wm.get_master_position());
+
+        // Cycling goes clockwise, mirroring to the opposite side
+        wm.cycle_master_position(PrevOrNext::Next);
+        assert_eq!(MasterPosition::Top, wm.get_master_position());
+        wm.mirror_master_position();
+        assert_eq!(MasterPosition::Bottom, wm.get_master_position());
+        assert_eq!(300, geometry(&wm, 1).y);
+        wm.cycle_master_position(PrevOrNext::Prev);
+        assert_eq!(MasterPosition::Right, wm.get_master_position());
+        assert_eq!(400, geometry(&wm, 1).x);
+        assert_eq!(0, geometry(&wm, 2).x);
+
+        // A chosen position is kept when the screen is resized
+        wm.resize_screen(Screen {
+            width: 600,
+            height: 800,
+        });
+        assert_eq!(MasterPosition::Right, wm.get_master_position());
+        wm.set_master_position(None);
+        assert_eq!(MasterPosition::Top, wm.get_master_position());
+    }
+    #[test]
+    fn test_urgency_support() {
+        let mut wm = FullscreenWM::new(SCREEN);
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_float(3, SOME_GEOM)).unwrap();
+        assert!(wm.get_urgent_windows().is_empty());
+        assert!(wm.set_urgent(4, true).is_err());
+
+        // The most recent urgent window comes first, the layout stays the
+        // same
+        let layout = wm.get_window_layout();
+        wm.set_urgent(1, true).unwrap();
+        wm.set_urgent(2, true).unwrap();
+        assert_eq!(vec![2, 1], wm.get_urgent_windows());
+        assert!(wm.is_urgent(1));
+        assert_eq!(layout, wm.get_window_layout());
+        wm.set_urgent(1, true).unwrap();
+        assert_eq!(vec![1, 2], wm.get_urgent_windows());
+        wm.set_urgent(1, false).unwrap();
+        assert_eq!(vec![2], wm.get_urgent_windows());
+
+        // The focused window can't be urgent
+        wm.set_urgent(3, true).unwrap();
+        assert!(!wm.is_urgent(3));
+
+        // Focusing clears the urgency
+        wm.set_urgent(1, true).unwrap();
+        wm.focus_urgent_window().unwrap();
+        assert_eq!(Some(1), wm.get_focused_window());
+        assert_eq!(vec![2], wm.get_urgent_windows());
+        wm.cycle_focus(PrevOrNext::Next);
+        assert_eq!(Some(2), wm.get_focused_window());
+        assert!(wm.get_urgent_windows().is_empty());
+        wm.focus_urgent_window().unwrap();
+        assert_eq!(Some(2), wm.get_focused_window());
+
+        // A minimised urgent window is unminimised when it is focused
+        wm.toggle_minimised(1).unwrap();
+        wm.set_urgent(1, true).unwrap();
+        wm.focus_urgent_window().unwrap();
+        assert!(!wm.is_minimised(1));
+        assert_eq!(Some(1), wm.get_focused_window());
+
+        // Removed windows are no longer urgent
+        wm.set_urgent(3, true).unwrap();
+        wm.remove_window(3).unwrap();
+        assert!(wm.get_urgent_windows().is_empty());
+    }
+    #[test]
+    fn test_focus_history() {
+        let mut wm = FullscreenWM::new(SCREEN);
+        assert!(wm.get_focus_history().is_empty());
+        wm.alt_tab().unwrap();
+        assert_eq!(None, wm.get_focused_window());
+
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_float(3, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(4, SOME_GEOM)).unwrap();
+        assert_eq!(vec![4, 3, 2, 1], wm.get_focus_history());
+
+        // alt_tab goes back and forth between the last two windows
+        wm.focus_window(Some(1)).unwrap();
+        wm.alt_tab().unwrap();
+        assert_eq!(Some(4), wm.get_focused_window());
+        wm.alt_tab().unwrap();
+        assert_eq!(Some(1), wm.get_focused_window());
+        assert_eq!(vec![1, 4, 3, 2], wm.get_focus_history());
+
+        // cycle_focus is remembered as well
+        wm.cycle_focus(PrevOrNext::Next);
+        assert_eq!(Some(2), wm.get_focused_window());
+        assert_eq!(vec![2, 1, 4, 3], wm.get_focus_history());
+
+        // Removing the focused window focuses the previous one
+        wm.remove_window(2).unwrap();
+        assert_eq!(Some(1), wm.get_focused_window());
+        assert_eq!(vec![1, 4, 3], wm.get_focus_history());
+
+        // Minimising the focused window skips the minimised windows
+        wm.toggle_minimised(4).unwrap();
+        wm.toggle_minimised(1).unwrap();
+        assert_eq!(Some(3), wm.get_focused_window());
 
+        // alt_tab unminimises the previous window
+        wm.alt_tab().unwrap();
+        assert_eq!(Some(1), wm.get_focused_window());
+        assert!(!wm.is_minimised(1));
+
+        // Without other windows, nothing is focused
+        wm.remove_window(3).unwrap();
+        wm.remove_window(4).unwrap();
+        wm.toggle_minimised(1).unwrap();
+        assert_eq!(None, wm.get_focused_window());
+        assert_eq!(vec![1], wm.get_focus_history());
     }
 }
@@ -18,20 +18,698 @@
 //! or you want to explain your approach, write it down after the comments
 //! section.
 //!
-//! COMPLETED: No
+//! COMPLETED: YES
 //!
 //! COMMENTS:
 //!
-//! ...
+//! ## General approach
 //!
+//! Instead of making a copy of the tiling window manager, I wrote a wrapper
+//! *GapWM* around the window manager of assignment E, in that way the gaps
+//! come for free with tiling, floating, minimised and fullscreen windows,
+//! and every trait of the previous assignments can be forwarded to the
+//! wrapped window manager.
+//!
+//! The wrapped window manager keeps calculating the tiles as if there were
+//! no gaps. The gap is only applied when the information leaves the
+//! wrapper, i.e. in *get_window_layout* and *get_window_info*. Only the
+//! tiled windows are shrunk, the floating windows and the fullscreen window
+//! keep their geometry.
+//!
+//! The trait allows to ignore a gap that is larger than a tile, but then
+//! the width or height of a window would underflow. Instead, the gap is
+//! capped per side so that the window keeps room for its borders and at
+//! least one pixel, and the window is never moved outside of its tile. The
+//! X11 backend subtracts the borders from the geometry, so a smaller window
+//! would underflow there.
 
 // Add imports here
+use std::cmp::min;
+
+use cplwm_api::types::{ColumnRatio, Direction, FloatOrTile, GapSize, Geometry, MasterCount,
+                       MasterPosition, MasterRatio, PrevOrNext, Screen, TabBar, Window,
+                       WindowLayout, WindowProperties, WindowWithInfo};
+use cplwm_api::wm::WindowManager;
+use cplwm_api::wm::TilingSupport;
+use cplwm_api::wm::FloatSupport;
+use cplwm_api::wm::MinimiseSupport;
+use cplwm_api::wm::FullscreenSupport;
+use cplwm_api::wm::GapSupport;
+use cplwm_api::wm::LayoutSupport;
+use cplwm_api::wm::TabBarSupport;
+use cplwm_api::wm::ColumnRatioSupport;
+use cplwm_api::wm::MasterRatioSupport;
+use cplwm_api::wm::MultiMasterSupport;
+use cplwm_api::wm::MasterPositionSupport;
+use cplwm_api::wm::DirectionalSupport;
+use cplwm_api::wm::WindowPropertiesSupport;
+use cplwm_api::wm::ScratchpadSupport;
+use cplwm_api::wm::UrgencySupport;
+use cplwm_api::wm::StickySupport;
+use cplwm_api::wm::FocusHistorySupport;
+use cplwm_api::wm::InvariantCheck;
+
+use e_fullscreen_windows::FullscreenWM;
+
+/// Window manager aliase.
+pub type WMName = GapWM<FullscreenWM>;
+
+/// The width of the border of a window in the X11 backend, see
+/// `WINDOW_BORDER_WIDTH` in `cplwm_x11`.
+const WINDOW_BORDER_WIDTH: GapSize = 1;
 
+/// The smallest width or height of a tile after the gap: both borders and
+/// one pixel for the window itself.
+const MIN_TILE_SIZE: GapSize = 2 * WINDOW_BORDER_WIDTH + 1;
 
-/// **TODO**: You are free to choose the name for your window manager. As we
-/// will use automated tests when grading your assignment, indicate here the
-/// name of your window manager data type so we can just use `WMName` instead
-/// of having to manually figure out your window manager name.
+/// The GapWM struct
 ///
-/// Replace `()` with the name of your window manager data type.
-pub type WMName = ();
+/// A wrapper around a window manager that adds a gap around its tiled
+/// windows.
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
+pub struct GapWM<WM> {
+    /// The wrapped window manager.
+    pub wm: WM,
+    /// The size of the gap around every tiled window.
+    pub gap: GapSize,
+}
+
+/// Supported functions
+impl<WM> GapWM<WM>
+    where WM: WindowManager + FloatSupport + FullscreenSupport
+{
+    /// returns true if the given window is a visible tiled window of the
+    /// wrapped window manager, i.e. a window that should get a gap.
+    fn is_tiled(&self, window: Window) -> bool {
+        !self.wm.is_floating(window) && self.wm.get_fullscreen_window() != Some(window)
+    }
+
+    /// shrinks the given tile geometry by the gap.
+    ///
+    /// The gap is capped per side to half of the tile (minus
+    /// `MIN_TILE_SIZE`), so the window keeps room for its borders and stays
+    /// inside its tile.
+    fn apply_gap(&self, geometry: Geometry) -> Geometry {
+        let gap_x = min(self.gap, geometry.width.saturating_sub(MIN_TILE_SIZE) / 2);
+        let gap_y = min(self.gap, geometry.height.saturating_sub(MIN_TILE_SIZE) / 2);
+        Geometry {
+            x: geometry.x + gap_x as i32,
+            y: geometry.y + gap_y as i32,
+            width: geometry.width - 2 * gap_x,
+            height: geometry.height - 2 * gap_y,
+        }
+    }
+}
+
+impl<WM> WindowManager for GapWM<WM>
+    where WM: WindowManager + FloatSupport + FullscreenSupport
+{
+    /// The errors of the wrapped window manager are reused.
+    type Error = WM::Error;
+
+    /// The GapWM constructor.
+    ///
+    /// The wrapped window manager is created with the given screen and the
+    /// gap is initialised as 0.
+    fn new(screen: Screen) -> GapWM<WM> {
+        GapWM {
+            wm: WM::new(screen),
+            gap: 0,
+        }
+    }
+
+    /// returns the layout of the wrapped window manager where the tiled
+    /// windows are shrunk by the gap.
+    fn get_window_layout(&self) -> WindowLayout {
+        let window_layout = self.wm.get_window_layout();
+        let mut temp_windows = Vec::new();
+        for &(window, geometry) in window_layout.windows.iter() {
+            if self.is_tiled(window) {
+                temp_windows.push((window, self.apply_gap(geometry)));
+            } else {
+                temp_windows.push((window, geometry));
+            }
+        }
+        WindowLayout {
+            focused_window: window_layout.focused_window,
+            windows: temp_windows,
+        }
+    }
+
+    /// Returns all the managed windows in the window manager.
+    fn get_windows(&self) -> Vec<Window> {
+        self.wm.get_windows()
+    }
+
+    /// gets the current focused window.
+    fn get_focused_window(&self) -> Option<Window> {
+        self.wm.get_focused_window()
+    }
+
+    /// adds new window_with_info to the wrapped window manager.
+    fn add_window(&mut self, window_with_info: WindowWithInfo) -> Result<(), Self::Error> {
+        self.wm.add_window(window_with_info)
+    }
+
+    /// removes the given window from the wrapped window manager.
+    fn remove_window(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.wm.remove_window(window)
+    }
+
+    /// set the focused window in the wrapped window manager.
+    fn focus_window(&mut self, window: Option<Window>) -> Result<(), Self::Error> {
+        self.wm.focus_window(window)
+    }
+
+    /// back/forth to the next window from the current focused window.
+    fn cycle_focus(&mut self, dir: PrevOrNext) {
+        self.wm.cycle_focus(dir)
+    }
+
+    /// gets the complete current information of the given window.
+    ///
+    /// The geometry of a tiled window that is not fullscreen is shrunk by
+    /// the gap, so it matches the geometry in *get_window_layout*.
+    fn get_window_info(&self, window: Window) -> Result<WindowWithInfo, Self::Error> {
+        let mut window_with_info = try!(self.wm.get_window_info(window));
+        if window_with_info.float_or_tile == FloatOrTile::Tile && !window_with_info.fullscreen {
+            window_with_info.geometry = self.apply_gap(window_with_info.geometry);
+        }
+        Ok(window_with_info)
+    }
+
+    /// gets the current window screen size.
+    fn get_screen(&self) -> Screen {
+        self.wm.get_screen()
+    }
+
+    /// set the given screen as new screen size, the gap is kept.
+    fn resize_screen(&mut self, screen: Screen) {
+        self.wm.resize_screen(screen)
+    }
+}
+
+impl<WM> TilingSupport for GapWM<WM>
+    where WM: TilingSupport + FloatSupport + FullscreenSupport
+{
+    /// returns the master window of the wrapped window manager.
+    fn get_master_window(&self) -> Option<Window> {
+        self.wm.get_master_window()
+    }
+
+    /// swap the given window with the master window.
+    fn swap_with_master(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.wm.swap_with_master(window)
+    }
+
+    /// swap the focused window with the next or previous tile.
+    fn swap_windows(&mut self, dir: PrevOrNext) {
+        self.wm.swap_windows(dir)
+    }
+}
+
+impl<WM> FloatSupport for GapWM<WM>
+    where WM: WindowManager + FloatSupport + FullscreenSupport
+{
+    /// return a vector that contains the floating windows.
+    fn get_floating_windows(&self) -> Vec<Window> {
+        self.wm.get_floating_windows()
+    }
+
+    /// set the given window to a floating window if it was a tiled window
+    /// or viceversa.
+    fn toggle_floating(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.wm.toggle_floating(window)
+    }
+
+    /// set the geometry of the given floating window, floating windows
+    /// have no gap, so the geometry is passed as it is.
+    fn set_window_geometry(&mut self,
+                           window: Window,
+                           new_geometry: Geometry)
+                           -> Result<(), Self::Error> {
+        self.wm.set_window_geometry(window, new_geometry)
+    }
+}
+
+impl<WM> MinimiseSupport for GapWM<WM>
+    where WM: MinimiseSupport + FloatSupport + FullscreenSupport
+{
+    /// Returns a vector of all the minimised windows.
+    fn get_minimised_windows(&self) -> Vec<Window> {
+        self.wm.get_minimised_windows()
+    }
+
+    /// if the given window is minimised is unminimised or viceversa
+    fn toggle_minimised(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.wm.toggle_minimised(window)
+    }
+}
+
+impl<WM> FullscreenSupport for GapWM<WM>
+    where WM: WindowManager + FloatSupport + FullscreenSupport
+{
+    /// Return the current fullscreen, if any.
+    fn get_fullscreen_window(&self) -> Option<Window> {
+        self.wm.get_fullscreen_window()
+    }
+
+    /// Make the given window fullscreen, or when it is already fullscreen,
+    /// undo it. The fullscreen window has no gap.
+    fn toggle_fullscreen(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.wm.toggle_fullscreen(window)
+    }
+}
+
+impl<WM> GapSupport for GapWM<WM>
+    where WM: WindowManager + FloatSupport + FullscreenSupport
+{
+    /// Return the current gap size.
+    fn get_gap(&self) -> GapSize {
+        self.gap
+    }
+
+    /// Set the gap size.
+    ///
+    /// The wrapped window manager is not affected, the gap is only applied
+    /// when the layout is requested.
+    fn set_gap(&mut self, gap: GapSize) {
+        self.gap = gap;
+    }
+}
+
+
+impl<WM> LayoutSupport for GapWM<WM>
+    where WM: LayoutSupport + FloatSupport + FullscreenSupport
+{
+    /// Activate the previous or next layout, the gap is applied to the
+    /// tiles of every layout.
+    fn cycle_layout(&mut self, dir: PrevOrNext) {
+        self.wm.cycle_layout(dir)
+    }
+}
+
+impl<WM> TabBarSupport for GapWM<WM>
+    where WM: TabBarSupport + FloatSupport + FullscreenSupport
+{
+    /// Return the tab bars of the wrapped window manager, shrunk by the gap
+    /// like the tiled windows below them.
+    fn get_tab_bars(&self) -> Vec<TabBar> {
+        let mut tab_bars = self.wm.get_tab_bars();
+        for tab_bar in tab_bars.iter_mut() {
+            tab_bar.geometry = self.apply_gap(tab_bar.geometry);
+        }
+        tab_bars
+    }
+}
+
+impl<WM> ColumnRatioSupport for GapWM<WM>
+    where WM: ColumnRatioSupport + FloatSupport + FullscreenSupport
+{
+    /// Return the column ratio of the wrapped window manager.
+    fn get_column_ratio(&self) -> ColumnRatio {
+        self.wm.get_column_ratio()
+    }
+
+    /// Set the column ratio of the wrapped window manager, the gap is
+    /// applied afterwards.
+    fn set_column_ratio(&mut self, ratio: ColumnRatio) {
+        self.wm.set_column_ratio(ratio)
+    }
+}
+
+impl<WM> MasterRatioSupport for GapWM<WM>
+    where WM: MasterRatioSupport + FloatSupport + FullscreenSupport
+{
+    /// Return the master ratio of the wrapped window manager.
+    fn get_master_ratio(&self) -> MasterRatio {
+        self.wm.get_master_ratio()
+    }
+
+    /// Set the master ratio of the wrapped window manager, the gap is
+    /// applied afterwards.
+    fn set_master_ratio(&mut self, ratio: MasterRatio) {
+        self.wm.set_master_ratio(ratio)
+    }
+}
+
+impl<WM> MultiMasterSupport for GapWM<WM>
+    where WM: MultiMasterSupport + FloatSupport + FullscreenSupport
+{
+    /// Return the master windows of the wrapped window manager.
+    fn get_master_windows(&self) -> Vec<Window> {
+        self.wm.get_master_windows()
+    }
+
+    /// Return the number of master windows of the wrapped window manager.
+    fn get_master_count(&self) -> MasterCount {
+        self.wm.get_master_count()
+    }
+
+    /// Set the number of master windows of the wrapped window manager.
+    fn set_master_count(&mut self, count: MasterCount) {
+        self.wm.set_master_count(count)
+    }
+}
+
+impl<WM> MasterPositionSupport for GapWM<WM>
+    where WM: MasterPositionSupport + FloatSupport + FullscreenSupport
+{
+    /// Return the master position of the wrapped window manager.
+    fn get_master_position(&self) -> MasterPosition {
+        self.wm.get_master_position()
+    }
+
+    /// Set the master position of the wrapped window manager, the gap is
+    /// applied afterwards.
+    fn set_master_position(&mut self, position: Option<MasterPosition>) {
+        self.wm.set_master_position(position)
+    }
+}
+
+impl<WM> DirectionalSupport for GapWM<WM>
+    where WM: DirectionalSupport + FloatSupport + FullscreenSupport
+{
+    /// Swap the focused window with the window in the given direction, the
+    /// gap does not change which window that is.
+    fn swap_direction(&mut self, dir: Direction) {
+        self.wm.swap_direction(dir)
+    }
+}
+
+impl<WM> WindowPropertiesSupport for GapWM<WM>
+    where WM: WindowPropertiesSupport + FloatSupport + FullscreenSupport
+{
+    /// Replace the properties of the window, they are not affected by the gap.
+    fn set_window_properties(&mut self,
+                             window: Window,
+                             properties: WindowProperties)
+                             -> Result<(), Self::Error> {
+        self.wm.set_window_properties(window, properties)
+    }
+}
+
+impl<WM> ScratchpadSupport for GapWM<WM>
+    where WM: ScratchpadSupport + FloatSupport + FullscreenSupport
+{
+    /// Return the scratchpad windows of the wrapped window manager.
+    fn get_scratchpad_windows(&self) -> Vec<Window> {
+        self.wm.get_scratchpad_windows()
+    }
+
+    /// Move the window to the scratchpad of the wrapped window manager.
+    fn move_to_scratchpad(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.wm.move_to_scratchpad(window)
+    }
+
+    /// Show or hide the scratchpad, the scratchpad windows float so they
+    /// get no gap.
+    fn toggle_scratchpad(&mut self) {
+        self.wm.toggle_scratchpad()
+    }
+}
+
+impl<WM> UrgencySupport for GapWM<WM>
+    where WM: UrgencySupport + FloatSupport + FullscreenSupport
+{
+    /// Return the urgent windows of the wrapped window manager.
+    fn get_urgent_windows(&self) -> Vec<Window> {
+        self.wm.get_urgent_windows()
+    }
+
+    /// Mark the window as urgent or not in the wrapped window manager.
+    fn set_urgent(&mut self, window: Window, urgent: bool) -> Result<(), Self::Error> {
+        self.wm.set_urgent(window, urgent)
+    }
+}
+
+impl<WM> StickySupport for GapWM<WM>
+    where WM: StickySupport + FullscreenSupport
+{
+    /// Return the sticky windows of the wrapped window manager.
+    fn get_sticky_windows(&self) -> Vec<Window> {
+        self.wm.get_sticky_windows()
+    }
+
+    /// Toggle the stickiness in the wrapped window manager, sticky windows
+    /// float so they get no gap.
+    fn toggle_sticky(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.wm.toggle_sticky(window)
+    }
+}
+
+impl<WM> FocusHistorySupport for GapWM<WM>
+    where WM: FocusHistorySupport + FloatSupport + FullscreenSupport
+{
+    /// Return the focus history of the wrapped window manager.
+    fn get_focus_history(&self) -> Vec<Window> {
+        self.wm.get_focus_history()
+    }
+}
+
+impl<WM> InvariantCheck for GapWM<WM>
+    where WM: InvariantCheck + FloatSupport + FullscreenSupport
+{
+    /// checks the wrapped window manager.
+    fn check_invariants(&self) -> Vec<String> {
+        self.wm.check_invariants()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    // We have to import `WMName` from the super module.
+    use super::WMName;
+
+    // We have to repeat the imports we did in the super module.
+    use cplwm_api::wm::WindowManager;
+    use cplwm_api::wm::TilingSupport;
+    use cplwm_api::wm::FloatSupport;
+    use cplwm_api::wm::MinimiseSupport;
+    use cplwm_api::wm::FullscreenSupport;
+    use cplwm_api::wm::GapSupport;
+    use cplwm_api::types::*;
+
+    // The generic tests of the traits that this window manager implements.
+    conformance_tests!(WMName: window_manager, tiling, floating, minimising, fullscreen);
+
+    // We define a static variable for the screen we will use in the tests.
+    static SCREEN: Screen = Screen {
+        width: 800,
+        height: 600,
+    };
+
+    static SCREEN2: Screen = Screen {
+        width: 1000,
+        height: 800,
+    };
+
+    // We define a static variable for the geometry of a fullscreen window.
+    static SCREEN_GEOM: Geometry = Geometry {
+        x: 0,
+        y: 0,
+        width: 800,
+        height: 600,
+    };
+
+    // We define a static variable for some random geometry that we will use
+    // when adding windows to a window manager.
+    static SOME_GEOM: Geometry = Geometry {
+        x: 10,
+        y: 10,
+        width: 100,
+        height: 100,
+    };
+
+    #[test]
+    fn test_adding_and_removing_some_windows() {
+        let mut wm = WMName::new(SCREEN);
+
+        // Initially the gap is 0 and the layout is empty
+        assert_eq!(0, wm.get_gap());
+        assert_eq!(WindowLayout::new(), wm.get_window_layout());
+
+        // Without a gap, the layout is the same as the one of the wrapped
+        // window manager.
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        assert!(wm.is_managed(1));
+        assert_eq!(vec![1], wm.get_windows());
+        let wl1 = wm.get_window_layout();
+        assert_eq!(Some(1), wl1.focused_window);
+        assert_eq!(vec![(1, SCREEN_GEOM)], wl1.windows);
+
+        // Even when there is only a single tile there should be a gap
+        // around it.
+        wm.set_gap(5);
+        assert_eq!(5, wm.get_gap());
+        let gap_screen = Geometry {
+            x: 5,
+            y: 5,
+            width: 790,
+            height: 590,
+        };
+        assert_eq!(vec![(1, gap_screen)], wm.get_window_layout().windows);
+
+        // Windows don't share the gap in between them.
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        let first_half = Geometry {
+            x: 5,
+            y: 5,
+            width: 390,
+            height: 590,
+        };
+        let second_half = Geometry {
+            x: 405,
+            y: 5,
+            width: 390,
+            height: 590,
+        };
+        let wl2 = wm.get_window_layout();
+        assert_eq!(Some(2), wl2.focused_window);
+        assert_eq!(vec![(1, first_half), (2, second_half)], wl2.windows);
+
+        // get_window_info must reflect the gap as well
+        assert_eq!(second_half, wm.get_window_info(2).unwrap().geometry);
+
+        wm.remove_window(2).unwrap();
+        assert!(!wm.is_managed(2));
+        assert_eq!(vec![(1, gap_screen)], wm.get_window_layout().windows);
+
+        // Setting the gap back to 0 restores the original layout
+        wm.set_gap(0);
+        assert_eq!(vec![(1, SCREEN_GEOM)], wm.get_window_layout().windows);
+    }
+
+    #[test]
+    fn test_floating_and_fullscreen_windows_have_no_gap() {
+        let mut wm = WMName::new(SCREEN);
+        wm.set_gap(10);
+
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_float(2, SOME_GEOM)).unwrap();
+
+        let tile = Geometry {
+            x: 10,
+            y: 10,
+            width: 780,
+            height: 580,
+        };
+
+        // The floating window keeps its geometry
+        let wl1 = wm.get_window_layout();
+        assert_eq!(vec![(1, tile), (2, SOME_GEOM)], wl1.windows);
+        assert_eq!(SOME_GEOM, wm.get_window_info(2).unwrap().geometry);
+
+        // Moving the floating window is not affected by the gap either
+        let new_geom = Geometry {
+            x: 20,
+            y: 30,
+            width: 200,
+            height: 100,
+        };
+        wm.set_window_geometry(2, new_geom).unwrap();
+        assert_eq!(vec![(1, tile), (2, new_geom)], wm.get_window_layout().windows);
+
+        // The fullscreen window takes up the whole screen, without gap
+        wm.toggle_fullscreen(1).unwrap();
+        assert_eq!(Some(1), wm.get_fullscreen_window());
+        assert_eq!(vec![(1, SCREEN_GEOM)], wm.get_window_layout().windows);
+        assert_eq!(SCREEN_GEOM, wm.get_window_info(1).unwrap().geometry);
+
+        // The gap is back when the window is no longer fullscreen
+        wm.toggle_fullscreen(1).unwrap();
+        assert_eq!(vec![(1, tile), (2, new_geom)], wm.get_window_layout().windows);
+    }
+
+    #[test]
+    fn test_gap_larger_than_tile() {
+        let mut wm = WMName::new(SCREEN);
+
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+
+        // The side tiles are 400x300, the master tile is 400x600. A gap of
+        // 250 is larger than half of the width of every tile and larger
+        // than half of the height of the side tiles.
+        wm.set_gap(250);
+        assert_eq!(250, wm.get_gap());
+
+        let master = Geometry {
+            x: 198,
+            y: 250,
+            width: 4,
+            height: 100,
+        };
+        let first_side = Geometry {
+            x: 598,
+            y: 148,
+            width: 4,
+            height: 4,
+        };
+        let second_side = Geometry {
+            x: 598,
+            y: 448,
+            width: 4,
+            height: 4,
+        };
+        let wl1 = wm.get_window_layout();
+        assert_eq!(vec![(1, master), (2, first_side), (3, second_side)], wl1.windows);
+
+        // Every window keeps room for its borders and stays inside its own
+        // tile
+        for &(_, geometry) in wl1.windows.iter() {
+            assert!(geometry.width >= 3 && geometry.height >= 3);
+            assert!(geometry.x >= 0 && geometry.x + geometry.width as i32 <= 800);
+            assert!(geometry.y >= 0 && geometry.y + geometry.height as i32 <= 600);
+        }
+
+        // A gap that is larger than the whole screen does not underflow
+        wm.set_gap(GapSize::max_value());
+        let wl2 = wm.get_window_layout();
+        assert_eq!(3, wl2.windows.len());
+        for &(_, geometry) in wl2.windows.iter() {
+            assert!(geometry.width >= 3 && geometry.height >= 3);
+        }
+    }
+
+    #[test]
+    fn test_forwarded_traits() {
+        let mut wm = WMName::new(SCREEN);
+        wm.set_gap(5);
+
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+
+        // Tiling
+        assert_eq!(Some(1), wm.get_master_window());
+        wm.swap_with_master(3).unwrap();
+        assert_eq!(Some(3), wm.get_master_window());
+        assert_eq!(Some(3), wm.get_focused_window());
+        wm.swap_windows(PrevOrNext::Next);
+        assert_eq!(Some(2), wm.get_master_window());
+
+        // Focus
+        wm.cycle_focus(PrevOrNext::Prev);
+        assert_eq!(Some(2), wm.get_focused_window());
+        assert!(wm.focus_window(Some(10)).is_err());
+
+        // Floating
+        wm.toggle_floating(1).unwrap();
+        assert_eq!(vec![1], wm.get_floating_windows());
+
+        // Minimising
+        wm.toggle_minimised(2).unwrap();
+        assert_eq!(vec![2], wm.get_minimised_windows());
+        assert!(wm.get_window_layout().windows.iter().all(|&(w, _)| w != 2));
+        wm.toggle_minimised(2).unwrap();
+        assert!(wm.get_minimised_windows().is_empty());
+
+        // Resizing the screen keeps the gap
+        wm.resize_screen(SCREEN2);
+        assert_eq!(SCREEN2, wm.get_screen());
+        assert_eq!(5, wm.get_gap());
+    }
+}
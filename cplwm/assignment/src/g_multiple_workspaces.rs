@@ -23,25 +23,1003 @@
 //! or you want to explain your approach, write it down after the comments
 //! section.
 //!
-//! COMPLETED: No
+//! COMPLETED: YES
 //!
 //! COMMENTS:
 //!
-//! ...
+//! ## General approach
 //!
+//! *MultiWorkspaceWM* stores one window manager per workspace in a vec and
+//! the index of the current workspace. Everything that is shown to the user
+//! (*get_window_layout*, *get_focused_window*, *get_master_window*,
+//! *get_floating_windows*, *get_minimised_windows*,
+//! *get_fullscreen_window*) only concerns the current workspace, so the
+//! backend hides the windows of the other workspaces.
+//!
+//! *get_windows* and *is_managed* on the other hand concern all the
+//! workspaces, otherwise the backend would not recognise (e.g. when it is
+//! destroyed) a window that lives in a hidden workspace. The same goes for
+//! *is_minimised*, which asks the workspace of the window, so a window that
+//! is minimised on a hidden workspace is still minimised.
+//!
+//! Functions without a window argument (*cycle_focus*, *swap_windows*) are
+//! forwarded to the current workspace. Functions with a window argument are
+//! forwarded to the workspace the window belongs to, or to the current
+//! workspace when no workspace knows the window (so the window manager of
+//! the workspace returns the appropriate error). When such a function must
+//! focus the window (*focus_window*, *swap_with_master* and
+//! *toggle_fullscreen*), the workspace of the window becomes the current
+//! workspace first.
+//!
+//! *resize_screen* and *set_gap* are applied to all the workspaces.
+//!
+//! ## Urgency
+//!
+//! The urgent windows are not forwarded to the workspaces: the focused
+//! window of a hidden workspace is not the focused window of the
+//! *MultiWorkspaceWM*, but its workspace would refuse to make it urgent.
+//! Instead *MultiWorkspaceWM* keeps the urgent windows of all the workspaces
+//! in *urgent_windows*, the windows that were removed are filtered out when
+//! it is read. *focus_urgent_window* switches to the workspace of the window
+//! through *focus_window*.
+//!
+//! ## Sticky windows
+//!
+//! *MultiWorkspaceWM* keeps the sticky windows itself as well. A sticky
+//! window stays in the workspace it was added to, so *get_windows* contains
+//! it once and switching workspaces does not change any workspace.
+//! *get_window_layout* adds the sticky windows of the other workspaces on
+//! top of the layout of the current workspace, with the geometry and in the
+//! stacking order of their own workspace, when they are visible there (e.g.
+//! not minimised). *focus_window* moves a sticky window of another workspace
+//! to the current workspace, like *toggle_scratchpad* does, instead of
+//! switching to its workspace. As the *sticky_windows* vec is part of the
+//! serialised state, sticky windows survive a restart.
+//!
+//! The window manager of assignment F is used for the workspaces since it
+//! implements the traits of all the other assignments as well.
 
 // Add imports here
+use std::error;
+use std::fmt;
+
+use cplwm_api::types::{ColumnRatio, Direction, FloatOrTile, GapSize, Geometry, MAX_WORKSPACE_INDEX,
+                       MasterCount, MasterPosition, MasterRatio, PrevOrNext, Screen, TabBar, Window,
+                       WindowLayout, WindowProperties, WindowWithInfo, WorkspaceIndex};
+use cplwm_api::wm::WindowManager;
+use cplwm_api::wm::TilingSupport;
+use cplwm_api::wm::FloatSupport;
+use cplwm_api::wm::MinimiseSupport;
+use cplwm_api::wm::FullscreenSupport;
+use cplwm_api::wm::GapSupport;
+use cplwm_api::wm::LayoutSupport;
+use cplwm_api::wm::TabBarSupport;
+use cplwm_api::wm::ColumnRatioSupport;
+use cplwm_api::wm::MasterRatioSupport;
+use cplwm_api::wm::MultiMasterSupport;
+use cplwm_api::wm::MasterPositionSupport;
+use cplwm_api::wm::DirectionalSupport;
+use cplwm_api::wm::WindowPropertiesSupport;
+use cplwm_api::wm::ScratchpadSupport;
+use cplwm_api::wm::UrgencySupport;
+use cplwm_api::wm::StickySupport;
+use cplwm_api::wm::FocusHistorySupport;
+use cplwm_api::wm::MultiWorkspaceSupport;
+use cplwm_api::wm::InvariantCheck;
+
+use f_gaps;
+use n_invariants::{check_window_manager, prefix_violations};
+
+/// Window manager aliase.
+pub type WMName = MultiWorkspaceWM<f_gaps::WMName>;
+
+/// The MultiWorkspaceWM struct
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
+pub struct MultiWorkspaceWM<WM> {
+    /// A vector with the window manager of every workspace, the index in
+    /// the vector is the index of the workspace.
+    pub workspaces: Vec<WM>,
+    /// The index of the current workspace.
+    pub current_workspace: WorkspaceIndex,
+    /// The urgent windows of all the workspaces, the one that became urgent
+    /// most recently comes first.
+    pub urgent_windows: Vec<Window>,
+    /// The sticky windows of all the workspaces, in the order they were made
+    /// sticky.
+    pub sticky_windows: Vec<Window>,
+}
+
+/// Supported functions
+impl<WM: WindowManager> MultiWorkspaceWM<WM> {
+    /// returns the window manager of the current workspace.
+    fn get_current(&self) -> &WM {
+        // unwrap() is used because current_workspace is always a valid index
+        self.workspaces.get(self.current_workspace).unwrap()
+    }
+
+    /// returns the window manager of the current workspace mutably.
+    fn get_current_mut(&mut self) -> &mut WM {
+        // unwrap() is used because current_workspace is always a valid index
+        self.workspaces.get_mut(self.current_workspace).unwrap()
+    }
+
+    /// returns the index of the workspace that manages the given window,
+    /// if there is no such workspace the current workspace index is
+    /// returned.
+    fn get_window_workspace_index(&self, window: Window) -> WorkspaceIndex {
+        match self.workspaces.iter().position(|wm| wm.is_managed(window)) {
+            None => self.current_workspace,
+            Some(index) => index,
+        }
+    }
+
+    /// returns the window manager of the workspace that manages the given
+    /// window, or the one of the current workspace.
+    fn get_window_workspace(&self, window: Window) -> &WM {
+        let index = self.get_window_workspace_index(window);
+        // unwrap() is used because the index is always a valid index
+        self.workspaces.get(index).unwrap()
+    }
+
+    /// returns the window manager of the workspace that manages the given
+    /// window, or the one of the current workspace, mutably.
+    fn get_window_workspace_mut(&mut self, window: Window) -> &mut WM {
+        let index = self.get_window_workspace_index(window);
+        // unwrap() is used because the index is always a valid index
+        self.workspaces.get_mut(index).unwrap()
+    }
+
+    /// switch to the workspace that manages the given window, if any.
+    fn switch_to_window_workspace(&mut self, window: Window) {
+        self.current_workspace = self.get_window_workspace_index(window);
+    }
+
+    /// move the given sticky window from the workspace that manages it to
+    /// the current workspace, with the same geometry.
+    fn adopt_sticky_window(&mut self,
+                           window: Window)
+                           -> Result<(), MultiWorkspaceWMError<WM::Error>> {
+        let index = self.get_window_workspace_index(window);
+        if index != self.current_workspace {
+            let window_with_info = try!(self.workspaces[index]
+                .get_window_info(window)
+                .map_err(MultiWorkspaceWMError::WorkspaceError));
+            try!(self.workspaces[index]
+                .remove_window(window)
+                .map_err(MultiWorkspaceWMError::WorkspaceError));
+            try!(self.get_current_mut()
+                .add_window(window_with_info)
+                .map_err(MultiWorkspaceWMError::WorkspaceError));
+        }
+        Ok(())
+    }
+}
+
+/// The errors that this window manager can return.
+#[derive(Debug)]
+pub enum MultiWorkspaceWMError<E> {
+    /// There is no workspace with this index.
+    UnknownWorkspace(WorkspaceIndex),
+    /// An error returned by the window manager of a workspace.
+    WorkspaceError(E),
+}
+
+impl<E: error::Error> fmt::Display for MultiWorkspaceWMError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MultiWorkspaceWMError::UnknownWorkspace(ref index) => {
+                write!(f, "Unknown workspace: {}", index)
+            }
+            MultiWorkspaceWMError::WorkspaceError(ref err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl<E: error::Error> error::Error for MultiWorkspaceWMError<E> {
+    fn description(&self) -> &str {
+        match *self {
+            MultiWorkspaceWMError::UnknownWorkspace(_) => "Unknown workspace",
+            MultiWorkspaceWMError::WorkspaceError(ref err) => err.description(),
+        }
+    }
+}
+
+impl<WM: WindowManager> WindowManager for MultiWorkspaceWM<WM> {
+    type Error = MultiWorkspaceWMError<WM::Error>;
+
+    /// The MultiWorkspaceWM constructor.
+    ///
+    /// A window manager with the given screen is created for every
+    /// workspace, the first workspace is the current one.
+    fn new(screen: Screen) -> MultiWorkspaceWM<WM> {
+        let mut workspaces = Vec::new();
+        for _ in 0..MAX_WORKSPACE_INDEX + 1 {
+            workspaces.push(WM::new(screen));
+        }
+        MultiWorkspaceWM {
+            workspaces: workspaces,
+            current_workspace: 0,
+            urgent_windows: Vec::new(),
+            sticky_windows: Vec::new(),
+        }
+    }
+
+    /// returns the layout of the current workspace, with the visible
+    /// sticky windows of the other workspaces on top.
+    fn get_window_layout(&self) -> WindowLayout {
+        let mut window_layout = self.get_current().get_window_layout();
+        if !self.sticky_windows.is_empty() {
+            for (index, wm) in self.workspaces.iter().enumerate() {
+                if index != self.current_workspace {
+                    let sticky_windows = wm.get_window_layout()
+                        .windows
+                        .into_iter()
+                        .filter(|&(w, _)| self.sticky_windows.contains(&w));
+                    window_layout.windows.extend(sticky_windows);
+                }
+            }
+        }
+        window_layout
+    }
+
+    /// checks all the workspaces.
+    fn is_managed(&self, window: Window) -> bool {
+        self.workspaces.iter().any(|wm| wm.is_managed(window))
+    }
+
+    /// Returns the windows of all the workspaces.
+    fn get_windows(&self) -> Vec<Window> {
+        let mut temp_windows = Vec::new();
+        for wm in self.workspaces.iter() {
+            temp_windows.extend(wm.get_windows());
+        }
+        temp_windows
+    }
+
+    /// gets the focused window of the current workspace.
+    fn get_focused_window(&self) -> Option<Window> {
+        self.get_current().get_focused_window()
+    }
+
+    /// adds the window to the current workspace.
+    ///
+    /// When the window is already managed by another workspace, it is
+    /// passed to that workspace, which returns the appropriate error.
+    fn add_window(&mut self, window_with_info: WindowWithInfo) -> Result<(), Self::Error> {
+        self.get_window_workspace_mut(window_with_info.window)
+            .add_window(window_with_info)
+            .map_err(MultiWorkspaceWMError::WorkspaceError)
+    }
+
+    /// removes the window from the workspace that manages it.
+    fn remove_window(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.get_window_workspace_mut(window)
+            .remove_window(window)
+            .map_err(MultiWorkspaceWMError::WorkspaceError)
+    }
+
+    /// focus the given window, if the window belongs to another workspace,
+    /// that workspace becomes the current one. A sticky window of another
+    /// workspace is moved to the current workspace instead.
+    fn focus_window(&mut self, window: Option<Window>) -> Result<(), Self::Error> {
+        if let Some(w) = window {
+            if self.sticky_windows.contains(&w) {
+                try!(self.adopt_sticky_window(w));
+            } else {
+                self.switch_to_window_workspace(w);
+            }
+        }
+        try!(self.get_current_mut()
+            .focus_window(window)
+            .map_err(MultiWorkspaceWMError::WorkspaceError));
+        // the user is looking at the window now
+        self.urgent_windows.retain(|w| Some(*w) != window);
+        Ok(())
+    }
+
+    /// cycle the focus in the current workspace.
+    fn cycle_focus(&mut self, dir: PrevOrNext) {
+        self.get_current_mut().cycle_focus(dir);
+        if let Some(window) = self.get_focused_window() {
+            self.urgent_windows.retain(|w| *w != window);
+        }
+    }
+
+    /// gets the info of the window from the workspace that manages it.
+    fn get_window_info(&self, window: Window) -> Result<WindowWithInfo, Self::Error> {
+        self.get_window_workspace(window)
+            .get_window_info(window)
+            .map_err(MultiWorkspaceWMError::WorkspaceError)
+    }
+
+    /// gets the screen of the current workspace, all workspaces share the
+    /// same screen.
+    fn get_screen(&self) -> Screen {
+        self.get_current().get_screen()
+    }
+
+    /// resizes the screen of all the workspaces.
+    fn resize_screen(&mut self, screen: Screen) {
+        for wm in self.workspaces.iter_mut() {
+            wm.resize_screen(screen);
+        }
+    }
+}
+
+impl<WM: TilingSupport> TilingSupport for MultiWorkspaceWM<WM> {
+    /// returns the master window of the current workspace.
+    fn get_master_window(&self) -> Option<Window> {
+        self.get_current().get_master_window()
+    }
+
+    /// swap the window with the master window of its workspace, which
+    /// becomes the current workspace since the master window is focused.
+    fn swap_with_master(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.switch_to_window_workspace(window);
+        self.get_current_mut()
+            .swap_with_master(window)
+            .map_err(MultiWorkspaceWMError::WorkspaceError)
+    }
+
+    /// swap the windows in the current workspace.
+    fn swap_windows(&mut self, dir: PrevOrNext) {
+        self.get_current_mut().swap_windows(dir)
+    }
+}
+
+impl<WM: FloatSupport> FloatSupport for MultiWorkspaceWM<WM> {
+    /// returns the floating windows of the current workspace.
+    fn get_floating_windows(&self) -> Vec<Window> {
+        self.get_current().get_floating_windows()
+    }
+
+    /// toggles the window in the workspace that manages it, a tiled window
+    /// is not sticky.
+    fn toggle_floating(&mut self, window: Window) -> Result<(), Self::Error> {
+        try!(self.get_window_workspace_mut(window)
+            .toggle_floating(window)
+            .map_err(MultiWorkspaceWMError::WorkspaceError));
+        if !self.get_window_workspace(window).get_floating_windows().contains(&window) {
+            self.sticky_windows.retain(|w| *w != window);
+        }
+        Ok(())
+    }
+
+    /// sets the geometry in the workspace that manages the window.
+    fn set_window_geometry(&mut self,
+                           window: Window,
+                           new_geometry: Geometry)
+                           -> Result<(), Self::Error> {
+        self.get_window_workspace_mut(window)
+            .set_window_geometry(window, new_geometry)
+            .map_err(MultiWorkspaceWMError::WorkspaceError)
+    }
+}
+
+impl<WM: MinimiseSupport> MinimiseSupport for MultiWorkspaceWM<WM> {
+    /// returns the minimised windows of the current workspace.
+    fn get_minimised_windows(&self) -> Vec<Window> {
+        self.get_current().get_minimised_windows()
+    }
+
+    /// checks the workspace that manages the window, which is not
+    /// necessarily the current one.
+    fn is_minimised(&self, window: Window) -> bool {
+        self.get_window_workspace(window).is_minimised(window)
+    }
+
+    /// (un)minimises the window in the workspace that manages it.
+    fn toggle_minimised(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.get_window_workspace_mut(window)
+            .toggle_minimised(window)
+            .map_err(MultiWorkspaceWMError::WorkspaceError)
+    }
+}
+
+impl<WM: FullscreenSupport> FullscreenSupport for MultiWorkspaceWM<WM> {
+    /// returns the fullscreen window of the current workspace.
+    fn get_fullscreen_window(&self) -> Option<Window> {
+        self.get_current().get_fullscreen_window()
+    }
+
+    /// toggles the fullscreen of the window in its workspace, which becomes
+    /// the current workspace since the fullscreen window is focused.
+    fn toggle_fullscreen(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.switch_to_window_workspace(window);
+        self.get_current_mut()
+            .toggle_fullscreen(window)
+            .map_err(MultiWorkspaceWMError::WorkspaceError)
+    }
+}
+
+impl<WM: GapSupport> GapSupport for MultiWorkspaceWM<WM> {
+    /// returns the gap of the current workspace, all workspaces share the
+    /// same gap.
+    fn get_gap(&self) -> GapSize {
+        self.get_current().get_gap()
+    }
+
+    /// sets the gap of all the workspaces.
+    fn set_gap(&mut self, gap: GapSize) {
+        for wm in self.workspaces.iter_mut() {
+            wm.set_gap(gap);
+        }
+    }
+}
+
+impl<WM: LayoutSupport> LayoutSupport for MultiWorkspaceWM<WM> {
+    /// cycle the layout of the current workspace, every workspace keeps its
+    /// own layout.
+    fn cycle_layout(&mut self, dir: PrevOrNext) {
+        self.get_current_mut().cycle_layout(dir)
+    }
+}
+
+impl<WM: TabBarSupport> TabBarSupport for MultiWorkspaceWM<WM> {
+    /// returns the tab bars of the current workspace, the other workspaces
+    /// are not visible.
+    fn get_tab_bars(&self) -> Vec<TabBar> {
+        self.get_current().get_tab_bars()
+    }
+}
+
+impl<WM: ColumnRatioSupport> ColumnRatioSupport for MultiWorkspaceWM<WM> {
+    /// returns the column ratio of the current workspace.
+    fn get_column_ratio(&self) -> ColumnRatio {
+        self.get_current().get_column_ratio()
+    }
+
+    /// sets the column ratio of the current workspace, every workspace
+    /// keeps its own column ratio.
+    fn set_column_ratio(&mut self, ratio: ColumnRatio) {
+        self.get_current_mut().set_column_ratio(ratio)
+    }
+}
+
+impl<WM: MasterRatioSupport> MasterRatioSupport for MultiWorkspaceWM<WM> {
+    /// returns the master ratio of the current workspace.
+    fn get_master_ratio(&self) -> MasterRatio {
+        self.get_current().get_master_ratio()
+    }
+
+    /// sets the master ratio of the current workspace, every workspace
+    /// keeps its own master ratio.
+    fn set_master_ratio(&mut self, ratio: MasterRatio) {
+        self.get_current_mut().set_master_ratio(ratio)
+    }
+}
+
+impl<WM: MultiMasterSupport> MultiMasterSupport for MultiWorkspaceWM<WM> {
+    /// returns the master windows of the current workspace.
+    fn get_master_windows(&self) -> Vec<Window> {
+        self.get_current().get_master_windows()
+    }
+
+    /// returns the number of master windows of the current workspace.
+    fn get_master_count(&self) -> MasterCount {
+        self.get_current().get_master_count()
+    }
+
+    /// sets the number of master windows of the current workspace, every
+    /// workspace keeps its own number of master windows.
+    fn set_master_count(&mut self, count: MasterCount) {
+        self.get_current_mut().set_master_count(count)
+    }
+}
+
+impl<WM: MasterPositionSupport> MasterPositionSupport for MultiWorkspaceWM<WM> {
+    /// returns the master position of the current workspace.
+    fn get_master_position(&self) -> MasterPosition {
+        self.get_current().get_master_position()
+    }
+
+    /// sets the master position of the current workspace, every workspace
+    /// keeps its own master position.
+    fn set_master_position(&mut self, position: Option<MasterPosition>) {
+        self.get_current_mut().set_master_position(position)
+    }
+}
+
+impl<WM: DirectionalSupport> DirectionalSupport for MultiWorkspaceWM<WM> {
+    /// swap the windows in the current workspace.
+    fn swap_direction(&mut self, dir: Direction) {
+        self.get_current_mut().swap_direction(dir)
+    }
+}
+
+impl<WM: WindowPropertiesSupport> WindowPropertiesSupport for MultiWorkspaceWM<WM> {
+    /// replaces the properties in the workspace that manages the window, the
+    /// window can be on any workspace.
+    fn set_window_properties(&mut self,
+                             window: Window,
+                             properties: WindowProperties)
+                             -> Result<(), Self::Error> {
+        self.get_window_workspace_mut(window)
+            .set_window_properties(window, properties)
+            .map_err(MultiWorkspaceWMError::WorkspaceError)
+    }
+}
+
+impl<WM: ScratchpadSupport> ScratchpadSupport for MultiWorkspaceWM<WM> {
+    /// returns the scratchpad windows of the current workspace first,
+    /// followed by the ones of the other workspaces in order.
+    fn get_scratchpad_windows(&self) -> Vec<Window> {
+        let mut windows = self.get_current().get_scratchpad_windows();
+        for (index, wm) in self.workspaces.iter().enumerate() {
+            if index != self.current_workspace {
+                windows.extend(wm.get_scratchpad_windows());
+            }
+        }
+        windows
+    }
+
+    /// moves the window to the scratchpad in the workspace that manages it.
+    fn move_to_scratchpad(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.get_window_workspace_mut(window)
+            .move_to_scratchpad(window)
+            .map_err(MultiWorkspaceWMError::WorkspaceError)
+    }
+
+    /// toggles the scratchpad of the current workspace.
+    ///
+    /// The scratchpad is shared by all workspaces: when the current
+    /// workspace has no scratchpad windows, the first scratchpad window of
+    /// another workspace is moved to the scratchpad of the current one
+    /// first.
+    fn toggle_scratchpad(&mut self) {
+        if self.get_current().get_scratchpad_windows().is_empty() {
+            if let Some(window) = self.get_scratchpad_windows().first().cloned() {
+                let index = self.get_window_workspace_index(window);
+                // unwrap() is used because the window is managed by the
+                // workspace with this index and not by the current one
+                let window_with_info = self.workspaces[index].get_window_info(window).unwrap();
+                self.workspaces[index].remove_window(window).unwrap();
+                let current = self.get_current_mut();
+                current.add_window(window_with_info).unwrap();
+                current.move_to_scratchpad(window).unwrap();
+            }
+        }
+        self.get_current_mut().toggle_scratchpad()
+    }
+}
+
+impl<WM: WindowManager> UrgencySupport for MultiWorkspaceWM<WM> {
+    /// returns the urgent windows of all the workspaces, without the ones
+    /// that were removed and the focused window.
+    fn get_urgent_windows(&self) -> Vec<Window> {
+        let focused_window = self.get_focused_window();
+        self.urgent_windows
+            .iter()
+            .cloned()
+            .filter(|w| self.is_managed(*w) && Some(*w) != focused_window)
+            .collect()
+    }
+
+    /// marks the window as urgent or not, the window can be on any
+    /// workspace.
+    fn set_urgent(&mut self, window: Window, urgent: bool) -> Result<(), Self::Error> {
+        // returns the appropriate error when the window is not managed
+        try!(self.get_window_info(window));
+        self.urgent_windows = self.get_urgent_windows();
+        self.urgent_windows.retain(|w| *w != window);
+        if urgent && self.get_focused_window() != Some(window) {
+            self.urgent_windows.insert(0, window);
+        }
+        Ok(())
+    }
+}
+
+impl<WM: FloatSupport> StickySupport for MultiWorkspaceWM<WM> {
+    /// returns the sticky windows of all the workspaces, without the ones
+    /// that were removed.
+    fn get_sticky_windows(&self) -> Vec<Window> {
+        self.sticky_windows.iter().cloned().filter(|w| self.is_managed(*w)).collect()
+    }
+
+    /// toggles the stickiness of the window, the window can be on any
+    /// workspace. A tiled window is made floating in its workspace first.
+    fn toggle_sticky(&mut self, window: Window) -> Result<(), Self::Error> {
+        let window_with_info = try!(self.get_window_info(window));
+        self.sticky_windows = self.get_sticky_windows();
+        if self.sticky_windows.contains(&window) {
+            self.sticky_windows.retain(|w| *w != window);
+            return Ok(());
+        }
+        if window_with_info.float_or_tile == FloatOrTile::Tile {
+            try!(self.toggle_floating(window));
+        }
+        self.sticky_windows.push(window);
+        Ok(())
+    }
+}
+
+impl<WM: FocusHistorySupport> FocusHistorySupport for MultiWorkspaceWM<WM> {
+    /// returns the focus history of the current workspace, every workspace
+    /// has its own.
+    fn get_focus_history(&self) -> Vec<Window> {
+        self.get_current().get_focus_history()
+    }
+}
+
+impl<WM: WindowManager> MultiWorkspaceSupport<WM> for MultiWorkspaceWM<WM> {
+    /// Return the current workspace index.
+    fn get_current_workspace_index(&self) -> WorkspaceIndex {
+        self.current_workspace
+    }
+
+    /// Get an immutable borrow of the workspace at the given index.
+    fn get_workspace(&self, index: WorkspaceIndex) -> Result<&WM, Self::Error> {
+        match self.workspaces.get(index) {
+            None => Err(MultiWorkspaceWMError::UnknownWorkspace(index)),
+            Some(wm) => Ok(wm),
+        }
+    }
+
+    /// Get a mutable borrow of the workspace at the given index.
+    fn get_workspace_mut(&mut self, index: WorkspaceIndex) -> Result<&mut WM, Self::Error> {
+        match self.workspaces.get_mut(index) {
+            None => Err(MultiWorkspaceWMError::UnknownWorkspace(index)),
+            Some(wm) => Ok(wm),
+        }
+    }
+
+    /// Switch to the workspace at the given index.
+    ///
+    /// Every workspace keeps its own focused window.
+    fn switch_workspace(&mut self, index: WorkspaceIndex) -> Result<(), Self::Error> {
+        if index > MAX_WORKSPACE_INDEX {
+            Err(MultiWorkspaceWMError::UnknownWorkspace(index))
+        } else {
+            self.current_workspace = index;
+            Ok(())
+        }
+    }
+}
+
+
+impl<WM: InvariantCheck> InvariantCheck for MultiWorkspaceWM<WM> {
+    /// checks the invariants of the window manager, which also catches
+    /// windows that are on more than one workspace, and of the window
+    /// manager of every workspace.
+    fn check_invariants(&self) -> Vec<String> {
+        let mut violations = check_window_manager(self);
+        for (index, wm) in self.workspaces.iter().enumerate() {
+            let prefix = format!("workspace {}", index);
+            violations.extend(prefix_violations(&prefix, wm.check_invariants()));
+        }
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    // We have to import `WMName` from the super module.
+    use super::WMName;
+
+    // We have to repeat the imports we did in the super module.
+    use cplwm_api::wm::WindowManager;
+    use cplwm_api::wm::TilingSupport;
+    use cplwm_api::wm::FloatSupport;
+    use cplwm_api::wm::MinimiseSupport;
+    use cplwm_api::wm::FullscreenSupport;
+    use cplwm_api::wm::GapSupport;
+    use cplwm_api::wm::MultiWorkspaceSupport;
+    use cplwm_api::wm::WindowPropertiesSupport;
+    use cplwm_api::wm::ScratchpadSupport;
+    use cplwm_api::wm::UrgencySupport;
+    use cplwm_api::wm::StickySupport;
+    use cplwm_api::types::*;
+
+    // The generic tests of the traits that this window manager implements.
+    conformance_tests!(WMName: window_manager, tiling, floating, minimising, fullscreen);
+
+    // We define a static variable for the screen we will use in the tests.
+    static SCREEN: Screen = Screen {
+        width: 800,
+        height: 600,
+    };
+
+    static SCREEN2: Screen = Screen {
+        width: 1000,
+        height: 800,
+    };
+
+    // We define a static variable for the geometry of a fullscreen window.
+    static SCREEN_GEOM: Geometry = Geometry {
+        x: 0,
+        y: 0,
+        width: 800,
+        height: 600,
+    };
+
+    // We define a static variable for some random geometry that we will use
+    // when adding windows to a window manager.
+    static SOME_GEOM: Geometry = Geometry {
+        x: 10,
+        y: 10,
+        width: 100,
+        height: 100,
+    };
+
+    #[test]
+    fn test_switch_workspace() {
+        let mut wm = WMName::new(SCREEN);
+
+        // Initially the first workspace is the current one
+        assert_eq!(0, wm.get_current_workspace_index());
+
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        let wl1 = wm.get_window_layout();
+
+        // The new workspace is empty
+        wm.switch_workspace(1).unwrap();
+        assert_eq!(1, wm.get_current_workspace_index());
+        assert_eq!(WindowLayout::new(), wm.get_window_layout());
+        assert_eq!(None, wm.get_focused_window());
+
+        // Windows added now only appear in this workspace
+        wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+        let wl2 = wm.get_window_layout();
+        assert_eq!(Some(3), wl2.focused_window);
+        assert_eq!(vec![(3, SCREEN_GEOM)], wl2.windows);
+
+        // **Invariant**: the window layout after switching to another
+        // workspace and then switching back to the original workspace
+        // should be the same as before.
+        wm.switch_workspace(0).unwrap();
+        assert_eq!(wl1, wm.get_window_layout());
+        wm.switch_workspace(1).unwrap();
+        assert_eq!(wl2, wm.get_window_layout());
+
+        // Switching to the current workspace does nothing
+        wm.switch_workspace(1).unwrap();
+        assert_eq!(wl2, wm.get_window_layout());
+
+        // Only valid indices are accepted
+        assert!(wm.switch_workspace(MAX_WORKSPACE_INDEX + 1).is_err());
+        assert!(wm.get_workspace(MAX_WORKSPACE_INDEX + 1).is_err());
+        assert!(wm.get_workspace_mut(MAX_WORKSPACE_INDEX + 1).is_err());
+        assert_eq!(1, wm.get_current_workspace_index());
+        assert_eq!(vec![1, 2], wm.get_workspace(0).unwrap().get_windows());
+        assert_eq!(vec![3], wm.get_workspace(1).unwrap().get_windows());
+        assert!(wm.get_workspace(MAX_WORKSPACE_INDEX).unwrap().get_windows().is_empty());
+    }
+
+    #[test]
+    fn test_windows_of_other_workspaces() {
+        let mut wm = WMName::new(SCREEN);
+
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        wm.switch_workspace(2).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+
+        // All windows are managed, visible or not
+        assert_eq!(vec![1, 2, 3], wm.get_windows());
+        assert!(wm.is_managed(1));
+        assert!(wm.is_managed(3));
+
+        // A window can't be managed by two workspaces
+        assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_err());
+        assert_eq!(vec![3], wm.get_workspace(2).unwrap().get_windows());
+
+        // The info of a window in another workspace can be retrieved
+        assert!(wm.get_window_info(2).is_ok());
+        assert!(wm.get_window_info(10).is_err());
+
+        // The properties of a window in another workspace can be updated,
+        // without switching to that workspace
+        let mut properties = WindowProperties::default();
+        properties.title = Some("title".to_owned());
+        wm.set_window_properties(2, properties.clone()).unwrap();
+        assert_eq!(properties, wm.get_window_info(2).unwrap().properties);
+        assert_eq!(2, wm.get_current_workspace_index());
+
+        // A window in a hidden workspace can be removed
+        wm.remove_window(1).unwrap();
+        assert!(!wm.is_managed(1));
+        assert_eq!(vec![2], wm.get_workspace(0).unwrap().get_windows());
+        assert_eq!(2, wm.get_current_workspace_index());
+        assert!(wm.remove_window(1).is_err());
+
+        // Focusing a window in another workspace switches to that workspace
+        wm.focus_window(Some(2)).unwrap();
+        assert_eq!(0, wm.get_current_workspace_index());
+        assert_eq!(Some(2), wm.get_focused_window());
+        assert!(wm.focus_window(Some(10)).is_err());
+
+        // Floating a window in another workspace does not switch
+        wm.toggle_floating(3).unwrap();
+        assert_eq!(0, wm.get_current_workspace_index());
+        assert!(wm.get_floating_windows().is_empty());
+        assert_eq!(vec![3], wm.get_workspace(2).unwrap().get_floating_windows());
+    }
+
+    #[test]
+    fn test_forwarded_traits() {
+        let mut wm = WMName::new(SCREEN);
+
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+        wm.switch_workspace(1).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(4, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_float(5, SOME_GEOM)).unwrap();
+
+        // Tiling concerns the current workspace
+        assert_eq!(Some(4), wm.get_master_window());
+        wm.swap_windows(PrevOrNext::Next);
+        assert_eq!(Some(4), wm.get_master_window());
+
+        // Swapping a window of another workspace with the master switches
+        // to that workspace
+        wm.swap_with_master(3).unwrap();
+        assert_eq!(0, wm.get_current_workspace_index());
+        assert_eq!(Some(3), wm.get_master_window());
+        assert_eq!(Some(3), wm.get_focused_window());
+
+        // Cycling the focus concerns the current workspace
+        wm.cycle_focus(PrevOrNext::Next);
+        assert_eq!(Some(2), wm.get_focused_window());
+
+        // Floating windows of the current workspace only
+        assert!(wm.get_floating_windows().is_empty());
+        wm.switch_workspace(1).unwrap();
+        assert_eq!(vec![5], wm.get_floating_windows());
+        wm.set_window_geometry(5, SCREEN_GEOM).unwrap();
+        assert_eq!(SCREEN_GEOM, wm.get_window_info(5).unwrap().geometry);
+
+        // Minimised windows of the current workspace only, is_minimised
+        // checks the other workspaces as well
+        wm.toggle_minimised(1).unwrap();
+        assert!(wm.get_minimised_windows().is_empty());
+        assert!(wm.is_minimised(1));
+        wm.switch_workspace(0).unwrap();
+        assert_eq!(vec![1], wm.get_minimised_windows());
+        wm.toggle_minimised(1).unwrap();
+
+        // Making a window of another workspace fullscreen switches to it
+        wm.toggle_fullscreen(4).unwrap();
+        assert_eq!(1, wm.get_current_workspace_index());
+        assert_eq!(Some(4), wm.get_fullscreen_window());
+        assert_eq!(vec![(4, SCREEN_GEOM)], wm.get_window_layout().windows);
+        wm.switch_workspace(0).unwrap();
+        assert_eq!(None, wm.get_fullscreen_window());
+    }
+
+    #[test]
+    fn test_shared_screen_and_gap() {
+        let mut wm = WMName::new(SCREEN);
+
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.switch_workspace(3).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+
+        // The gap is set in every workspace
+        wm.set_gap(5);
+        assert_eq!(5, wm.get_gap());
+        for index in 0..MAX_WORKSPACE_INDEX + 1 {
+            assert_eq!(5, wm.get_workspace(index).unwrap().get_gap());
+        }
+
+        // The screen is resized in every workspace
+        wm.resize_screen(SCREEN2);
+        assert_eq!(SCREEN2, wm.get_screen());
+        let gap_screen2 = Geometry {
+            x: 5,
+            y: 5,
+            width: 990,
+            height: 790,
+        };
+        assert_eq!(vec![(2, gap_screen2)], wm.get_window_layout().windows);
+        wm.switch_workspace(0).unwrap();
+        assert_eq!(SCREEN2, wm.get_screen());
+        assert_eq!(vec![(1, gap_screen2)], wm.get_window_layout().windows);
+    }
+
+    #[test]
+    fn test_shared_scratchpad() {
+        let mut wm = WMName::new(SCREEN);
+
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        wm.move_to_scratchpad(2).unwrap();
+        wm.switch_workspace(1).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+
+        // The scratchpad windows of all workspaces are returned
+        assert_eq!(vec![2], wm.get_scratchpad_windows());
+        assert!(wm.get_workspace(1).unwrap().get_scratchpad_windows().is_empty());
+
+        // Toggling shows the window on the current workspace
+        wm.toggle_scratchpad();
+        assert_eq!(Some(2), wm.get_focused_window());
+        assert!(wm.get_workspace(1).unwrap().is_managed(2));
+        assert!(!wm.get_workspace(0).unwrap().is_managed(2));
+        assert!(wm.get_window_layout().windows.iter().any(|&(w, _)| w == 2));
+
+        // And hides it again
+        wm.toggle_scratchpad();
+        assert!(wm.is_minimised(2));
+        assert!(wm.get_window_layout().windows.iter().all(|&(w, _)| w != 2));
+        assert_eq!(vec![2], wm.get_scratchpad_windows());
+        assert!(wm.move_to_scratchpad(10).is_err());
+    }
+
+    #[test]
+    fn test_urgent_window_on_other_workspace() {
+        let mut wm = WMName::new(SCREEN);
+
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.switch_workspace(1).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+        assert!(wm.set_urgent(4, true).is_err());
+
+        // The focused window of a hidden workspace can be urgent
+        wm.set_urgent(1, true).unwrap();
+        wm.set_urgent(2, true).unwrap();
+        assert_eq!(vec![2, 1], wm.get_urgent_windows());
+        wm.set_urgent(3, true).unwrap();
+        assert_eq!(vec![2, 1], wm.get_urgent_windows());
+
+        // Focusing the urgent window switches to its workspace
+        wm.set_urgent(1, true).unwrap();
+        wm.focus_urgent_window().unwrap();
+        assert_eq!(0, wm.get_current_workspace_index());
+        assert_eq!(Some(1), wm.get_focused_window());
+        assert_eq!(vec![2], wm.get_urgent_windows());
+
+        // Removed windows are no longer urgent
+        wm.remove_window(2).unwrap();
+        assert!(wm.get_urgent_windows().is_empty());
+    }
+
+    #[test]
+    fn test_sticky_windows() {
+        let mut wm = WMName::new(SCREEN);
+
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_float(2, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_float(3, SOME_GEOM)).unwrap();
+        assert!(wm.toggle_sticky(4).is_err());
+
+        // A tiled window becomes floating
+        wm.toggle_sticky(3).unwrap();
+        wm.toggle_sticky(1).unwrap();
+        assert_eq!(vec![3, 1], wm.get_sticky_windows());
+        assert!(wm.get_window_info(1).unwrap().float_or_tile == FloatOrTile::Float);
+        let layout = wm.get_window_layout();
+
+        // The sticky windows are visible on the other workspaces, with the
+        // same geometry and stacking order, but they are not duplicated
+        wm.switch_workspace(1).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(5, SOME_GEOM)).unwrap();
+        let sticky_layout: Vec<_> =
+            layout.windows.iter().cloned().filter(|&(w, _)| w != 2).collect();
+        assert_eq!(sticky_layout, wm.get_window_layout().windows[1..].to_vec());
+        assert_eq!(Some(5), wm.get_focused_window());
+        assert_eq!(4, wm.get_windows().len());
+
+        // Switching back does not change the layout
+        wm.switch_workspace(0).unwrap();
+        assert_eq!(layout, wm.get_window_layout());
 
+        // Focusing a sticky window from another workspace moves it there
+        wm.switch_workspace(1).unwrap();
+        wm.focus_window(Some(3)).unwrap();
+        assert_eq!(1, wm.get_current_workspace_index());
+        assert_eq!(Some(3), wm.get_focused_window());
+        assert!(wm.get_workspace(1).unwrap().is_managed(3));
+        assert!(wm.is_sticky(3));
+        assert_eq!(SOME_GEOM, wm.get_window_info(3).unwrap().geometry);
 
-/// **TODO**: You are free to choose the name for your window manager. As we
-/// will use automated tests when grading your assignment, indicate here the
-/// name of your window manager data type so we can just use `WMName` instead
-/// of having to manually figure out your window manager name.
-///
-/// Replace `()` with the name of your window manager data type.
-///
-/// Replace the `WM` type parameter with the name of the window manager from
-/// assignment E or the window manager of the last assignment you completed
-/// (except F). E.g. use `type WMName =
-/// MultiWorkspaceWM<e_fullscreen_windows::WMName>`.
-pub type WMName = ();
+        // Tiling a sticky window or toggling it makes it not sticky anymore
+        wm.toggle_floating(3).unwrap();
+        wm.toggle_sticky(1).unwrap();
+        assert!(wm.get_sticky_windows().is_empty());
+        assert!(wm.get_window_layout().windows.iter().all(|&(w, _)| w != 1));
+    }
+}
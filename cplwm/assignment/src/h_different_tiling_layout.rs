@@ -28,25 +28,1677 @@
 //! or you want to explain your approach, write it down after the comments
 //! section.
 //!
-//! COMPLETED: No
+//! COMPLETED: YES
 //!
 //! COMMENTS:
 //!
-//! ...
+//! ## General approach
 //!
+//! Instead of making a copy of the tiling window manager for every layout,
+//! the layouts are defined in this module as implementations of the
+//! [`Layout`] trait. *LayoutWM*, the tiling window manager of this module,
+//! keeps a list of *TilingLayout*s and uses the active one in
+//! *update_geometries*, the user can switch between them with
+//! *cycle_layout*. The window manager of assignment E puts the floating,
+//! minimised and fullscreen windows on top of it.
 //!
-//! **TODO**: If you did not come up yourself with this layout, mention its
-//! source below.
+//! *TilingLayout* is an enum instead of a boxed trait object, so the active
+//! layout can be serialised together with the rest of the window manager.
 //!
-//! ...
+//! *MasterStackLayout* is the layout of the previous assignments.
+//! *MonocleLayout* gives every tiled window the whole screen below a tab
+//! bar, like the [monocle] layout of dwm. As only the window on top is
+//! visible, the window manager raises the focused tiled window and reports
+//! the tab bar through *TabBarSupport*, the backend draws it.
+//!
+//! ## Grid layout
+//!
+//! *GridLayout* is the new layout of this assignment, it is based on the
+//! [Grid] layout of xmonad-contrib. With many windows the stack of the
+//! master-stack layout gets very thin tiles, the grid arranges all windows
+//! in rows and columns of (nearly) the same size instead: the number of
+//! columns is the square root of the number of windows, rounded up, the
+//! last row takes the remaining windows, which share its width.
+//!
+//! The pixels that are left when the width or height is divided are spread
+//! over the first columns or rows, one each, so the grid covers the whole
+//! screen and the tiles differ at most one pixel in size.
+//!
+//! The windows fill the grid row by row in the order of the window manager,
+//! so *swap_windows* moves a window through the grid, and *focus_direction*
+//! and *swap_direction* work on the geometries, like for any layout.
+//!
+//! ## Dwindle layout
+//!
+//! *DwindleLayout* is a binary space partition, like the dwindle layout of
+//! [bspwm] and [Hyprland]: every window takes half of the space that is left
+//! by the windows before it, the last window takes all that is left. The
+//! space is split along its longest side, so the splits alternate between
+//! vertical and horizontal on a landscape screen.
+//!
+//! Like the other layouts it only depends on the order of the tiled windows
+//! and the screen, so *swap_with_master* moves the focused window to the
+//! largest tile, minimised windows give their space to the next windows and
+//! *resize_screen* splits the new screen in the same way.
+//!
+//! ## Centered master layout
+//!
+//! *CenteredMasterLayout* is meant for ultrawide screens, where a master
+//! window on the left edge is uncomfortable to look at. It is based on the
+//! [centeredmaster] patch of dwm and the ThreeColMid layout of xmonad: the
+//! master windows are stacked in the center column, which is as wide as the
+//! master area of the master-stack layout, the other windows alternate
+//! between the right and the left column, which share the remaining width.
+//!
+//! With a single stack window a third column would stay empty, so the
+//! layout falls back to the two columns of the master-stack layout. The
+//! first stack window goes to the right column, so it doesn't move when the
+//! third column appears.
+//!
+//! ## Scrolling layout
+//!
+//! With many windows the stack of the master-stack layout gets very thin
+//! tiles. *ScrollingLayout* works like [PaperWM] instead: the tiled windows
+//! are columns of a fixed width on a strip that is infinitely wide, the
+//! screen is a viewport on that strip that follows the focus.
+//!
+//! The viewport only shows whole columns, *first_column* is the index of the
+//! first visible one. The layout is the only one with state, which is
+//! updated by *follow_focus*: the viewport scrolls as little as possible to
+//! show the focused window. The windows outside the viewport are left out of
+//! the geometries, so the window manager leaves them out of its window
+//! layout and the backend hides them like minimised windows.
+//!
+//! The width of the columns is the column ratio of the layout settings, a
+//! percentage of the width of the screen, so it scales when the screen is
+//! resized, the default shows two columns. Like the master ratio it is a
+//! setting of the window manager, see *ColumnRatioSupport*, so it is kept
+//! when the user cycles through the layouts.
+//!
+//! ## Master position
+//!
+//! The master-stack layout can put its master area on any side of the
+//! screen, following the master position of the layout settings. Rather
+//! than writing the layout four times, it is calculated for a master area on
+//! the left and the geometries are moved afterwards by *orient_geometry*:
+//! mirrored for the right side, transposed for the top, and both for the
+//! bottom. For the top and the bottom the layout is calculated on a
+//! transposed screen, so the master ratio applies to the height.
+//!
+//! Without a chosen position, the settings fall back to the left side, or
+//! the top on a portrait screen, so the layout follows *resize_screen*.
+//!
+//! [`Layout`]: ../../cplwm_api/layout/trait.Layout.html
+//! [monocle]: https://dwm.suckless.org/tutorial/
+//! [Grid]: http://xmonad.org/xmonad-docs/xmonad-contrib/XMonad-Layout-Grid.html
+//! [bspwm]: https://github.com/baskerville/bspwm
+//! [Hyprland]: https://wiki.hyprland.org/Configuring/Dwindle-Layout/
+//! [centeredmaster]: https://dwm.suckless.org/patches/centeredmaster/
+//! [PaperWM]: https://github.com/paperwm/PaperWM
 
 // Add imports here
+use std::cmp::{max, min};
+
+use cplwm_api::layout::{Layout, LayoutSettings};
+use cplwm_api::types::{ColumnRatio, Direction, Geometry, MAX_COLUMN_RATIO, MAX_MASTER_RATIO,
+                       MIN_COLUMN_RATIO, MIN_MASTER_RATIO, MasterCount, MasterPosition,
+                       MasterRatio, PrevOrNext, Screen, TabBar, Window, WindowLayout,
+                       WindowProperties, WindowWithInfo};
+use cplwm_api::wm::WindowManager;
+use cplwm_api::wm::TilingSupport;
+use cplwm_api::wm::LayoutSupport;
+use cplwm_api::wm::TabBarSupport;
+use cplwm_api::wm::ColumnRatioSupport;
+use cplwm_api::wm::MasterRatioSupport;
+use cplwm_api::wm::MultiMasterSupport;
+use cplwm_api::wm::MasterPositionSupport;
+use cplwm_api::wm::DirectionalSupport;
+use cplwm_api::wm::WindowPropertiesSupport;
+use cplwm_api::wm::InvariantCheck;
+
+use b_tiling_wm::TillingWMError;
+use e_fullscreen_windows::FullscreenWM;
+use n_invariants::{check_tiling, check_window_manager};
+
+/// The window manager of assignment E, which can switch between the layouts
+/// of this module.
+pub type WMName = FullscreenWM;
+
+/// The height of the tab bar of the tabbed layouts.
+pub const TAB_BAR_HEIGHT: u32 = 20;
+
+/// returns the width of the master area for the given screen.
+///
+/// The master ratio is clamped again, so a layout never lets one of the
+/// areas collapse, whatever the settings are.
+fn get_master_width(screen: Screen, settings: &LayoutSettings) -> u32 {
+    let ratio = max(MIN_MASTER_RATIO, min(MAX_MASTER_RATIO, settings.master_ratio));
+    // u64 is used to avoid an overflow on huge screens
+    (screen.width as u64 * ratio as u64 / 100) as u32
+}
+
+/// moves the given geometry, calculated for a master area on the left, to
+/// the given master position on the given screen.
+///
+/// For a master area at the top or at the bottom, the geometry must have
+/// been calculated for the transposed screen, i.e. with the width and the
+/// height swapped, it is transposed back.
+fn orient_geometry(geometry: Geometry, position: MasterPosition, screen: Screen) -> Geometry {
+    let geometry = if position.is_vertical() {
+        Geometry {
+            x: geometry.y,
+            y: geometry.x,
+            width: geometry.height,
+            height: geometry.width,
+        }
+    } else {
+        geometry
+    };
+    match position {
+        MasterPosition::Left | MasterPosition::Top => geometry,
+        MasterPosition::Right => {
+            Geometry { x: screen.width as i32 - geometry.x - geometry.width as i32, ..geometry }
+        }
+        MasterPosition::Bottom => {
+            Geometry { y: screen.height as i32 - geometry.y - geometry.height as i32, ..geometry }
+        }
+    }
+}
+
+/// stacks the given windows on top of each other in a column that starts at
+/// the given x and has the given width and height.
+fn get_column_geometries(windows: &[Window],
+                         x: i32,
+                         width: u32,
+                         height: u32)
+                         -> Vec<(Window, Geometry)> {
+    let mut geometries = Vec::new();
+    if !windows.is_empty() {
+        let height_window = height / windows.len() as u32;
+        let mut y_point = 0 as i32;
+        for window in windows {
+            geometries.push((*window,
+                             Geometry {
+                x: x,
+                y: y_point,
+                width: width,
+                height: height_window,
+            }));
+            y_point += height_window as i32;
+        }
+    }
+    geometries
+}
+
+/// splits the given length in the given number of parts, returns the offset
+/// and the length of every part.
+///
+/// The parts differ at most one in length: the remainder of the division is
+/// spread over the first parts, one each.
+pub fn split_evenly(length: u32, parts: u32) -> Vec<(i32, u32)> {
+    let mut splits = Vec::new();
+    if parts > 0 {
+        let part_length = length / parts;
+        let remainder = length % parts;
+        let mut offset = 0;
+        for part in 0..parts {
+            let length = if part < remainder {
+                part_length + 1
+            } else {
+                part_length
+            };
+            splits.push((offset, length));
+            offset += length as i32;
+        }
+    }
+    splits
+}
+
+/// The layouts a window manager can switch between.
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone, PartialEq, Eq)]
+pub enum TilingLayout {
+    /// See *MasterStackLayout*.
+    MasterStack(MasterStackLayout),
+    /// See *MonocleLayout*.
+    Monocle(MonocleLayout),
+    /// See *GridLayout*.
+    Grid(GridLayout),
+    /// See *DwindleLayout*.
+    Dwindle(DwindleLayout),
+    /// See *CenteredMasterLayout*.
+    CenteredMaster(CenteredMasterLayout),
+    /// See *ScrollingLayout*.
+    Scrolling(ScrollingLayout),
+}
+
+impl TilingLayout {
+    /// returns all the layouts with their default settings, in the order in
+    /// which *cycle_layout* goes through them.
+    pub fn all() -> Vec<TilingLayout> {
+        vec![TilingLayout::MasterStack(MasterStackLayout),
+             TilingLayout::Monocle(MonocleLayout),
+             TilingLayout::Grid(GridLayout),
+             TilingLayout::Dwindle(DwindleLayout),
+             TilingLayout::CenteredMaster(CenteredMasterLayout),
+             TilingLayout::Scrolling(ScrollingLayout::new())]
+    }
+}
+
+impl Layout for TilingLayout {
+    fn get_geometries(&self,
+                      screen: Screen,
+                      settings: &LayoutSettings,
+                      windows: &[Window])
+                      -> Vec<(Window, Geometry)> {
+        match *self {
+            TilingLayout::MasterStack(ref layout) => {
+                layout.get_geometries(screen, settings, windows)
+            }
+            TilingLayout::Monocle(ref layout) => layout.get_geometries(screen, settings, windows),
+            TilingLayout::Grid(ref layout) => layout.get_geometries(screen, settings, windows),
+            TilingLayout::Dwindle(ref layout) => layout.get_geometries(screen, settings, windows),
+            TilingLayout::CenteredMaster(ref layout) => {
+                layout.get_geometries(screen, settings, windows)
+            }
+            TilingLayout::Scrolling(ref layout) => layout.get_geometries(screen, settings, windows),
+        }
+    }
+
+    fn get_tab_bar_geometry(&self, screen: Screen, settings: &LayoutSettings) -> Option<Geometry> {
+        match *self {
+            TilingLayout::MasterStack(ref layout) => layout.get_tab_bar_geometry(screen, settings),
+            TilingLayout::Monocle(ref layout) => layout.get_tab_bar_geometry(screen, settings),
+            TilingLayout::Grid(ref layout) => layout.get_tab_bar_geometry(screen, settings),
+            TilingLayout::Dwindle(ref layout) => layout.get_tab_bar_geometry(screen, settings),
+            TilingLayout::CenteredMaster(ref layout) => {
+                layout.get_tab_bar_geometry(screen, settings)
+            }
+            TilingLayout::Scrolling(ref layout) => layout.get_tab_bar_geometry(screen, settings),
+        }
+    }
+
+    fn follow_focus(&mut self,
+                    screen: Screen,
+                    settings: &LayoutSettings,
+                    windows: &[Window],
+                    focused_window: Window) {
+        match *self {
+            TilingLayout::MasterStack(ref mut layout) => {
+                layout.follow_focus(screen, settings, windows, focused_window)
+            }
+            TilingLayout::Monocle(ref mut layout) => {
+                layout.follow_focus(screen, settings, windows, focused_window)
+            }
+            TilingLayout::Grid(ref mut layout) => {
+                layout.follow_focus(screen, settings, windows, focused_window)
+            }
+            TilingLayout::Dwindle(ref mut layout) => {
+                layout.follow_focus(screen, settings, windows, focused_window)
+            }
+            TilingLayout::CenteredMaster(ref mut layout) => {
+                layout.follow_focus(screen, settings, windows, focused_window)
+            }
+            TilingLayout::Scrolling(ref mut layout) => {
+                layout.follow_focus(screen, settings, windows, focused_window)
+            }
+        }
+    }
+}
+
+/// The master windows are stacked on top of each other in the left part of
+/// the screen, the other windows are stacked on top of each other in the
+/// right part. The width of the left part is given by the master ratio of
+/// the settings, half of the screen by default, the number of master windows
+/// by the master count, one by default.
+///
+/// When there are only master windows, they take the whole width of the
+/// screen, e.g. a single window takes the whole screen.
+///
+/// ```text
+/// +----------+----------+
+/// |          |    2     |
+/// |          |          |
+/// |    1     +----------+
+/// |          |    3     |
+/// |          |          |
+/// +----------+----------+
+/// ```
+///
+/// The master position of the settings moves the master area to another
+/// side of the screen. At the top or at the bottom, the layout is
+/// transposed: the windows of both areas are placed side by side and the
+/// master ratio sets the height of the master area.
+///
+/// ```text
+/// +---------------------+
+/// |          1          |
+/// |                     |
+/// +----------+----------+
+/// |    2     |    3     |
+/// +----------+----------+
+/// ```
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MasterStackLayout;
+
+impl Layout for MasterStackLayout {
+    fn get_geometries(&self,
+                      screen: Screen,
+                      settings: &LayoutSettings,
+                      windows: &[Window])
+                      -> Vec<(Window, Geometry)> {
+        // there is always at least one master window
+        let master_count = min(max(1, settings.master_count), windows.len());
+        let (masters, stack) = windows.split_at(master_count);
+
+        // the geometries are calculated for a master area on the left of
+        // the (transposed) screen, then moved to the master position
+        let position = settings.get_master_position(screen);
+        let left_screen = if position.is_vertical() {
+            Screen {
+                width: screen.height,
+                height: screen.width,
+            }
+        } else {
+            screen
+        };
+
+        let width_master = if stack.is_empty() {
+            left_screen.width
+        } else {
+            get_master_width(left_screen, settings)
+        };
 
+        let mut geometries =
+            get_column_geometries(masters, 0, width_master, left_screen.height);
+        geometries.extend(get_column_geometries(stack,
+                                                width_master as i32,
+                                                left_screen.width - width_master,
+                                                left_screen.height));
+        geometries.into_iter()
+            .map(|(window, geometry)| (window, orient_geometry(geometry, position, screen)))
+            .collect()
+    }
+}
 
-/// **TODO**: You are free to choose the name for your window manager. As we
-/// will use automated tests when grading your assignment, indicate here the
-/// name of your window manager data type so we can just use `WMName` instead
-/// of having to manually figure out your window manager name.
+/// Every window takes the whole screen below a tab bar of *TAB_BAR_HEIGHT*
+/// pixels, so the windows are stacked on top of each other. The master ratio
+/// and the master count are ignored.
 ///
-/// Replace `()` with the name of your window manager data type.
-pub type WMName = ();
+/// ```text
+/// +---+---+---+---------+
+/// | 1 | 2 | 3 |         |
+/// +---+---+---+---------+
+/// |                     |
+/// |      1 (2, 3)       |
+/// |                     |
+/// +---------------------+
+/// ```
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MonocleLayout;
+
+impl Layout for MonocleLayout {
+    fn get_geometries(&self,
+                      screen: Screen,
+                      _settings: &LayoutSettings,
+                      windows: &[Window])
+                      -> Vec<(Window, Geometry)> {
+        // the tab bar never takes more than the screen
+        let bar_height = min(TAB_BAR_HEIGHT, screen.height);
+        let geometry = Geometry {
+            x: 0,
+            y: bar_height as i32,
+            width: screen.width,
+            height: screen.height - bar_height,
+        };
+        windows.iter().map(|window| (*window, geometry)).collect()
+    }
+
+    fn get_tab_bar_geometry(&self,
+                            screen: Screen,
+                            _settings: &LayoutSettings)
+                            -> Option<Geometry> {
+        Some(Geometry {
+            x: 0,
+            y: 0,
+            width: screen.width,
+            height: min(TAB_BAR_HEIGHT, screen.height),
+        })
+    }
+}
+
+/// The windows are arranged in a grid, row by row. The number of columns is
+/// the square root of the number of windows, rounded up, the last row takes
+/// the remaining windows, which share its width. The master ratio and the
+/// master count are ignored.
+///
+/// ```text
+/// +------+------+------+
+/// |  1   |  2   |  3   |
+/// +------+------+------+
+/// |  4   |  5   |  6   |
+/// +------+------+------+
+/// |         7          |
+/// +--------------------+
+/// ```
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridLayout;
+
+impl GridLayout {
+    /// returns the number of columns of the grid for the given number of
+    /// windows, the smallest number whose square is not less than it.
+    pub fn get_column_count(window_count: usize) -> usize {
+        let mut columns = 0;
+        while columns * columns < window_count {
+            columns += 1;
+        }
+        columns
+    }
+}
+
+impl Layout for GridLayout {
+    fn get_geometries(&self,
+                      screen: Screen,
+                      _settings: &LayoutSettings,
+                      windows: &[Window])
+                      -> Vec<(Window, Geometry)> {
+        let mut geometries = Vec::new();
+        let columns = GridLayout::get_column_count(windows.len());
+        if columns == 0 {
+            return geometries;
+        }
+        let rows = (windows.len() + columns - 1) / columns;
+        let row_splits = split_evenly(screen.height, rows as u32);
+        for (row, &(y, height)) in windows.chunks(columns).zip(row_splits.iter()) {
+            let column_splits = split_evenly(screen.width, row.len() as u32);
+            for (window, &(x, width)) in row.iter().zip(column_splits.iter()) {
+                geometries.push((*window,
+                                 Geometry {
+                    x: x,
+                    y: y,
+                    width: width,
+                    height: height,
+                }));
+            }
+        }
+        geometries
+    }
+}
+
+/// Every window takes half of the space left by the windows before it, the
+/// last window takes all that is left. The space is split along its longest
+/// side, the first half gets the odd pixel. The master ratio and the master
+/// count are ignored.
+///
+/// ```text
+/// +----------+----------+
+/// |          |    2     |
+/// |          |          |
+/// |    1     +-----+----+
+/// |          |  3  | 4  |
+/// |          |     |    |
+/// +----------+-----+----+
+/// ```
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DwindleLayout;
+
+impl Layout for DwindleLayout {
+    fn get_geometries(&self,
+                      screen: Screen,
+                      _settings: &LayoutSettings,
+                      windows: &[Window])
+                      -> Vec<(Window, Geometry)> {
+        let mut geometries = Vec::new();
+        // the space that is left for the remaining windows
+        let mut space = screen.to_geometry();
+        for (index, window) in windows.iter().enumerate() {
+            if index == windows.len() - 1 {
+                geometries.push((*window, space));
+            } else if space.width >= space.height {
+                let width = (space.width + 1) / 2;
+                geometries.push((*window, Geometry { width: width, ..space }));
+                space.x += width as i32;
+                space.width -= width;
+            } else {
+                let height = (space.height + 1) / 2;
+                geometries.push((*window, Geometry { height: height, ..space }));
+                space.y += height as i32;
+                space.height -= height;
+            }
+        }
+        geometries
+    }
+}
+
+/// The master windows are stacked in the center column, the other windows
+/// alternate between the right and the left column, starting on the right.
+/// The width of the center column is given by the master ratio of the
+/// settings, the number of master windows by the master count, like in
+/// *MasterStackLayout*. The side columns share the remaining width, the left
+/// one gets the odd pixel.
+///
+/// With a single stack window, the layout falls back to the two columns of
+/// *MasterStackLayout*, with only master windows they take the whole screen.
+/// The master position is ignored, the master area is always in the middle
+/// or on the left.
+///
+/// ```text
+/// +------+--------+------+
+/// |  3   |        |  2   |
+/// |      |   1    +------+
+/// +------+        |  4   |
+/// |  5   |        |      |
+/// +------+--------+------+
+/// ```
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CenteredMasterLayout;
+
+impl Layout for CenteredMasterLayout {
+    fn get_geometries(&self,
+                      screen: Screen,
+                      settings: &LayoutSettings,
+                      windows: &[Window])
+                      -> Vec<(Window, Geometry)> {
+        // there is always at least one master window
+        let master_count = min(max(1, settings.master_count), windows.len());
+        let (masters, stack) = windows.split_at(master_count);
+        if stack.len() <= 1 {
+            let left_settings = LayoutSettings {
+                master_position: Some(MasterPosition::Left),
+                ..*settings
+            };
+            return MasterStackLayout.get_geometries(screen, &left_settings, windows);
+        }
+
+        let width_master = get_master_width(screen, settings);
+        let width_right = (screen.width - width_master) / 2;
+        let width_left = screen.width - width_master - width_right;
+        let right: Vec<Window> = stack.iter()
+            .enumerate()
+            .filter(|&(i, _)| i % 2 == 0)
+            .map(|(_, &window)| window)
+            .collect();
+        let left: Vec<Window> = stack.iter()
+            .enumerate()
+            .filter(|&(i, _)| i % 2 == 1)
+            .map(|(_, &window)| window)
+            .collect();
+
+        let mut geometries = get_column_geometries(masters,
+                                                   width_left as i32,
+                                                   width_master,
+                                                   screen.height);
+        geometries.extend(get_column_geometries(&right,
+                                                (width_left + width_master) as i32,
+                                                width_right,
+                                                screen.height));
+        geometries.extend(get_column_geometries(&left, 0, width_left, screen.height));
+        geometries
+    }
+}
+
+/// Every window is a column of the full height of the screen on a strip that
+/// is infinitely wide, the screen shows the columns from *first_column* on,
+/// as many as fit on the screen. The other windows are left out, so they are
+/// hidden. The width of the columns is the column ratio of the settings, the
+/// master ratio and the master count are ignored.
+///
+/// ```text
+///      +-------+-------+
+///  1   |   2   |   3   |   4   5
+///      |       |       |
+///      +-------+-------+
+/// ```
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ScrollingLayout {
+    /// The index of the first visible column.
+    pub first_column: usize,
+}
+
+impl ScrollingLayout {
+    /// creates a scrolling layout that shows the first column.
+    pub fn new() -> ScrollingLayout {
+        ScrollingLayout { first_column: 0 }
+    }
+
+    /// returns the width of the columns for the given screen and settings.
+    ///
+    /// The column ratio is clamped again, so a column is never wider than
+    /// the screen and at least one pixel wide.
+    pub fn get_column_width(&self, screen: Screen, settings: &LayoutSettings) -> u32 {
+        let ratio = max(MIN_COLUMN_RATIO, min(MAX_COLUMN_RATIO, settings.column_ratio));
+        // u64 is used to avoid an overflow on huge screens
+        max(1, (screen.width as u64 * ratio as u64 / 100) as u32)
+    }
+
+    /// returns the index of the first visible column and the number of
+    /// visible columns for the given screen and number of windows.
+    ///
+    /// When windows were removed, the viewport is moved back so it is
+    /// still filled with columns.
+    fn get_viewport(&self,
+                    screen: Screen,
+                    settings: &LayoutSettings,
+                    window_count: usize)
+                    -> (usize, usize) {
+        let column_count = max(1, screen.width / self.get_column_width(screen, settings)) as usize;
+        let first_column = min(self.first_column,
+                               window_count.saturating_sub(column_count));
+        (first_column, column_count)
+    }
+}
+
+impl Layout for ScrollingLayout {
+    fn get_geometries(&self,
+                      screen: Screen,
+                      settings: &LayoutSettings,
+                      windows: &[Window])
+                      -> Vec<(Window, Geometry)> {
+        let width = self.get_column_width(screen, settings);
+        let (first_column, column_count) = self.get_viewport(screen, settings, windows.len());
+        windows.iter()
+            .skip(first_column)
+            .take(column_count)
+            .enumerate()
+            .map(|(column, window)| {
+                (*window,
+                 Geometry {
+                    x: (column as u32 * width) as i32,
+                    y: 0,
+                    width: width,
+                    height: screen.height,
+                })
+            })
+            .collect()
+    }
+
+    /// Scrolls as little as possible to show the given window.
+    fn follow_focus(&mut self,
+                    screen: Screen,
+                    settings: &LayoutSettings,
+                    windows: &[Window],
+                    focused_window: Window) {
+        let (first_column, column_count) = self.get_viewport(screen, settings, windows.len());
+        self.first_column = match windows.iter().position(|w| *w == focused_window) {
+            Some(index) if index < first_column => index,
+            Some(index) if index >= first_column + column_count => index + 1 - column_count,
+            _ => first_column,
+        };
+    }
+}
+
+/// A tiling window manager that arranges its windows with the layouts of
+/// this module.
+///
+/// It only knows tiled windows, the layers of [`m_layers`] add the floating,
+/// minimised and fullscreen windows on top of it, see assignment E.
+///
+/// [`m_layers`]: ../m_layers/index.html
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
+pub struct LayoutWM {
+    /// The tiled windows, in the order in which the layouts arrange them.
+    pub windows: Vec<WindowWithInfo>,
+    /// The size of the screen.
+    pub screen: Screen,
+    /// The focused window, if any.
+    pub focused_window: Option<Window>,
+    /// The layouts the user can cycle through.
+    pub layouts: Vec<TilingLayout>,
+    /// The index of the active layout in *layouts*.
+    pub index_layout: usize,
+    /// The settings shared by all the layouts.
+    pub layout_settings: LayoutSettings,
+    /// The last focused window, in a tabbed layout it stays on top of the
+    /// other windows when nothing is focused, e.g. because a floating window
+    /// of a layer on top of this window manager is focused.
+    pub last_focused_tile: Option<Window>,
+}
+
+impl LayoutWM {
+    /// returns the index of the given window in *windows*.
+    fn get_index(&self, window: Window) -> Option<usize> {
+        self.windows.iter().position(|w| w.window == window)
+    }
+
+    /// returns the windows in the order of *windows*.
+    fn get_tiled_windows(&self) -> Vec<Window> {
+        self.windows.iter().map(|w| w.window).collect()
+    }
+
+    /// returns the active layout.
+    fn get_layout(&self) -> &TilingLayout {
+        // unwrap() is used because index_layout is always a valid index
+        self.layouts.get(self.index_layout).unwrap()
+    }
+
+    /// calculates the geometries of the windows with the active layout.
+    ///
+    /// The active layout follows *last_focused_tile* first, the windows that
+    /// it leaves out keep their previous geometry.
+    fn update_geometries(&mut self) {
+        let tiled_windows = self.get_tiled_windows();
+        if let Some(window) = self.last_focused_tile {
+            // unwrap() is used because index_layout is always a valid index
+            self.layouts
+                .get_mut(self.index_layout)
+                .unwrap()
+                .follow_focus(self.screen, &self.layout_settings, &tiled_windows, window);
+        }
+        let geometries =
+            self.get_layout().get_geometries(self.screen, &self.layout_settings, &tiled_windows);
+        for (window, geometry) in geometries {
+            for tiled_window in self.windows.iter_mut().filter(|w| w.window == window) {
+                tiled_window.geometry = geometry;
+            }
+        }
+    }
+
+    /// focuses the given window, which is managed, it becomes the last
+    /// focused tile, so a scrolling layout scrolls to it.
+    fn set_focus(&mut self, window: Window) {
+        self.focused_window = Some(window);
+        self.last_focused_tile = Some(window);
+        self.update_geometries();
+    }
+
+    /// returns the window of the active tab when the active layout is
+    /// tabbed: the focused window, otherwise the last focused window or the
+    /// first window.
+    ///
+    /// None is returned when the active layout is not tabbed or there are no
+    /// windows.
+    fn get_active_tab(&self) -> Option<Window> {
+        if self.get_layout().get_tab_bar_geometry(self.screen, &self.layout_settings).is_none() {
+            return None;
+        }
+        self.focused_window
+            .or(self.last_focused_tile)
+            .or(self.windows.first().map(|w| w.window))
+    }
+}
+
+impl WindowManager for LayoutWM {
+    /// The errors are the ones of the tiling window manager.
+    type Error = TillingWMError;
+
+    /// The LayoutWM constructor.
+    ///
+    /// All the layouts are available, the first one is active, with the
+    /// default settings.
+    fn new(screen: Screen) -> LayoutWM {
+        LayoutWM {
+            windows: Vec::new(),
+            screen: screen,
+            focused_window: None,
+            layouts: TilingLayout::all(),
+            index_layout: 0,
+            layout_settings: LayoutSettings::default(),
+            last_focused_tile: None,
+        }
+    }
+
+    /// returns the windows in the order of *windows*.
+    fn get_windows(&self) -> Vec<Window> {
+        self.get_tiled_windows()
+    }
+
+    /// returns the focused window.
+    fn get_focused_window(&self) -> Option<Window> {
+        self.focused_window
+    }
+
+    /// adds the window at the end of *windows* and focuses it.
+    fn add_window(&mut self, window_with_info: WindowWithInfo) -> Result<(), Self::Error> {
+        let window = window_with_info.window;
+        if self.is_managed(window) {
+            return Err(TillingWMError::ManagedWindow(window));
+        }
+        self.windows.push(window_with_info);
+        self.set_focus(window);
+        Ok(())
+    }
+
+    /// removes the window, when it was focused nothing is focused anymore.
+    fn remove_window(&mut self, window: Window) -> Result<(), Self::Error> {
+        match self.get_index(window) {
+            None => Err(TillingWMError::UnknownWindow(window)),
+            Some(i) => {
+                self.windows.remove(i);
+                if self.focused_window == Some(window) {
+                    self.focused_window = None;
+                }
+                if self.last_focused_tile == Some(window) {
+                    self.last_focused_tile = None;
+                }
+                self.update_geometries();
+                Ok(())
+            }
+        }
+    }
+
+    /// returns the windows with the geometries of the active layout.
+    ///
+    /// The windows that the layout leaves out, e.g. because they are
+    /// scrolled out of view, are hidden. In a tabbed layout the active tab is
+    /// put on top of the other windows.
+    fn get_window_layout(&self) -> WindowLayout {
+        let mut windows = self.get_layout()
+            .get_geometries(self.screen, &self.layout_settings, &self.get_tiled_windows());
+        if let Some(active_window) = self.get_active_tab() {
+            if let Some(position) = windows.iter().position(|&(w, _)| w == active_window) {
+                let active_tab = windows.remove(position);
+                windows.push(active_tab);
+            }
+        }
+        WindowLayout {
+            focused_window: self.focused_window,
+            windows: windows,
+        }
+    }
+
+    /// focuses the given window, or no window when None is given.
+    fn focus_window(&mut self, window: Option<Window>) -> Result<(), Self::Error> {
+        match window {
+            None => {
+                self.focused_window = None;
+                Ok(())
+            }
+            Some(w) => {
+                if !self.is_managed(w) {
+                    return Err(TillingWMError::UnknownWindow(w));
+                }
+                self.set_focus(w);
+                Ok(())
+            }
+        }
+    }
+
+    /// cycles the focus over *windows*, wrapping around. When there is no
+    /// focused window, the first one is focused.
+    fn cycle_focus(&mut self, dir: PrevOrNext) {
+        if self.windows.is_empty() {
+            return;
+        }
+        let last = self.windows.len() - 1;
+        let next = match self.focused_window.and_then(|w| self.get_index(w)) {
+            None => 0,
+            Some(i) => {
+                match dir {
+                    PrevOrNext::Prev => if i == 0 { last } else { i - 1 },
+                    PrevOrNext::Next => if i == last { 0 } else { i + 1 },
+                }
+            }
+        };
+        let window = self.windows[next].window;
+        self.set_focus(window);
+    }
+
+    /// returns the information of the window with its current geometry.
+    fn get_window_info(&self, window: Window) -> Result<WindowWithInfo, Self::Error> {
+        match self.get_index(window) {
+            None => Err(TillingWMError::UnknownWindow(window)),
+            Some(i) => Ok(self.windows[i].clone()),
+        }
+    }
+
+    /// returns the screen.
+    fn get_screen(&self) -> Screen {
+        self.screen
+    }
+
+    /// the geometries are calculated again for the new screen.
+    fn resize_screen(&mut self, screen: Screen) {
+        self.screen = screen;
+        self.update_geometries()
+    }
+}
+
+impl TilingSupport for LayoutWM {
+    /// returns the first window of *windows*.
+    fn get_master_window(&self) -> Option<Window> {
+        self.windows.first().map(|w| w.window)
+    }
+
+    /// swaps the window with the first window of *windows* and focuses it.
+    fn swap_with_master(&mut self, window: Window) -> Result<(), Self::Error> {
+        match self.get_index(window) {
+            None => Err(TillingWMError::UnknownWindow(window)),
+            Some(i) => {
+                self.windows.swap(0, i);
+                self.set_focus(window);
+                Ok(())
+            }
+        }
+    }
+
+    /// swaps the focused window with the previous or next window, wrapping
+    /// around, the focused window stays focused.
+    fn swap_windows(&mut self, dir: PrevOrNext) {
+        if let Some(i) = self.focused_window.and_then(|w| self.get_index(w)) {
+            let last = self.windows.len() - 1;
+            let j = match dir {
+                PrevOrNext::Prev => if i == 0 { last } else { i - 1 },
+                PrevOrNext::Next => if i == last { 0 } else { i + 1 },
+            };
+            self.windows.swap(i, j);
+            self.update_geometries();
+        }
+    }
+}
+
+impl LayoutSupport for LayoutWM {
+    /// moves the active layout index back/forth, wrapping around the
+    /// *layouts* vec, and updates the geometries.
+    fn cycle_layout(&mut self, dir: PrevOrNext) {
+        let total_layouts = self.layouts.len();
+        self.index_layout = match dir {
+            PrevOrNext::Prev => (self.index_layout + total_layouts - 1) % total_layouts,
+            PrevOrNext::Next => (self.index_layout + 1) % total_layouts,
+        };
+        self.update_geometries()
+    }
+}
+
+impl TabBarSupport for LayoutWM {
+    /// returns the tab bar of the active layout when it is tabbed, with all
+    /// the windows as tabs.
+    fn get_tab_bars(&self) -> Vec<TabBar> {
+        match (self.get_layout().get_tab_bar_geometry(self.screen, &self.layout_settings),
+               self.get_active_tab()) {
+            (Some(geometry), Some(active_window)) => {
+                vec![TabBar {
+                         geometry: geometry,
+                         windows: self.get_tiled_windows(),
+                         active_window: Some(active_window),
+                     }]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl ColumnRatioSupport for LayoutWM {
+    /// returns the column ratio of the layout settings.
+    fn get_column_ratio(&self) -> ColumnRatio {
+        self.layout_settings.column_ratio
+    }
+
+    /// the ratio is clamped before storing it in the layout settings, then
+    /// the geometries are updated.
+    fn set_column_ratio(&mut self, ratio: ColumnRatio) {
+        self.layout_settings.column_ratio = max(MIN_COLUMN_RATIO, min(MAX_COLUMN_RATIO, ratio));
+        self.update_geometries()
+    }
+}
+
+impl MasterRatioSupport for LayoutWM {
+    /// returns the master ratio of the layout settings.
+    fn get_master_ratio(&self) -> MasterRatio {
+        self.layout_settings.master_ratio
+    }
+
+    /// the ratio is clamped before storing it in the layout settings, then
+    /// the geometries are updated.
+    fn set_master_ratio(&mut self, ratio: MasterRatio) {
+        self.layout_settings.master_ratio = max(MIN_MASTER_RATIO, min(MAX_MASTER_RATIO, ratio));
+        self.update_geometries()
+    }
+}
+
+impl MultiMasterSupport for LayoutWM {
+    /// returns the first windows, as many as the master count of the layout
+    /// settings.
+    fn get_master_windows(&self) -> Vec<Window> {
+        self.windows
+            .iter()
+            .take(self.layout_settings.master_count)
+            .map(|w| w.window)
+            .collect()
+    }
+
+    /// returns the master count of the layout settings.
+    fn get_master_count(&self) -> MasterCount {
+        self.layout_settings.master_count
+    }
+
+    /// the count is stored in the layout settings, a count of 0 is stored as
+    /// 1, then the geometries are updated.
+    fn set_master_count(&mut self, count: MasterCount) {
+        self.layout_settings.master_count = max(1, count);
+        self.update_geometries()
+    }
+}
+
+impl MasterPositionSupport for LayoutWM {
+    /// returns the master position of the layout settings for the current
+    /// screen.
+    fn get_master_position(&self) -> MasterPosition {
+        self.layout_settings.get_master_position(self.screen)
+    }
+
+    /// the position is stored in the layout settings, then the geometries
+    /// are updated.
+    fn set_master_position(&mut self, position: Option<MasterPosition>) {
+        self.layout_settings.master_position = position;
+        self.update_geometries()
+    }
+}
+
+impl DirectionalSupport for LayoutWM {
+    /// swaps the focused window with the window in the given direction of
+    /// the window layout in *windows*, the focused window stays focused.
+    fn swap_direction(&mut self, dir: Direction) {
+        let layout = self.get_window_layout();
+        let other_window = match layout.focused_window
+            .and_then(|w| layout.get_window_in_direction(w, dir)) {
+            None => return,
+            Some(window) => window,
+        };
+        // unwrap() is used because both windows are in the layout, hence
+        // they are managed
+        let focused_index = layout.focused_window.and_then(|w| self.get_index(w)).unwrap();
+        let other_index = self.get_index(other_window).unwrap();
+        self.windows.swap(focused_index, other_index);
+        self.update_geometries();
+    }
+}
+
+impl WindowPropertiesSupport for LayoutWM {
+    /// replaces the properties of the given window.
+    ///
+    /// returns an UnknownWindow error if the window is not managed.
+    fn set_window_properties(&mut self,
+                             window: Window,
+                             properties: WindowProperties)
+                             -> Result<(), Self::Error> {
+        match self.get_index(window) {
+            None => Err(TillingWMError::UnknownWindow(window)),
+            Some(i) => {
+                self.windows[i].properties = properties;
+                Ok(())
+            }
+        }
+    }
+}
+
+impl InvariantCheck for LayoutWM {
+    /// checks the invariants of the window manager and the tiling.
+    fn check_invariants(&self) -> Vec<String> {
+        let mut violations = check_window_manager(self);
+        violations.extend(check_tiling(self));
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    // We have to import the layouts from the super module.
+    use super::{CenteredMasterLayout, DwindleLayout, GridLayout, LayoutWM, MasterStackLayout,
+                MonocleLayout, ScrollingLayout, TAB_BAR_HEIGHT, TilingLayout};
+    // We have to repeat the imports we did in the super module.
+    use cplwm_api::layout::{Layout, LayoutSettings};
+    use cplwm_api::types::*;
+
+    // The generic tests of the traits that this window manager implements.
+    conformance_tests!(LayoutWM: window_manager, tiling);
+
+    // We define a static variable for the screen we will use in the tests.
+    static SCREEN: Screen = Screen {
+        width: 800,
+        height: 600,
+    };
+
+    // We define a static variable for the geometry of a fullscreen window.
+    static SCREEN_GEOM: Geometry = Geometry {
+        x: 0,
+        y: 0,
+        width: 800,
+        height: 600,
+    };
+
+    #[test]
+    fn test_master_stack_layout() {
+        let layout = MasterStackLayout;
+        let mut settings = LayoutSettings::default();
+
+        // No windows, no geometries
+        assert!(layout.get_geometries(SCREEN, &settings, &[]).is_empty());
+
+        // A single window takes the whole screen
+        assert_eq!(vec![(1, SCREEN_GEOM)], layout.get_geometries(SCREEN, &settings, &[1]));
+
+        // The master on the left, the others stacked on the right
+        let master_geom = Geometry {
+            x: 0,
+            y: 0,
+            width: 400,
+            height: 600,
+        };
+        let first_stack_geom = Geometry {
+            x: 400,
+            y: 0,
+            width: 400,
+            height: 300,
+        };
+        let second_stack_geom = Geometry {
+            x: 400,
+            y: 300,
+            width: 400,
+            height: 300,
+        };
+        assert_eq!(vec![(3, master_geom), (1, first_stack_geom), (2, second_stack_geom)],
+                   layout.get_geometries(SCREEN, &settings, &[3, 1, 2]));
+
+        // The enum gives the same geometries as the layout it wraps
+        let tiling_layout = TilingLayout::MasterStack(layout);
+        assert_eq!(layout.get_geometries(SCREEN, &settings, &[3, 1, 2]),
+                   tiling_layout.get_geometries(SCREEN, &settings, &[3, 1, 2]));
+
+        // The master ratio sets the width of the master area
+        settings.master_ratio = 75;
+        let geometries = layout.get_geometries(SCREEN, &settings, &[3, 1, 2]);
+        assert_eq!(600, geometries[0].1.width);
+        assert_eq!(600, geometries[1].1.x);
+        assert_eq!(200, geometries[1].1.width);
+
+        // A single window still takes the whole screen
+        assert_eq!(vec![(1, SCREEN_GEOM)], layout.get_geometries(SCREEN, &settings, &[1]));
+
+        // An invalid master ratio is clamped, neither side collapses
+        settings.master_ratio = 100;
+        let geometries = layout.get_geometries(SCREEN, &settings, &[3, 1, 2]);
+        assert_eq!(720, geometries[0].1.width);
+        assert_eq!(80, geometries[1].1.width);
+        settings.master_ratio = 0;
+        let geometries = layout.get_geometries(SCREEN, &settings, &[3, 1, 2]);
+        assert_eq!(80, geometries[0].1.width);
+        assert_eq!(720, geometries[1].1.width);
+
+        // Odd widths leave no pixels unused
+        let odd_screen = Screen {
+            width: 801,
+            height: 600,
+        };
+        settings.master_ratio = 50;
+        let geometries = layout.get_geometries(odd_screen, &settings, &[3, 1]);
+        assert_eq!(400, geometries[0].1.width);
+        assert_eq!(401, geometries[1].1.width);
+    }
+
+    #[test]
+    fn test_master_stack_layout_master_count() {
+        let layout = MasterStackLayout;
+        let mut settings = LayoutSettings::default();
+        settings.master_count = 2;
+
+        // Two masters on the left, the others stacked on the right
+        let geometries = layout.get_geometries(SCREEN, &settings, &[1, 2, 3, 4, 5]);
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 300,
+                       width: 400,
+                       height: 300,
+                   },
+                   geometries[1].1);
+        assert_eq!(Geometry {
+                       x: 400,
+                       y: 400,
+                       width: 400,
+                       height: 200,
+                   },
+                   geometries[4].1);
+
+        // When there are only masters, they take the whole width
+        let geometries = layout.get_geometries(SCREEN, &settings, &[1, 2]);
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 800,
+                       height: 300,
+                   },
+                   geometries[0].1);
+        assert_eq!(vec![(1, SCREEN_GEOM)], layout.get_geometries(SCREEN, &settings, &[1]));
+
+        // A count of 0 behaves like a single master
+        settings.master_count = 0;
+        let single_master = LayoutSettings::default();
+        assert_eq!(layout.get_geometries(SCREEN, &single_master, &[1, 2, 3]),
+                   layout.get_geometries(SCREEN, &settings, &[1, 2, 3]));
+    }
+
+    #[test]
+    fn test_master_stack_layout_master_position() {
+        let layout = MasterStackLayout;
+        let mut settings = LayoutSettings::default();
+        let geom = |x, y, width, height| {
+            Geometry {
+                x: x,
+                y: y,
+                width: width,
+                height: height,
+            }
+        };
+
+        // The master area on the right mirrors the layout
+        settings.master_position = Some(MasterPosition::Right);
+        assert_eq!(vec![(1, geom(400, 0, 400, 600)),
+                        (2, geom(0, 0, 400, 300)),
+                        (3, geom(0, 300, 400, 300))],
+                   layout.get_geometries(SCREEN, &settings, &[1, 2, 3]));
+
+        // At the top the windows are placed side by side, the master ratio
+        // applies to the height
+        settings.master_position = Some(MasterPosition::Top);
+        assert_eq!(vec![(1, geom(0, 0, 800, 300)),
+                        (2, geom(0, 300, 400, 300)),
+                        (3, geom(400, 300, 400, 300))],
+                   layout.get_geometries(SCREEN, &settings, &[1, 2, 3]));
+        settings.master_ratio = 75;
+        assert_eq!(geom(0, 0, 800, 450),
+                   layout.get_geometries(SCREEN, &settings, &[1, 2, 3])[0].1);
+        settings.master_ratio = 50;
+
+        // At the bottom both
+        settings.master_position = Some(MasterPosition::Bottom);
+        assert_eq!(vec![(1, geom(0, 300, 800, 300)),
+                        (2, geom(0, 0, 400, 300)),
+                        (3, geom(400, 0, 400, 300))],
+                   layout.get_geometries(SCREEN, &settings, &[1, 2, 3]));
+
+        // A single window still takes the whole screen
+        assert_eq!(vec![(1, SCREEN_GEOM)], layout.get_geometries(SCREEN, &settings, &[1]));
+
+        // Without a chosen position, the master area is at the top of a
+        // portrait screen
+        let portrait_screen = Screen {
+            width: 600,
+            height: 800,
+        };
+        settings.master_position = None;
+        assert_eq!(vec![(1, geom(0, 0, 600, 400)),
+                        (2, geom(0, 400, 300, 400)),
+                        (3, geom(300, 400, 300, 400))],
+                   layout.get_geometries(portrait_screen, &settings, &[1, 2, 3]));
+        assert_eq!(layout.get_geometries(SCREEN, &LayoutSettings::default(), &[1, 2, 3]),
+                   layout.get_geometries(SCREEN, &settings, &[1, 2, 3]));
+
+        // A chosen position is kept on a portrait screen
+        settings.master_position = Some(MasterPosition::Left);
+        assert_eq!(geom(0, 0, 300, 800),
+                   layout.get_geometries(portrait_screen, &settings, &[1, 2, 3])[0].1);
+    }
+
+    #[test]
+    fn test_monocle_layout() {
+        let layout = MonocleLayout;
+        let settings = LayoutSettings::default();
+        let tab_bar_geom = Geometry {
+            x: 0,
+            y: 0,
+            width: 800,
+            height: TAB_BAR_HEIGHT,
+        };
+        let window_geom = Geometry {
+            x: 0,
+            y: TAB_BAR_HEIGHT as i32,
+            width: 800,
+            height: 600 - TAB_BAR_HEIGHT,
+        };
+
+        // No windows, no geometries, but still a tab bar
+        assert!(layout.get_geometries(SCREEN, &settings, &[]).is_empty());
+        assert_eq!(Some(tab_bar_geom), layout.get_tab_bar_geometry(SCREEN, &settings));
+
+        // Every window takes the screen below the tab bar
+        assert_eq!(vec![(3, window_geom), (1, window_geom), (2, window_geom)],
+                   layout.get_geometries(SCREEN, &settings, &[3, 1, 2]));
+
+        // The enum gives the same geometries and tab bar
+        let tiling_layout = TilingLayout::Monocle(layout);
+        assert_eq!(layout.get_geometries(SCREEN, &settings, &[3, 1, 2]),
+                   tiling_layout.get_geometries(SCREEN, &settings, &[3, 1, 2]));
+        assert_eq!(Some(tab_bar_geom), tiling_layout.get_tab_bar_geometry(SCREEN, &settings));
+
+        // The master stack layout has no tab bar
+        let tiling_layout = TilingLayout::MasterStack(MasterStackLayout);
+        assert_eq!(None, tiling_layout.get_tab_bar_geometry(SCREEN, &settings));
+
+        // The tab bar never takes more than the screen
+        let tiny_screen = Screen {
+            width: 10,
+            height: 10,
+        };
+        let geometries = layout.get_geometries(tiny_screen, &settings, &[1]);
+        assert_eq!(10, geometries[0].1.y);
+        assert_eq!(0, geometries[0].1.height);
+        assert_eq!(10, layout.get_tab_bar_geometry(tiny_screen, &settings).unwrap().height);
+    }
+
+    #[test]
+    fn test_grid_layout() {
+        let layout = GridLayout;
+        let settings = LayoutSettings::default();
+
+        // The number of columns
+        assert_eq!(0, GridLayout::get_column_count(0));
+        assert_eq!(1, GridLayout::get_column_count(1));
+        assert_eq!(2, GridLayout::get_column_count(2));
+        assert_eq!(2, GridLayout::get_column_count(4));
+        assert_eq!(3, GridLayout::get_column_count(5));
+        assert_eq!(3, GridLayout::get_column_count(9));
+        assert_eq!(4, GridLayout::get_column_count(12));
+
+        // No windows, no geometries
+        assert!(layout.get_geometries(SCREEN, &settings, &[]).is_empty());
+
+        // A single window takes the whole screen
+        assert_eq!(vec![(1, SCREEN_GEOM)], layout.get_geometries(SCREEN, &settings, &[1]));
+
+        // Six windows in two rows of three, in the given order
+        let geometries = layout.get_geometries(SCREEN, &settings, &[6, 5, 4, 3, 2, 1]);
+        assert_eq!(vec![6, 5, 4, 3, 2, 1],
+                   geometries.iter().map(|&(w, _)| w).collect::<Vec<_>>());
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 267,
+                       height: 300,
+                   },
+                   geometries[0].1);
+        assert_eq!(Geometry {
+                       x: 267,
+                       y: 300,
+                       width: 267,
+                       height: 300,
+                   },
+                   geometries[4].1);
+        // The leftover pixel goes to the first column
+        assert_eq!(Geometry {
+                       x: 534,
+                       y: 300,
+                       width: 266,
+                       height: 300,
+                   },
+                   geometries[5].1);
+
+        // The last row shares its width among the remaining windows
+        let geometries = layout.get_geometries(SCREEN, &settings, &[1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 400,
+                       width: 800,
+                       height: 200,
+                   },
+                   geometries[6].1);
+        let geometries = layout.get_geometries(SCREEN, &settings, &[1, 2, 3]);
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 300,
+                       width: 800,
+                       height: 300,
+                   },
+                   geometries[2].1);
+
+        // Twelve windows: the tiles cover the screen without overlapping
+        let windows: Vec<Window> = (1..13).collect();
+        let geometries = layout.get_geometries(SCREEN, &settings, &windows);
+        let area: u32 = geometries.iter().map(|&(_, g)| g.width * g.height).sum();
+        assert_eq!(800 * 600, area);
+        for &(_, g) in geometries.iter() {
+            assert_eq!(200, g.width);
+            assert_eq!(200, g.height);
+        }
+
+        // The enum gives the same geometries
+        let tiling_layout = TilingLayout::Grid(layout);
+        assert_eq!(layout.get_geometries(SCREEN, &settings, &windows),
+                   tiling_layout.get_geometries(SCREEN, &settings, &windows));
+        assert_eq!(None, tiling_layout.get_tab_bar_geometry(SCREEN, &settings));
+    }
+
+    #[test]
+    fn test_dwindle_layout() {
+        let layout = DwindleLayout;
+        let settings = LayoutSettings::default();
+
+        // No windows, no geometries
+        assert!(layout.get_geometries(SCREEN, &settings, &[]).is_empty());
+
+        // A single window takes the whole screen
+        assert_eq!(vec![(1, SCREEN_GEOM)], layout.get_geometries(SCREEN, &settings, &[1]));
+
+        // Every window halves the space that is left, along its longest side
+        let geometries = layout.get_geometries(SCREEN, &settings, &[4, 3, 2, 1]);
+        assert_eq!(vec![(4,
+                         Geometry {
+                             x: 0,
+                             y: 0,
+                             width: 400,
+                             height: 600,
+                         }),
+                        (3,
+                         Geometry {
+                             x: 400,
+                             y: 0,
+                             width: 400,
+                             height: 300,
+                         }),
+                        (2,
+                         Geometry {
+                             x: 400,
+                             y: 300,
+                             width: 200,
+                             height: 300,
+                         }),
+                        (1,
+                         Geometry {
+                             x: 600,
+                             y: 300,
+                             width: 200,
+                             height: 300,
+                         })],
+                   geometries);
+
+        // On a portrait screen the first split is horizontal, the first half
+        // gets the odd pixel
+        let portrait_screen = Screen {
+            width: 600,
+            height: 801,
+        };
+        let geometries = layout.get_geometries(portrait_screen, &settings, &[1, 2]);
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 0,
+                       width: 600,
+                       height: 401,
+                   },
+                   geometries[0].1);
+        assert_eq!(Geometry {
+                       x: 0,
+                       y: 401,
+                       width: 600,
+                       height: 400,
+                   },
+                   geometries[1].1);
+
+        // The tiles cover the screen without overlapping
+        let windows: Vec<Window> = (1..10).collect();
+        let geometries = layout.get_geometries(SCREEN, &settings, &windows);
+        let area: u32 = geometries.iter().map(|&(_, g)| g.width * g.height).sum();
+        assert_eq!(800 * 600, area);
+
+        // The enum gives the same geometries
+        let tiling_layout = TilingLayout::Dwindle(layout);
+        assert_eq!(layout.get_geometries(SCREEN, &settings, &windows),
+                   tiling_layout.get_geometries(SCREEN, &settings, &windows));
+    }
+
+    #[test]
+    fn test_centered_master_layout() {
+        let layout = CenteredMasterLayout;
+        let mut settings = LayoutSettings::default();
+
+        // No windows, no geometries
+        assert!(layout.get_geometries(SCREEN, &settings, &[]).is_empty());
+
+        // A single window takes the whole screen
+        assert_eq!(vec![(1, SCREEN_GEOM)], layout.get_geometries(SCREEN, &settings, &[1]));
+
+        // Two windows fall back to the master-stack layout
+        assert_eq!(MasterStackLayout.get_geometries(SCREEN, &settings, &[1, 2]),
+                   layout.get_geometries(SCREEN, &settings, &[1, 2]));
+
+        // The master in the center, the stack alternates between the right
+        // and the left column
+        let geometries = layout.get_geometries(SCREEN, &settings, &[1, 2, 3, 4, 5]);
+        assert_eq!(vec![(1,
+                         Geometry {
+                             x: 200,
+                             y: 0,
+                             width: 400,
+                             height: 600,
+                         }),
+                        (2,
+                         Geometry {
+                             x: 600,
+                             y: 0,
+                             width: 200,
+                             height: 300,
+                         }),
+                        (4,
+                         Geometry {
+                             x: 600,
+                             y: 300,
+                             width: 200,
+                             height: 300,
+                         }),
+                        (3,
+                         Geometry {
+                             x: 0,
+                             y: 0,
+                             width: 200,
+                             height: 300,
+                         }),
+                        (5,
+                         Geometry {
+                             x: 0,
+                             y: 300,
+                             width: 200,
+                             height: 300,
+                         })],
+                   geometries);
+
+        // The master ratio sets the width of the center column, the left
+        // column gets the odd pixel
+        settings.master_ratio = 75;
+        let geometries = layout.get_geometries(SCREEN, &settings, &[1, 2, 3]);
+        assert_eq!(vec![(1,
+                         Geometry {
+                             x: 100,
+                             y: 0,
+                             width: 600,
+                             height: 600,
+                         }),
+                        (2,
+                         Geometry {
+                             x: 700,
+                             y: 0,
+                             width: 100,
+                             height: 600,
+                         }),
+                        (3,
+                         Geometry {
+                             x: 0,
+                             y: 0,
+                             width: 100,
+                             height: 600,
+                         })],
+                   geometries);
+        let odd_screen = Screen {
+            width: 801,
+            height: 600,
+        };
+        settings.master_ratio = 50;
+        let geometries = layout.get_geometries(odd_screen, &settings, &[1, 2, 3]);
+        assert_eq!(vec![(1, 201, 400), (2, 601, 200), (3, 0, 201)],
+                   geometries.iter()
+                       .map(|&(w, g)| (w, g.x, g.width))
+                       .collect::<Vec<_>>());
+
+        // The master windows share the center column
+        settings.master_count = 2;
+        let geometries = layout.get_geometries(SCREEN, &settings, &[1, 2, 3, 4]);
+        assert_eq!(vec![(1, 200, 0), (2, 200, 300), (3, 600, 0), (4, 0, 0)],
+                   geometries.iter()
+                       .map(|&(w, g)| (w, g.x, g.y))
+                       .collect::<Vec<_>>());
+        // and with a single stack window there are two columns
+        assert_eq!(MasterStackLayout.get_geometries(SCREEN, &settings, &[1, 2, 3]),
+                   layout.get_geometries(SCREEN, &settings, &[1, 2, 3]));
+
+        // The enum gives the same geometries
+        let tiling_layout = TilingLayout::CenteredMaster(layout);
+        assert_eq!(layout.get_geometries(SCREEN, &settings, &[1, 2, 3, 4]),
+                   tiling_layout.get_geometries(SCREEN, &settings, &[1, 2, 3, 4]));
+    }
+    #[test]
+    fn test_scrolling_layout() {
+        let mut layout = ScrollingLayout::new();
+        let settings = LayoutSettings::default();
+
+        // No windows, no geometries
+        assert!(layout.get_geometries(SCREEN, &settings, &[]).is_empty());
+
+        // A single window keeps its column width
+        let column_geom = |x| {
+            Geometry {
+                x: x,
+                y: 0,
+                width: 400,
+                height: 600,
+            }
+        };
+        assert_eq!(vec![(1, column_geom(0))],
+                   layout.get_geometries(SCREEN, &settings, &[1]));
+
+        // Only the first two columns are visible
+        let windows = [1, 2, 3, 4, 5];
+        assert_eq!(vec![(1, column_geom(0)), (2, column_geom(400))],
+                   layout.get_geometries(SCREEN, &settings, &windows));
+
+        // Focusing a window on the right scrolls just enough to show it
+        layout.follow_focus(SCREEN, &settings, &windows, 4);
+        assert_eq!(2, layout.first_column);
+        assert_eq!(vec![(3, column_geom(0)), (4, column_geom(400))],
+                   layout.get_geometries(SCREEN, &settings, &windows));
+
+        // A visible window does not scroll
+        layout.follow_focus(SCREEN, &settings, &windows, 3);
+        assert_eq!(2, layout.first_column);
+
+        // Focusing a window on the left scrolls back
+        layout.follow_focus(SCREEN, &settings, &windows, 2);
+        assert_eq!(1, layout.first_column);
+        assert_eq!(vec![(2, column_geom(0)), (3, column_geom(400))],
+                   layout.get_geometries(SCREEN, &settings, &windows));
+
+        // When windows are removed the viewport stays filled
+        layout.first_column = 4;
+        assert_eq!(vec![(2, column_geom(0)), (3, column_geom(400))],
+                   layout.get_geometries(SCREEN, &settings, &[1, 2, 3]));
+
+        // Narrower columns show more windows, the ratio is clamped
+        let layout = ScrollingLayout::new();
+        let column_settings = |ratio| LayoutSettings { column_ratio: ratio, ..settings };
+        assert_eq!(4, layout.get_geometries(SCREEN, &column_settings(25), &windows).len());
+        assert_eq!(80, layout.get_column_width(SCREEN, &column_settings(0)));
+        assert_eq!(vec![(1, SCREEN_GEOM)],
+                   layout.get_geometries(SCREEN, &column_settings(150), &windows));
+
+        // The enum gives the same geometries and follows the focus too
+        let mut tiling_layout = TilingLayout::Scrolling(ScrollingLayout::new());
+        tiling_layout.follow_focus(SCREEN, &settings, &windows, 5);
+        assert_eq!(vec![(4, column_geom(0)), (5, column_geom(400))],
+                   tiling_layout.get_geometries(SCREEN, &settings, &windows));
+    }
+}
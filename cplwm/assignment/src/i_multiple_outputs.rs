@@ -0,0 +1,1042 @@
+//! Extra: Multiple Outputs
+//!
+//! Extend your window manager with support for multiple outputs (monitors).
+//! See the documentation of the [`MultiOutputSupport`] trait for the precise
+//! requirements.
+//!
+//! Like the multiple workspaces, this is a wrapper implementing the
+//! [`MultiOutputSupport`] trait that takes any existing window manager and
+//! uses it for the different outputs.
+//!
+//! [`MultiOutputSupport`]: ../../cplwm_api/wm/trait.MultiOutputSupport.html
+//!
+//! # Status
+//!
+//! COMPLETED: YES
+//!
+//! COMMENTS:
+//!
+//! ## General approach
+//!
+//! *MultiOutputWM* stores the geometries of the outputs, one window manager
+//! per output and the index of the current output. Every window manager
+//! gets a screen with the size of its output and works with geometries
+//! relative to its output, so it does not need to know about the other
+//! outputs. The wrapper translates the geometries: *get_window_layout* and
+//! *get_window_info* add the origin of the output, *add_window* and
+//! *set_window_geometry* subtract it.
+//!
+//! *get_window_layout* contains the windows of all the outputs, as all
+//! outputs are visible at the same time. The focused window is the focused
+//! window of the current output.
+//!
+//! The functions are forwarded in the same way as in the
+//! *MultiWorkspaceWM*: to the current output, or to the output the window
+//! belongs to, switching to that output when the window must be focused. A
+//! new window is added to the current output, when it would not be visible
+//! on that output, it is centered on it.
+//!
+//! The backend calls *set_outputs* after querying the outputs. The windows of
+//! outputs that are removed are added again to the current output, this
+//! means that a window that was minimised or on a hidden workspace becomes
+//! visible again.
+//!
+//! The window manager of assignment G is used for the outputs, so every
+//! output has its own workspaces.
+//!
+//! ## Urgency
+//!
+//! Like *MultiWorkspaceWM*, *MultiOutputWM* keeps the urgent windows of all
+//! the outputs itself, as the output window managers don't know which
+//! output is the current one.
+
+// Add imports here
+use std::error;
+use std::fmt;
+
+use cplwm_api::types::{ColumnRatio, Direction, GapSize, Geometry, MasterCount, MasterPosition,
+                       MasterRatio, OutputIndex, PrevOrNext, Screen, TabBar, Window, WindowLayout,
+                       WindowProperties, WindowWithInfo, WorkspaceIndex};
+use cplwm_api::wm::WindowManager;
+use cplwm_api::wm::TilingSupport;
+use cplwm_api::wm::FloatSupport;
+use cplwm_api::wm::MinimiseSupport;
+use cplwm_api::wm::FullscreenSupport;
+use cplwm_api::wm::GapSupport;
+use cplwm_api::wm::LayoutSupport;
+use cplwm_api::wm::TabBarSupport;
+use cplwm_api::wm::ColumnRatioSupport;
+use cplwm_api::wm::MasterRatioSupport;
+use cplwm_api::wm::MultiMasterSupport;
+use cplwm_api::wm::MasterPositionSupport;
+use cplwm_api::wm::DirectionalSupport;
+use cplwm_api::wm::WindowPropertiesSupport;
+use cplwm_api::wm::ScratchpadSupport;
+use cplwm_api::wm::UrgencySupport;
+use cplwm_api::wm::StickySupport;
+use cplwm_api::wm::FocusHistorySupport;
+use cplwm_api::wm::MultiWorkspaceSupport;
+use cplwm_api::wm::MultiOutputSupport;
+use cplwm_api::wm::InvariantCheck;
+
+use g_multiple_workspaces;
+use n_invariants::{check_window_manager, prefix_violations};
+
+/// Window manager aliase.
+pub type WMName = MultiOutputWM<g_multiple_workspaces::WMName>;
+
+/// The MultiOutputWM struct
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
+pub struct MultiOutputWM<WM> {
+    /// The geometries of the outputs, the index in the vector is the index
+    /// of the output.
+    pub outputs: Vec<Geometry>,
+    /// The window manager of every output, at the same index as its output.
+    pub output_wms: Vec<WM>,
+    /// The index of the current output.
+    pub current_output: OutputIndex,
+    /// The size of the whole screen.
+    pub screen: Screen,
+    /// The urgent windows of all the outputs, the one that became urgent
+    /// most recently comes first.
+    pub urgent_windows: Vec<Window>,
+}
+
+/// returns the screen that a window manager of the given output gets.
+fn get_output_screen(output: Geometry) -> Screen {
+    Screen {
+        width: output.width,
+        height: output.height,
+    }
+}
+
+/// translates a geometry relative to the given output to one relative to
+/// the screen.
+fn to_screen_geometry(output: Geometry, geometry: Geometry) -> Geometry {
+    Geometry {
+        x: geometry.x + output.x,
+        y: geometry.y + output.y,
+        width: geometry.width,
+        height: geometry.height,
+    }
+}
+
+/// translates a geometry relative to the screen to one relative to the
+/// given output.
+fn to_output_geometry(output: Geometry, geometry: Geometry) -> Geometry {
+    Geometry {
+        x: geometry.x - output.x,
+        y: geometry.y - output.y,
+        width: geometry.width,
+        height: geometry.height,
+    }
+}
+
+/// translates a geometry relative to the screen to one relative to the
+/// given output, like *to_output_geometry*. When the center of the geometry
+/// is not on the output, the geometry is centered on the output instead.
+fn fit_in_output(output: Geometry, geometry: Geometry) -> Geometry {
+    let center_x = geometry.x + (geometry.width / 2) as i32;
+    let center_y = geometry.y + (geometry.height / 2) as i32;
+    if center_x >= output.x && center_x < output.x + output.width as i32 &&
+       center_y >= output.y && center_y < output.y + output.height as i32 {
+        to_output_geometry(output, geometry)
+    } else {
+        Geometry {
+            x: (output.width as i32 - geometry.width as i32) / 2,
+            y: (output.height as i32 - geometry.height as i32) / 2,
+            width: geometry.width,
+            height: geometry.height,
+        }
+    }
+}
+
+/// Supported functions
+impl<WM: WindowManager> MultiOutputWM<WM> {
+    /// returns the window manager of the current output.
+    fn get_current(&self) -> &WM {
+        // unwrap() is used because current_output is always a valid index
+        self.output_wms.get(self.current_output).unwrap()
+    }
+
+    /// returns the window manager of the current output mutably.
+    fn get_current_mut(&mut self) -> &mut WM {
+        // unwrap() is used because current_output is always a valid index
+        self.output_wms.get_mut(self.current_output).unwrap()
+    }
+
+    /// returns the index of the output whose window manager manages the
+    /// given window, if there is no such output the current output index is
+    /// returned.
+    fn get_window_output_index(&self, window: Window) -> OutputIndex {
+        match self.output_wms.iter().position(|wm| wm.is_managed(window)) {
+            None => self.current_output,
+            Some(index) => index,
+        }
+    }
+
+    /// returns the window manager of the output that manages the given
+    /// window, or the one of the current output, mutably.
+    fn get_window_output_mut(&mut self, window: Window) -> &mut WM {
+        let index = self.get_window_output_index(window);
+        // unwrap() is used because the index is always a valid index
+        self.output_wms.get_mut(index).unwrap()
+    }
+
+    /// switch to the output that manages the given window, if any.
+    fn switch_to_window_output(&mut self, window: Window) {
+        self.current_output = self.get_window_output_index(window);
+    }
+
+    /// replaces the outputs.
+    ///
+    /// The window managers of the remaining outputs are resized, new
+    /// outputs get the window manager made by *new_output_wm*. The windows
+    /// of the removed outputs are added to the current output.
+    fn update_outputs<F>(&mut self, outputs: Vec<Geometry>, new_output_wm: F)
+        where F: Fn(Screen) -> WM
+    {
+        if !outputs.is_empty() {
+            let mut removed_windows = Vec::new();
+            while self.output_wms.len() > outputs.len() {
+                // unwrap() is used because there are more window managers
+                // than new outputs, so at least one
+                let wm = self.output_wms.pop().unwrap();
+                let output = self.outputs.pop().unwrap();
+                for window in wm.get_windows() {
+                    if let Ok(mut window_with_info) = wm.get_window_info(window) {
+                        window_with_info.geometry = to_screen_geometry(output,
+                                                                       window_with_info.geometry);
+                        removed_windows.push(window_with_info);
+                    }
+                }
+            }
+
+            for (index, output) in outputs.iter().enumerate() {
+                match self.output_wms.get_mut(index) {
+                    None => (),
+                    Some(wm) => wm.resize_screen(get_output_screen(*output)),
+                }
+                if index >= self.output_wms.len() {
+                    self.output_wms.push(new_output_wm(get_output_screen(*output)));
+                }
+            }
+
+            self.outputs = outputs;
+            if self.current_output >= self.outputs.len() {
+                self.current_output = self.outputs.len() - 1;
+            }
+
+            for window_with_info in removed_windows {
+                // The window was managed by a removed output, so the current
+                // output does not manage it yet
+                let _ = self.add_window(window_with_info);
+            }
+        }
+    }
+}
+
+/// The errors that this window manager can return.
+#[derive(Debug)]
+pub enum MultiOutputWMError<E> {
+    /// There is no output with this index.
+    UnknownOutput(OutputIndex),
+    /// An error returned by the window manager of an output.
+    OutputError(E),
+}
+
+impl<E: error::Error> fmt::Display for MultiOutputWMError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MultiOutputWMError::UnknownOutput(ref index) => write!(f, "Unknown output: {}", index),
+            MultiOutputWMError::OutputError(ref err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl<E: error::Error> error::Error for MultiOutputWMError<E> {
+    fn description(&self) -> &str {
+        match *self {
+            MultiOutputWMError::UnknownOutput(_) => "Unknown output",
+            MultiOutputWMError::OutputError(ref err) => err.description(),
+        }
+    }
+}
+
+impl<WM: WindowManager> WindowManager for MultiOutputWM<WM> {
+    type Error = MultiOutputWMError<WM::Error>;
+
+    /// The MultiOutputWM constructor.
+    ///
+    /// There is a single output that covers the whole screen.
+    fn new(screen: Screen) -> MultiOutputWM<WM> {
+        MultiOutputWM {
+            outputs: vec![screen.to_geometry()],
+            output_wms: vec![WM::new(screen)],
+            current_output: 0,
+            screen: screen,
+            urgent_windows: Vec::new(),
+        }
+    }
+
+    /// returns the layouts of all the outputs, the geometries are relative
+    /// to the screen. The focused window is the one of the current output.
+    fn get_window_layout(&self) -> WindowLayout {
+        let mut temp_windows = Vec::new();
+        for (output, wm) in self.outputs.iter().zip(self.output_wms.iter()) {
+            for (window, geometry) in wm.get_window_layout().windows {
+                temp_windows.push((window, to_screen_geometry(*output, geometry)));
+            }
+        }
+        WindowLayout {
+            focused_window: self.get_current().get_focused_window(),
+            windows: temp_windows,
+        }
+    }
+
+    /// checks all the outputs.
+    fn is_managed(&self, window: Window) -> bool {
+        self.output_wms.iter().any(|wm| wm.is_managed(window))
+    }
+
+    /// Returns the windows of all the outputs.
+    fn get_windows(&self) -> Vec<Window> {
+        let mut temp_windows = Vec::new();
+        for wm in self.output_wms.iter() {
+            temp_windows.extend(wm.get_windows());
+        }
+        temp_windows
+    }
+
+    /// gets the focused window of the current output.
+    fn get_focused_window(&self) -> Option<Window> {
+        self.get_current().get_focused_window()
+    }
+
+    /// adds the window to the current output.
+    ///
+    /// When the window is already managed by another output, it is passed to
+    /// that output, which returns the appropriate error.
+    fn add_window(&mut self, window_with_info: WindowWithInfo) -> Result<(), Self::Error> {
+        let index = self.get_window_output_index(window_with_info.window);
+        // unwrap() is used because the index is always a valid index
+        let output = *self.outputs.get(index).unwrap();
+        let geometry = fit_in_output(output, window_with_info.geometry);
+        let mut output_window = window_with_info;
+        output_window.geometry = geometry;
+        self.output_wms
+            .get_mut(index)
+            .unwrap()
+            .add_window(output_window)
+            .map_err(MultiOutputWMError::OutputError)
+    }
+
+    /// removes the window from the output that manages it.
+    fn remove_window(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.get_window_output_mut(window)
+            .remove_window(window)
+            .map_err(MultiOutputWMError::OutputError)
+    }
+
+    /// focus the given window, if the window belongs to another output, that
+    /// output becomes the current one.
+    fn focus_window(&mut self, window: Option<Window>) -> Result<(), Self::Error> {
+        if let Some(w) = window {
+            self.switch_to_window_output(w);
+        }
+        try!(self.get_current_mut()
+            .focus_window(window)
+            .map_err(MultiOutputWMError::OutputError));
+        // the user is looking at the window now
+        self.urgent_windows.retain(|w| Some(*w) != window);
+        Ok(())
+    }
+
+    /// cycle the focus in the current output.
+    fn cycle_focus(&mut self, dir: PrevOrNext) {
+        self.get_current_mut().cycle_focus(dir);
+        if let Some(window) = self.get_focused_window() {
+            self.urgent_windows.retain(|w| *w != window);
+        }
+    }
+
+    /// gets the info of the window from the output that manages it, the
+    /// geometry is relative to the screen.
+    fn get_window_info(&self, window: Window) -> Result<WindowWithInfo, Self::Error> {
+        let index = self.get_window_output_index(window);
+        // unwrap() is used because the index is always a valid index
+        let output = *self.outputs.get(index).unwrap();
+        let mut window_with_info = try!(self.output_wms
+            .get(index)
+            .unwrap()
+            .get_window_info(window)
+            .map_err(MultiOutputWMError::OutputError));
+        window_with_info.geometry = to_screen_geometry(output, window_with_info.geometry);
+        Ok(window_with_info)
+    }
+
+    /// gets the size of the whole screen.
+    fn get_screen(&self) -> Screen {
+        self.screen
+    }
+
+    /// resizes the whole screen.
+    ///
+    /// When the only output covers the whole screen, it keeps covering it.
+    /// Otherwise the outputs are not changed, the backend sets them with
+    /// *set_outputs*.
+    fn resize_screen(&mut self, screen: Screen) {
+        if self.outputs == vec![self.screen.to_geometry()] {
+            // The single output is resized, so no new window manager is made
+            self.update_outputs(vec![screen.to_geometry()], WM::new);
+        }
+        self.screen = screen;
+    }
+}
+
+impl<WM: TilingSupport> TilingSupport for MultiOutputWM<WM> {
+    /// returns the master window of the current output.
+    fn get_master_window(&self) -> Option<Window> {
+        self.get_current().get_master_window()
+    }
+
+    /// swap the window with the master window of its output, which becomes
+    /// the current output since the master window is focused.
+    fn swap_with_master(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.switch_to_window_output(window);
+        self.get_current_mut()
+            .swap_with_master(window)
+            .map_err(MultiOutputWMError::OutputError)
+    }
+
+    /// swap the windows in the current output.
+    fn swap_windows(&mut self, dir: PrevOrNext) {
+        self.get_current_mut().swap_windows(dir)
+    }
+}
+
+impl<WM: FloatSupport> FloatSupport for MultiOutputWM<WM> {
+    /// returns the floating windows of the current output.
+    fn get_floating_windows(&self) -> Vec<Window> {
+        self.get_current().get_floating_windows()
+    }
+
+    /// toggles the window in the output that manages it.
+    fn toggle_floating(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.get_window_output_mut(window)
+            .toggle_floating(window)
+            .map_err(MultiOutputWMError::OutputError)
+    }
+
+    /// sets the geometry, relative to the screen, in the output that manages
+    /// the window. The window stays in its output, even when it is moved to
+    /// another one.
+    fn set_window_geometry(&mut self,
+                           window: Window,
+                           new_geometry: Geometry)
+                           -> Result<(), Self::Error> {
+        let index = self.get_window_output_index(window);
+        // unwrap() is used because the index is always a valid index
+        let output = *self.outputs.get(index).unwrap();
+        self.output_wms
+            .get_mut(index)
+            .unwrap()
+            .set_window_geometry(window, to_output_geometry(output, new_geometry))
+            .map_err(MultiOutputWMError::OutputError)
+    }
+}
+
+impl<WM: MinimiseSupport> MinimiseSupport for MultiOutputWM<WM> {
+    /// returns the minimised windows of the current output.
+    fn get_minimised_windows(&self) -> Vec<Window> {
+        self.get_current().get_minimised_windows()
+    }
+
+    /// checks the output that manages the window, which is not necessarily
+    /// the current one.
+    fn is_minimised(&self, window: Window) -> bool {
+        let index = self.get_window_output_index(window);
+        self.output_wms[index].is_minimised(window)
+    }
+
+    /// (un)minimises the window in the output that manages it.
+    fn toggle_minimised(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.get_window_output_mut(window)
+            .toggle_minimised(window)
+            .map_err(MultiOutputWMError::OutputError)
+    }
+}
+
+impl<WM: FullscreenSupport> FullscreenSupport for MultiOutputWM<WM> {
+    /// returns the fullscreen window of the current output.
+    fn get_fullscreen_window(&self) -> Option<Window> {
+        self.get_current().get_fullscreen_window()
+    }
+
+    /// toggles the fullscreen of the window in its output, which becomes the
+    /// current output since the fullscreen window is focused. The window
+    /// takes the whole output, not the whole screen.
+    fn toggle_fullscreen(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.switch_to_window_output(window);
+        self.get_current_mut()
+            .toggle_fullscreen(window)
+            .map_err(MultiOutputWMError::OutputError)
+    }
+}
+
+impl<WM: GapSupport> GapSupport for MultiOutputWM<WM> {
+    /// returns the gap of the current output, all outputs share the same
+    /// gap.
+    fn get_gap(&self) -> GapSize {
+        self.get_current().get_gap()
+    }
+
+    /// sets the gap of all the outputs.
+    fn set_gap(&mut self, gap: GapSize) {
+        for wm in self.output_wms.iter_mut() {
+            wm.set_gap(gap);
+        }
+    }
+}
+
+impl<WM: LayoutSupport> LayoutSupport for MultiOutputWM<WM> {
+    /// cycle the layout of the current output.
+    fn cycle_layout(&mut self, dir: PrevOrNext) {
+        self.get_current_mut().cycle_layout(dir)
+    }
+}
+
+impl<WM: TabBarSupport> TabBarSupport for MultiOutputWM<WM> {
+    /// returns the tab bars of all the outputs, in screen coordinates.
+    fn get_tab_bars(&self) -> Vec<TabBar> {
+        let mut tab_bars = Vec::new();
+        for (output, wm) in self.outputs.iter().zip(self.output_wms.iter()) {
+            for mut tab_bar in wm.get_tab_bars() {
+                tab_bar.geometry = to_screen_geometry(*output, tab_bar.geometry);
+                tab_bars.push(tab_bar);
+            }
+        }
+        tab_bars
+    }
+}
+
+impl<WM: ColumnRatioSupport> ColumnRatioSupport for MultiOutputWM<WM> {
+    /// returns the column ratio of the current output.
+    fn get_column_ratio(&self) -> ColumnRatio {
+        self.get_current().get_column_ratio()
+    }
+
+    /// sets the column ratio of the current output.
+    fn set_column_ratio(&mut self, ratio: ColumnRatio) {
+        self.get_current_mut().set_column_ratio(ratio)
+    }
+}
+
+impl<WM: MasterRatioSupport> MasterRatioSupport for MultiOutputWM<WM> {
+    /// returns the master ratio of the current output.
+    fn get_master_ratio(&self) -> MasterRatio {
+        self.get_current().get_master_ratio()
+    }
+
+    /// sets the master ratio of the current output.
+    fn set_master_ratio(&mut self, ratio: MasterRatio) {
+        self.get_current_mut().set_master_ratio(ratio)
+    }
+}
+
+impl<WM: MultiMasterSupport> MultiMasterSupport for MultiOutputWM<WM> {
+    /// returns the master windows of the current output.
+    fn get_master_windows(&self) -> Vec<Window> {
+        self.get_current().get_master_windows()
+    }
+
+    /// returns the number of master windows of the current output.
+    fn get_master_count(&self) -> MasterCount {
+        self.get_current().get_master_count()
+    }
+
+    /// sets the number of master windows of the current output.
+    fn set_master_count(&mut self, count: MasterCount) {
+        self.get_current_mut().set_master_count(count)
+    }
+}
+
+impl<WM: MasterPositionSupport> MasterPositionSupport for MultiOutputWM<WM> {
+    /// returns the master position of the current output.
+    fn get_master_position(&self) -> MasterPosition {
+        self.get_current().get_master_position()
+    }
+
+    /// sets the master position of the current output, the other outputs
+    /// keep theirs, e.g. the default one of a portrait output.
+    fn set_master_position(&mut self, position: Option<MasterPosition>) {
+        self.get_current_mut().set_master_position(position)
+    }
+}
+
+impl<WM: DirectionalSupport> DirectionalSupport for MultiOutputWM<WM> {
+    /// swap the windows in the current output. The focus can move to another
+    /// output with *focus_direction*, but windows are only swapped within an
+    /// output.
+    fn swap_direction(&mut self, dir: Direction) {
+        self.get_current_mut().swap_direction(dir)
+    }
+}
+
+impl<WM: WindowPropertiesSupport> WindowPropertiesSupport for MultiOutputWM<WM> {
+    /// replaces the properties in the output that manages the window.
+    fn set_window_properties(&mut self,
+                             window: Window,
+                             properties: WindowProperties)
+                             -> Result<(), Self::Error> {
+        self.get_window_output_mut(window)
+            .set_window_properties(window, properties)
+            .map_err(MultiOutputWMError::OutputError)
+    }
+}
+
+impl<WM: ScratchpadSupport> ScratchpadSupport for MultiOutputWM<WM> {
+    /// returns the scratchpad windows of the current output first, followed
+    /// by the ones of the other outputs in order.
+    fn get_scratchpad_windows(&self) -> Vec<Window> {
+        let mut windows = self.get_current().get_scratchpad_windows();
+        for (index, wm) in self.output_wms.iter().enumerate() {
+            if index != self.current_output {
+                windows.extend(wm.get_scratchpad_windows());
+            }
+        }
+        windows
+    }
+
+    /// moves the window to the scratchpad in the output that manages it.
+    fn move_to_scratchpad(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.get_window_output_mut(window)
+            .move_to_scratchpad(window)
+            .map_err(MultiOutputWMError::OutputError)
+    }
+
+    /// toggles the scratchpad of the current output, like the workspaces,
+    /// the outputs share the scratchpad: a scratchpad window of another
+    /// output is moved to the current output when it has none.
+    fn toggle_scratchpad(&mut self) {
+        if self.get_current().get_scratchpad_windows().is_empty() {
+            if let Some(window) = self.get_scratchpad_windows().first().cloned() {
+                let index = self.get_window_output_index(window);
+                // unwrap() is used because the window is managed by the
+                // output with this index and not by the current one, the
+                // geometry is centered on the current output when it is shown
+                let window_with_info = self.output_wms[index].get_window_info(window).unwrap();
+                self.output_wms[index].remove_window(window).unwrap();
+                let current = self.get_current_mut();
+                current.add_window(window_with_info).unwrap();
+                current.move_to_scratchpad(window).unwrap();
+            }
+        }
+        self.get_current_mut().toggle_scratchpad()
+    }
+}
+
+impl<WM: WindowManager> UrgencySupport for MultiOutputWM<WM> {
+    /// returns the urgent windows of all the outputs, without the ones
+    /// that were removed and the focused window.
+    fn get_urgent_windows(&self) -> Vec<Window> {
+        let focused_window = self.get_focused_window();
+        self.urgent_windows
+            .iter()
+            .cloned()
+            .filter(|w| self.is_managed(*w) && Some(*w) != focused_window)
+            .collect()
+    }
+
+    /// marks the window as urgent or not, the window can be on any
+    /// output.
+    fn set_urgent(&mut self, window: Window, urgent: bool) -> Result<(), Self::Error> {
+        // returns the appropriate error when the window is not managed
+        try!(self.get_window_info(window));
+        self.urgent_windows = self.get_urgent_windows();
+        self.urgent_windows.retain(|w| *w != window);
+        if urgent && self.get_focused_window() != Some(window) {
+            self.urgent_windows.insert(0, window);
+        }
+        Ok(())
+    }
+}
+
+impl<WM: StickySupport> StickySupport for MultiOutputWM<WM> {
+    /// returns the sticky windows of all the outputs.
+    fn get_sticky_windows(&self) -> Vec<Window> {
+        let mut temp_windows = Vec::new();
+        for wm in self.output_wms.iter() {
+            temp_windows.extend(wm.get_sticky_windows());
+        }
+        temp_windows
+    }
+
+    /// toggles the stickiness in the output that manages the window, the
+    /// window is sticky on the workspaces of that output.
+    fn toggle_sticky(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.get_window_output_mut(window)
+            .toggle_sticky(window)
+            .map_err(MultiOutputWMError::OutputError)
+    }
+}
+
+impl<WM: FocusHistorySupport> FocusHistorySupport for MultiOutputWM<WM> {
+    /// returns the focus history of the current output.
+    fn get_focus_history(&self) -> Vec<Window> {
+        self.get_current().get_focus_history()
+    }
+}
+
+impl<WM, W> MultiWorkspaceSupport<W> for MultiOutputWM<WM>
+    where WM: MultiWorkspaceSupport<W>,
+          W: WindowManager
+{
+    /// Return the current workspace index of the current output.
+    fn get_current_workspace_index(&self) -> WorkspaceIndex {
+        self.get_current().get_current_workspace_index()
+    }
+
+    /// Get an immutable borrow of the workspace of the current output.
+    fn get_workspace(&self, index: WorkspaceIndex) -> Result<&W, Self::Error> {
+        self.get_current()
+            .get_workspace(index)
+            .map_err(MultiOutputWMError::OutputError)
+    }
+
+    /// Get a mutable borrow of the workspace of the current output.
+    fn get_workspace_mut(&mut self, index: WorkspaceIndex) -> Result<&mut W, Self::Error> {
+        self.get_current_mut()
+            .get_workspace_mut(index)
+            .map_err(MultiOutputWMError::OutputError)
+    }
+
+    /// Switch the workspace of the current output, the other outputs keep
+    /// their workspace.
+    fn switch_workspace(&mut self, index: WorkspaceIndex) -> Result<(), Self::Error> {
+        self.get_current_mut()
+            .switch_workspace(index)
+            .map_err(MultiOutputWMError::OutputError)
+    }
+}
+
+impl<WM: GapSupport> MultiOutputSupport<WM> for MultiOutputWM<WM> {
+    /// Return the geometries of the outputs.
+    fn get_outputs(&self) -> Vec<Geometry> {
+        self.outputs.clone()
+    }
+
+    /// Replace the outputs.
+    ///
+    /// New outputs get a new window manager with the gap that all the
+    /// outputs share, see *update_outputs*.
+    fn set_outputs(&mut self, outputs: Vec<Geometry>) {
+        let gap = self.get_gap();
+        self.update_outputs(outputs, |screen| {
+            let mut wm = WM::new(screen);
+            wm.set_gap(gap);
+            wm
+        })
+    }
+
+    /// Return the current output index.
+    fn get_current_output_index(&self) -> OutputIndex {
+        self.current_output
+    }
+
+    /// Get an immutable borrow of the window manager of the output at the
+    /// given index.
+    fn get_output(&self, index: OutputIndex) -> Result<&WM, Self::Error> {
+        match self.output_wms.get(index) {
+            None => Err(MultiOutputWMError::UnknownOutput(index)),
+            Some(wm) => Ok(wm),
+        }
+    }
+
+    /// Get a mutable borrow of the window manager of the output at the given
+    /// index.
+    fn get_output_mut(&mut self, index: OutputIndex) -> Result<&mut WM, Self::Error> {
+        match self.output_wms.get_mut(index) {
+            None => Err(MultiOutputWMError::UnknownOutput(index)),
+            Some(wm) => Ok(wm),
+        }
+    }
+
+    /// Make the output at the given index the current output.
+    fn focus_output(&mut self, index: OutputIndex) -> Result<(), Self::Error> {
+        if index >= self.outputs.len() {
+            Err(MultiOutputWMError::UnknownOutput(index))
+        } else {
+            self.current_output = index;
+            Ok(())
+        }
+    }
+}
+
+
+impl<WM: InvariantCheck> InvariantCheck for MultiOutputWM<WM> {
+    /// checks the invariants of the window manager, which also catches
+    /// windows that are on more than one output, and of the window manager
+    /// of every output.
+    fn check_invariants(&self) -> Vec<String> {
+        let mut violations = check_window_manager(self);
+        for (index, wm) in self.output_wms.iter().enumerate() {
+            let prefix = format!("output {}", index);
+            violations.extend(prefix_violations(&prefix, wm.check_invariants()));
+        }
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    // We have to import `WMName` from the super module.
+    use super::WMName;
+
+    // We have to repeat the imports we did in the super module.
+    use cplwm_api::wm::WindowManager;
+    use cplwm_api::wm::FloatSupport;
+    use cplwm_api::wm::FullscreenSupport;
+    use cplwm_api::wm::GapSupport;
+    use cplwm_api::wm::MultiWorkspaceSupport;
+    use cplwm_api::wm::MultiOutputSupport;
+    use cplwm_api::types::*;
+
+    // The generic tests of the traits that this window manager implements.
+    conformance_tests!(WMName: window_manager, tiling, floating, minimising, fullscreen);
+
+    // We define a static variable for the screen we will use in the tests.
+    static SCREEN: Screen = Screen {
+        width: 1600,
+        height: 600,
+    };
+
+    static SCREEN2: Screen = Screen {
+        width: 1000,
+        height: 800,
+    };
+
+    // We define a static variable for the geometry of a fullscreen window.
+    static SCREEN_GEOM: Geometry = Geometry {
+        x: 0,
+        y: 0,
+        width: 1600,
+        height: 600,
+    };
+
+    // The left and the right output of SCREEN.
+    static LEFT_OUTPUT: Geometry = Geometry {
+        x: 0,
+        y: 0,
+        width: 800,
+        height: 600,
+    };
+
+    static RIGHT_OUTPUT: Geometry = Geometry {
+        x: 800,
+        y: 0,
+        width: 800,
+        height: 600,
+    };
+
+    // We define a static variable for some random geometry that we will use
+    // when adding windows to a window manager.
+    static SOME_GEOM: Geometry = Geometry {
+        x: 10,
+        y: 10,
+        width: 100,
+        height: 100,
+    };
+
+    #[test]
+    fn test_single_output() {
+        let mut wm = WMName::new(SCREEN);
+
+        // Initially a single output covers the whole screen
+        assert_eq!(vec![SCREEN_GEOM], wm.get_outputs());
+        assert_eq!(0, wm.get_current_output_index());
+
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        assert_eq!(vec![(1, SCREEN_GEOM)], wm.get_window_layout().windows);
+        assert_eq!(Some(1), wm.get_window_layout().focused_window);
+
+        // It keeps covering the whole screen when the screen is resized
+        wm.resize_screen(SCREEN2);
+        assert_eq!(SCREEN2, wm.get_screen());
+        assert_eq!(vec![SCREEN2.to_geometry()], wm.get_outputs());
+        assert_eq!(vec![(1, SCREEN2.to_geometry())], wm.get_window_layout().windows);
+
+        // An empty list of outputs is ignored
+        wm.set_outputs(Vec::new());
+        assert_eq!(vec![SCREEN2.to_geometry()], wm.get_outputs());
+
+        // Only valid indices are accepted
+        assert!(wm.focus_output(1).is_err());
+        assert!(wm.get_output(1).is_err());
+        assert!(wm.get_output_mut(1).is_err());
+    }
+
+    #[test]
+    fn test_two_outputs() {
+        let mut wm = WMName::new(SCREEN);
+        wm.set_outputs(vec![LEFT_OUTPUT, RIGHT_OUTPUT]);
+        assert_eq!(vec![LEFT_OUTPUT, RIGHT_OUTPUT], wm.get_outputs());
+
+        // Every output has its own layout area
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.focus_output(1).unwrap();
+        assert_eq!(None, wm.get_focused_window());
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+        let wl = wm.get_window_layout();
+        assert_eq!(Some(3), wl.focused_window);
+        assert_eq!(vec![(1, LEFT_OUTPUT),
+                        (2,
+                         Geometry {
+                            x: 800,
+                            y: 0,
+                            width: 400,
+                            height: 600,
+                        }),
+                        (3,
+                         Geometry {
+                            x: 1200,
+                            y: 0,
+                            width: 400,
+                            height: 600,
+                        })],
+                   wl.windows);
+        assert_eq!(vec![1], wm.get_output(0).unwrap().get_windows());
+        assert_eq!(vec![2, 3], wm.get_output(1).unwrap().get_windows());
+
+        // Every output has its own focus
+        wm.focus_window(Some(1)).unwrap();
+        assert_eq!(0, wm.get_current_output_index());
+        wm.focus_output(1).unwrap();
+        assert_eq!(Some(3), wm.get_focused_window());
+
+        // The fullscreen window takes its whole output
+        wm.toggle_fullscreen(2).unwrap();
+        assert_eq!(vec![(1, LEFT_OUTPUT), (2, RIGHT_OUTPUT)],
+                   wm.get_window_layout().windows);
+        assert_eq!(RIGHT_OUTPUT, wm.get_window_info(2).unwrap().geometry);
+        wm.toggle_fullscreen(2).unwrap();
+
+        // The gap is set in every output
+        wm.set_gap(5);
+        assert_eq!(5, wm.get_output(0).unwrap().get_gap());
+
+        // Every output has its own workspaces
+        wm.switch_workspace(1).unwrap();
+        assert_eq!(vec![(1, Geometry {
+                            x: 5,
+                            y: 5,
+                            width: 790,
+                            height: 590,
+                        })],
+                   wm.get_window_layout().windows);
+        assert_eq!(0, wm.get_output(0).unwrap().get_current_workspace_index());
+    }
+
+    #[test]
+    fn test_floating_windows_on_outputs() {
+        let mut wm = WMName::new(SCREEN);
+        wm.set_outputs(vec![LEFT_OUTPUT, RIGHT_OUTPUT]);
+        wm.focus_output(1).unwrap();
+
+        // A window that is on the current output keeps its position
+        let right_geom = Geometry {
+            x: 900,
+            y: 100,
+            width: 100,
+            height: 100,
+        };
+        wm.add_window(WindowWithInfo::new_float(1, right_geom)).unwrap();
+        assert_eq!(right_geom, wm.get_window_info(1).unwrap().geometry);
+        assert_eq!(Geometry {
+                       x: 100,
+                       y: 100,
+                       width: 100,
+                       height: 100,
+                   },
+                   wm.get_output(1).unwrap().get_window_info(1).unwrap().geometry);
+
+        // A window that is on another output is centered on the current one
+        wm.add_window(WindowWithInfo::new_float(2, SOME_GEOM)).unwrap();
+        assert_eq!(Geometry {
+                       x: 1150,
+                       y: 250,
+                       width: 100,
+                       height: 100,
+                   },
+                   wm.get_window_info(2).unwrap().geometry);
+
+        // Moving a window uses geometries relative to the screen
+        wm.set_window_geometry(1, SOME_GEOM).unwrap();
+        assert_eq!(SOME_GEOM, wm.get_window_info(1).unwrap().geometry);
+        assert_eq!(vec![1, 2], wm.get_output(1).unwrap().get_floating_windows());
+    }
+
+    #[test]
+    fn test_removing_outputs() {
+        let mut wm = WMName::new(SCREEN);
+        wm.set_outputs(vec![LEFT_OUTPUT, RIGHT_OUTPUT]);
+
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.focus_output(1).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_float(3,
+                                                Geometry {
+                                                    x: 900,
+                                                    y: 100,
+                                                    width: 100,
+                                                    height: 100,
+                                                }))
+            .unwrap();
+
+        // The windows of the removed output move to the current output
+        wm.set_outputs(vec![SCREEN_GEOM]);
+        assert_eq!(0, wm.get_current_output_index());
+        assert_eq!(vec![1, 2, 3], wm.get_windows());
+        assert_eq!(vec![1, 2, 3], wm.get_output(0).unwrap().get_windows());
+        assert_eq!(Geometry {
+                       x: 900,
+                       y: 100,
+                       width: 100,
+                       height: 100,
+                   },
+                   wm.get_window_info(3).unwrap().geometry);
+        assert_eq!(SCREEN, wm.get_output(0).unwrap().get_screen());
+
+        // New outputs get an empty window manager
+        wm.set_outputs(vec![LEFT_OUTPUT, RIGHT_OUTPUT]);
+        assert!(wm.get_output(1).unwrap().get_windows().is_empty());
+        assert_eq!(Screen {
+                       width: 800,
+                       height: 600,
+                   },
+                   wm.get_output(0).unwrap().get_screen());
+    }
+
+    #[test]
+    fn test_new_outputs_share_the_gap() {
+        let mut wm = WMName::new(SCREEN);
+        wm.set_gap(5);
+
+        // A new output gets the gap of the other outputs
+        wm.set_outputs(vec![LEFT_OUTPUT, RIGHT_OUTPUT]);
+        assert_eq!(5, wm.get_output(1).unwrap().get_gap());
+        wm.focus_output(1).unwrap();
+        assert_eq!(5, wm.get_gap());
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        assert_eq!(vec![(1,
+                         Geometry {
+                            x: 805,
+                            y: 5,
+                            width: 790,
+                            height: 590,
+                        })],
+                   wm.get_window_layout().windows);
+    }
+}
@@ -0,0 +1,879 @@
+//! Manual Tiling Tree
+//!
+//! An alternative to the flat list of windows of the other window managers:
+//! like in i3, the tiled windows are the leaves of a tree of containers.
+//! Every container splits its space horizontally (its children are placed
+//! side by side) or vertically (its children are placed on top of each
+//! other). The user picks the split direction for the next window and can
+//! move windows between containers.
+//!
+//! # Status
+//!
+//! COMPLETED: YES
+//!
+//! COMMENTS:
+//!
+//! ## General approach
+//!
+//! The tree is a *Container* with a vector of *Node*s, a node is either a
+//! window or another container. The root container always exists, even when
+//! there are no tiled windows. The geometries of the tiled windows are not
+//! stored, they are calculated from the tree: a container splits its
+//! geometry evenly among its children, using the *split_evenly* function of
+//! the different tiling layouts.
+//!
+//! A new tiled window is placed right after the focused tiled window, in the
+//! same container. Calling *split* on a focused tiled window wraps it in a
+//! new container with the given split direction, like `split h` and `split
+//! v` in i3, so the next window will share the space of the focused window
+//! in that direction. When the focused window is the only child of its
+//! container, the direction of the container is changed instead of adding
+//! another level to the tree.
+//!
+//! When a window is removed or moved, the containers that become empty are
+//! removed as well, except for the root container. A container with a single
+//! child is kept, as i3 does, it disappears together with its last window.
+//!
+//! The *WindowWithInfo* of every window, tiled or floating, is kept in the
+//! order in which the windows were added, so the original geometry can be
+//! restored when a tiled window starts to float.
+//!
+//! The master window is the first tiled window of the tree, *swap_windows*
+//! swaps the focused window with the previous or next tiled window in the
+//! order of the tree (depth-first, from left to right). Both swap the
+//! windows in the leaves of the tree, so the containers stay the same.
+//!
+//! This window manager only implements *WindowManager*, *TilingSupport* and
+//! *FloatSupport*, so it can't be used with the X11 backend yet, which also
+//! requires fullscreen windows, minimising and window properties.
+
+use std::error;
+use std::fmt;
+
+use cplwm_api::types::{Direction, FloatOrTile, Geometry, PrevOrNext, Screen, Window,
+                       WindowLayout, WindowWithInfo};
+use cplwm_api::wm::{FloatSupport, InvariantCheck, TilingSupport, WindowManager};
+
+use h_different_tiling_layout::split_evenly;
+use n_invariants::{check_floating, check_tiling, check_window_manager};
+
+/// Window manager aliase.
+pub type WMName = TreeWM;
+
+/// The direction in which a container splits its space among its children.
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Split {
+    /// The children are placed side by side, from left to right.
+    Horizontal,
+    /// The children are placed on top of each other, from top to bottom.
+    Vertical,
+}
+
+/// A node of the tree: a tiled window or a container of other nodes.
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone, PartialEq, Eq)]
+pub enum Node {
+    /// A tiled window, always a leaf.
+    Window(Window),
+    /// A container, see *Container*.
+    Container(Container),
+}
+
+/// A container that splits its space among its children.
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone, PartialEq, Eq)]
+pub struct Container {
+    /// How the space is split among the children.
+    pub split: Split,
+    /// The children, from left to right or from top to bottom.
+    pub children: Vec<Node>,
+}
+
+impl Container {
+    /// Creates an empty container with the given split direction.
+    pub fn new(split: Split) -> Container {
+        Container {
+            split: split,
+            children: Vec::new(),
+        }
+    }
+
+    /// Returns the windows in this container and its descendants, in
+    /// depth-first order.
+    pub fn get_windows(&self) -> Vec<Window> {
+        let mut windows = Vec::new();
+        for child in &self.children {
+            match *child {
+                Node::Window(window) => windows.push(window),
+                Node::Container(ref container) => windows.extend(container.get_windows()),
+            }
+        }
+        windows
+    }
+
+    /// Returns true if the given window is in this container or one of its
+    /// descendants.
+    pub fn contains(&self, window: Window) -> bool {
+        self.children.iter().any(|child| {
+            match *child {
+                Node::Window(w) => w == window,
+                Node::Container(ref container) => container.contains(window),
+            }
+        })
+    }
+
+    /// Returns the windows in this container and its descendants together
+    /// with their geometry, when this container is given the given geometry.
+    pub fn get_geometries(&self, geometry: Geometry) -> Vec<(Window, Geometry)> {
+        let mut geometries = Vec::new();
+        let length = match self.split {
+            Split::Horizontal => geometry.width,
+            Split::Vertical => geometry.height,
+        };
+        let splits = split_evenly(length, self.children.len() as u32);
+        for (child, &(offset, length)) in self.children.iter().zip(splits.iter()) {
+            let child_geometry = match self.split {
+                Split::Horizontal => {
+                    Geometry {
+                        x: geometry.x + offset,
+                        width: length,
+                        ..geometry
+                    }
+                }
+                Split::Vertical => {
+                    Geometry {
+                        y: geometry.y + offset,
+                        height: length,
+                        ..geometry
+                    }
+                }
+            };
+            match *child {
+                Node::Window(window) => geometries.push((window, child_geometry)),
+                Node::Container(ref container) => {
+                    geometries.extend(container.get_geometries(child_geometry))
+                }
+            }
+        }
+        geometries
+    }
+
+    /// Returns the container of which the given window is a direct child.
+    fn get_parent_mut(&mut self, window: Window) -> Option<&mut Container> {
+        if self.children.contains(&Node::Window(window)) {
+            return Some(self);
+        }
+        for child in &mut self.children {
+            if let Node::Container(ref mut container) = *child {
+                if let Some(parent) = container.get_parent_mut(window) {
+                    return Some(parent);
+                }
+            }
+        }
+        None
+    }
+
+    /// Removes the given window, the containers that become empty are
+    /// removed too. Returns true if the window was found.
+    fn remove_window(&mut self, window: Window) -> bool {
+        if let Some(index) = self.children.iter().position(|child| *child == Node::Window(window)) {
+            self.children.remove(index);
+            return true;
+        }
+        let mut removed = false;
+        for child in &mut self.children {
+            if let Node::Container(ref mut container) = *child {
+                if container.remove_window(window) {
+                    removed = true;
+                    break;
+                }
+            }
+        }
+        if removed {
+            self.children.retain(|child| {
+                match *child {
+                    Node::Container(ref container) => !container.children.is_empty(),
+                    Node::Window(_) => true,
+                }
+            });
+        }
+        removed
+    }
+
+    /// Inserts the given node right before or after the given window, in the
+    /// container of that window. Returns false if the window was not found.
+    fn insert_next_to(&mut self, window: Window, node: Node, after: bool) -> bool {
+        match self.get_parent_mut(window) {
+            None => false,
+            Some(parent) => {
+                // unwrap() is used because the parent contains the window
+                let index = parent.children
+                    .iter()
+                    .position(|child| *child == Node::Window(window))
+                    .unwrap();
+                let index = if after { index + 1 } else { index };
+                parent.children.insert(index, node);
+                true
+            }
+        }
+    }
+
+    /// Swaps the places of the two given windows in the tree.
+    fn swap_windows(&mut self, window1: Window, window2: Window) {
+        for child in &mut self.children {
+            match *child {
+                Node::Window(ref mut window) => {
+                    if *window == window1 {
+                        *window = window2;
+                    } else if *window == window2 {
+                        *window = window1;
+                    }
+                }
+                Node::Container(ref mut container) => container.swap_windows(window1, window2),
+            }
+        }
+    }
+}
+
+/// The TreeWM struct
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
+pub struct TreeWM {
+    /// The root of the tree of tiled windows.
+    pub root: Container,
+    /// The information of all windows, tiled or floating, in the order in
+    /// which they were added. The geometry of a tiled window is the one it
+    /// was added with, its actual geometry follows from the tree.
+    pub windows: Vec<WindowWithInfo>,
+    /// The size of the screen.
+    pub screen: Screen,
+    /// The focused window, if any.
+    pub focused_window: Option<Window>,
+}
+
+/// Supported functions
+impl TreeWM {
+    /// Returns the info of the given window.
+    fn get_info(&self, window: Window) -> Result<&WindowWithInfo, TreeWMError> {
+        self.windows
+            .iter()
+            .find(|info| info.window == window)
+            .ok_or(TreeWMError::UnknownWindow(window))
+    }
+
+    /// Returns the info of the given window, mutably.
+    fn get_info_mut(&mut self, window: Window) -> Result<&mut WindowWithInfo, TreeWMError> {
+        self.windows
+            .iter_mut()
+            .find(|info| info.window == window)
+            .ok_or(TreeWMError::UnknownWindow(window))
+    }
+
+    /// Returns the tiled windows with their geometries, in the order of the
+    /// tree.
+    fn get_tiled_geometries(&self) -> Vec<(Window, Geometry)> {
+        self.root.get_geometries(self.screen.to_geometry())
+    }
+
+    /// Returns all windows in the order used to cycle the focus: the tiled
+    /// windows in the order of the tree, then the floating ones.
+    fn get_cycle_order(&self) -> Vec<Window> {
+        let mut windows = self.root.get_windows();
+        windows.extend(self.get_floating_windows());
+        windows
+    }
+
+    /// Returns the focused window if it is tiled.
+    fn get_focused_tile(&self) -> Option<Window> {
+        match self.focused_window {
+            Some(window) if self.root.contains(window) => Some(window),
+            _ => None,
+        }
+    }
+
+    /// Checks that the given window is tiled, returns an error otherwise.
+    fn check_tiled(&self, window: Window) -> Result<(), TreeWMError> {
+        if self.root.contains(window) {
+            Ok(())
+        } else {
+            try!(self.get_info(window));
+            Err(TreeWMError::NoTiledWindow(window))
+        }
+    }
+
+    /// Adds the given window to the tree, right after the focused tiled
+    /// window, or at the end of the root container if no tiled window is
+    /// focused.
+    fn insert_tile(&mut self, window: Window) {
+        let inserted = match self.get_focused_tile() {
+            Some(focused) => self.root.insert_next_to(focused, Node::Window(window), true),
+            None => false,
+        };
+        if !inserted {
+            self.root.children.push(Node::Window(window));
+        }
+    }
+
+    /// Splits the space of the focused tiled window in the given direction,
+    /// the next window will be placed next to it in that direction.
+    ///
+    /// Does nothing when no tiled window is focused.
+    pub fn split(&mut self, split: Split) {
+        if let Some(focused) = self.get_focused_tile() {
+            // unwrap() is used because the focused window is in the tree
+            let parent = self.root.get_parent_mut(focused).unwrap();
+            if parent.children.len() == 1 {
+                parent.split = split;
+            } else if parent.split != split {
+                // unwrap() is used because the parent contains the window
+                let index = parent.children
+                    .iter()
+                    .position(|child| *child == Node::Window(focused))
+                    .unwrap();
+                let mut container = Container::new(split);
+                container.children.push(Node::Window(focused));
+                parent.children[index] = Node::Container(container);
+            }
+        }
+    }
+
+    /// Moves the given tiled window to the container of the other given
+    /// tiled window, right after it.
+    ///
+    /// Returns an error when one of the windows is not managed or not tiled.
+    pub fn move_to_window(&mut self, window: Window, target: Window) -> Result<(), TreeWMError> {
+        try!(self.check_tiled(window));
+        try!(self.check_tiled(target));
+        if window != target {
+            self.root.remove_window(window);
+            self.root.insert_next_to(target, Node::Window(window), true);
+        }
+        Ok(())
+    }
+
+    /// Moves the focused tiled window to the container of the tiled window
+    /// in the given direction, before that window when moving left or up,
+    /// after it when moving right or down.
+    ///
+    /// Does nothing when no tiled window is focused or when there is no
+    /// tiled window in that direction.
+    pub fn move_in_direction(&mut self, dir: Direction) {
+        if let Some(focused) = self.get_focused_tile() {
+            let layout = WindowLayout {
+                focused_window: Some(focused),
+                windows: self.get_tiled_geometries(),
+            };
+            if let Some(target) = layout.get_window_in_direction(focused, dir) {
+                let after = dir == Direction::Right || dir == Direction::Down;
+                self.root.remove_window(focused);
+                self.root.insert_next_to(target, Node::Window(focused), after);
+            }
+        }
+    }
+}
+
+/// The errors that this window manager can return.
+#[derive(Debug)]
+pub enum TreeWMError {
+    /// This window is not known by the window manager.
+    UnknownWindow(Window),
+    /// This window is already managed by the window manager.
+    ManagedWindow(Window),
+    /// This window is not a floating window.
+    NoFloatingWindow(Window),
+    /// This window is not a tiled window
+    NoTiledWindow(Window),
+}
+
+impl fmt::Display for TreeWMError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TreeWMError::UnknownWindow(ref window) => write!(f, "Unknown window: {}", window),
+            TreeWMError::ManagedWindow(ref window) => {
+                write!(f, "Window {} is already managed", window)
+            }
+            TreeWMError::NoFloatingWindow(ref window) => {
+                write!(f, "Window {} is not floating", window)
+            }
+            TreeWMError::NoTiledWindow(ref window) => write!(f, "Window {} is not tiled", window),
+        }
+    }
+}
+
+impl error::Error for TreeWMError {
+    fn description(&self) -> &'static str {
+        match *self {
+            TreeWMError::UnknownWindow(_) => "Unknown window",
+            TreeWMError::ManagedWindow(_) => "Window is already managed",
+            TreeWMError::NoFloatingWindow(_) => "Window is not floating",
+            TreeWMError::NoTiledWindow(_) => "Window is not tiled",
+        }
+    }
+}
+
+impl WindowManager for TreeWM {
+    type Error = TreeWMError;
+
+    /// The TreeWM constructor.
+    ///
+    /// The tree is an empty horizontal root container, there are no windows
+    /// and no window is focused.
+    fn new(screen: Screen) -> TreeWM {
+        TreeWM {
+            root: Container::new(Split::Horizontal),
+            windows: Vec::new(),
+            screen: screen,
+            focused_window: None,
+        }
+    }
+
+    fn get_windows(&self) -> Vec<Window> {
+        self.windows.iter().map(|info| info.window).collect()
+    }
+
+    fn get_focused_window(&self) -> Option<Window> {
+        self.focused_window
+    }
+
+    /// A tiled window is added to the tree after the focused tiled window,
+    /// the new window is focused.
+    fn add_window(&mut self, window_with_info: WindowWithInfo) -> Result<(), Self::Error> {
+        let window = window_with_info.window;
+        if self.is_managed(window) {
+            return Err(TreeWMError::ManagedWindow(window));
+        }
+        if window_with_info.float_or_tile == FloatOrTile::Tile {
+            self.insert_tile(window);
+        }
+        self.windows.push(window_with_info);
+        self.focused_window = Some(window);
+        Ok(())
+    }
+
+    /// The window is removed from the tree, together with the containers
+    /// that become empty.
+    fn remove_window(&mut self, window: Window) -> Result<(), Self::Error> {
+        try!(self.get_info(window));
+        self.windows.retain(|info| info.window != window);
+        self.root.remove_window(window);
+        if self.focused_window == Some(window) {
+            self.focused_window = None;
+        }
+        Ok(())
+    }
+
+    /// The tiled windows come first, in the order of the tree, then the
+    /// floating windows, the focused floating window on top.
+    fn get_window_layout(&self) -> WindowLayout {
+        let mut windows = self.get_tiled_geometries();
+        let mut focused_float = None;
+        for info in &self.windows {
+            if info.float_or_tile == FloatOrTile::Float {
+                if self.focused_window == Some(info.window) {
+                    focused_float = Some((info.window, info.geometry));
+                } else {
+                    windows.push((info.window, info.geometry));
+                }
+            }
+        }
+        windows.extend(focused_float);
+        WindowLayout {
+            focused_window: self.focused_window,
+            windows: windows,
+        }
+    }
+
+    fn focus_window(&mut self, window: Option<Window>) -> Result<(), Self::Error> {
+        if let Some(window) = window {
+            try!(self.get_info(window));
+        }
+        self.focused_window = window;
+        Ok(())
+    }
+
+    /// The focus cycles through the tiled windows in the order of the tree,
+    /// then through the floating windows.
+    fn cycle_focus(&mut self, dir: PrevOrNext) {
+        let windows = self.get_cycle_order();
+        if windows.is_empty() {
+            return;
+        }
+        let index = match self.focused_window.and_then(|w| windows.iter().position(|x| *x == w)) {
+            None => 0,
+            Some(index) => {
+                match dir {
+                    PrevOrNext::Prev => (index + windows.len() - 1) % windows.len(),
+                    PrevOrNext::Next => (index + 1) % windows.len(),
+                }
+            }
+        };
+        self.focused_window = Some(windows[index]);
+    }
+
+    fn get_window_info(&self, window: Window) -> Result<WindowWithInfo, Self::Error> {
+        let mut info = try!(self.get_info(window)).clone();
+        if let Some(&(_, geometry)) = self.get_tiled_geometries()
+            .iter()
+            .find(|&&(w, _)| w == window) {
+            info.geometry = geometry;
+        }
+        Ok(info)
+    }
+
+    fn get_screen(&self) -> Screen {
+        self.screen
+    }
+
+    fn resize_screen(&mut self, screen: Screen) {
+        self.screen = screen;
+    }
+}
+
+impl TilingSupport for TreeWM {
+    /// The master window is the first tiled window in the order of the tree.
+    fn get_master_window(&self) -> Option<Window> {
+        self.root.get_windows().first().cloned()
+    }
+
+    fn swap_with_master(&mut self, window: Window) -> Result<(), Self::Error> {
+        try!(self.check_tiled(window));
+        // unwrap() is used because there is at least one tiled window
+        let master = self.get_master_window().unwrap();
+        self.root.swap_windows(window, master);
+        self.focused_window = Some(window);
+        Ok(())
+    }
+
+    /// The focused window is swapped with the previous or next tiled window
+    /// in the order of the tree.
+    fn swap_windows(&mut self, dir: PrevOrNext) {
+        if let Some(focused) = self.get_focused_tile() {
+            let windows = self.root.get_windows();
+            // unwrap() is used because the focused window is in the tree
+            let index = windows.iter().position(|w| *w == focused).unwrap();
+            let other = match dir {
+                PrevOrNext::Prev => windows[(index + windows.len() - 1) % windows.len()],
+                PrevOrNext::Next => windows[(index + 1) % windows.len()],
+            };
+            self.root.swap_windows(focused, other);
+        }
+    }
+}
+
+impl FloatSupport for TreeWM {
+    fn get_floating_windows(&self) -> Vec<Window> {
+        self.windows
+            .iter()
+            .filter(|info| info.float_or_tile == FloatOrTile::Float)
+            .map(|info| info.window)
+            .collect()
+    }
+
+    /// A floating window that sinks is added to the tree like a new window.
+    fn toggle_floating(&mut self, window: Window) -> Result<(), Self::Error> {
+        let float_or_tile = try!(self.get_info(window)).float_or_tile;
+        match float_or_tile {
+            FloatOrTile::Tile => {
+                self.root.remove_window(window);
+                try!(self.get_info_mut(window)).float_or_tile = FloatOrTile::Float;
+            }
+            FloatOrTile::Float => {
+                self.insert_tile(window);
+                try!(self.get_info_mut(window)).float_or_tile = FloatOrTile::Tile;
+            }
+        }
+        Ok(())
+    }
+
+    fn set_window_geometry(&mut self,
+                           window: Window,
+                           new_geometry: Geometry)
+                           -> Result<(), Self::Error> {
+        let info = try!(self.get_info_mut(window));
+        if info.float_or_tile != FloatOrTile::Float {
+            return Err(TreeWMError::NoFloatingWindow(window));
+        }
+        info.geometry = new_geometry;
+        Ok(())
+    }
+}
+
+impl InvariantCheck for TreeWM {
+    /// checks the invariants of the window manager, the tiling and the
+    /// floating windows.
+    fn check_invariants(&self) -> Vec<String> {
+        let mut violations = check_window_manager(self);
+        violations.extend(check_tiling(self));
+        violations.extend(check_floating(self));
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{Container, Node, Split, TreeWM};
+    use cplwm_api::wm::{FloatSupport, TilingSupport, WindowManager};
+    use cplwm_api::types::*;
+    use rustc_serialize::json;
+
+    // The generic tests of the traits that this window manager implements.
+    conformance_tests!(TreeWM: window_manager, tiling, floating);
+
+    static SCREEN: Screen = Screen {
+        width: 800,
+        height: 600,
+    };
+
+    static SCREEN_GEOM: Geometry = Geometry {
+        x: 0,
+        y: 0,
+        width: 800,
+        height: 600,
+    };
+
+    static SOME_GEOM: Geometry = Geometry {
+        x: 10,
+        y: 10,
+        width: 100,
+        height: 100,
+    };
+
+    /// Creates a geometry, to keep the tests short.
+    fn geom(x: i32, y: i32, width: u32, height: u32) -> Geometry {
+        Geometry {
+            x: x,
+            y: y,
+            width: width,
+            height: height,
+        }
+    }
+
+    #[test]
+    fn test_adding_and_removing_windows() {
+        let mut wm = TreeWM::new(SCREEN);
+        assert_eq!(WindowLayout::new(), wm.get_window_layout());
+
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        assert_eq!(vec![(1, SCREEN_GEOM)], wm.get_window_layout().windows);
+        assert!(wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).is_err());
+
+        // The root container splits horizontally
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        assert_eq!(Some(2), wm.get_focused_window());
+        assert_eq!(vec![(1, geom(0, 0, 400, 600)), (2, geom(400, 0, 400, 600))],
+                   wm.get_window_layout().windows);
+
+        // A new window is placed after the focused one
+        wm.focus_window(Some(1)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+        assert_eq!(vec![1, 3, 2], wm.root.get_windows());
+        assert_eq!(vec![1, 2, 3], wm.get_windows());
+
+        wm.remove_window(3).unwrap();
+        assert_eq!(None, wm.get_focused_window());
+        assert_eq!(vec![(1, geom(0, 0, 400, 600)), (2, geom(400, 0, 400, 600))],
+                   wm.get_window_layout().windows);
+        assert!(wm.remove_window(3).is_err());
+
+        wm.remove_window(1).unwrap();
+        wm.remove_window(2).unwrap();
+        assert_eq!(WindowLayout::new(), wm.get_window_layout());
+        assert!(wm.root.children.is_empty());
+    }
+
+    #[test]
+    fn test_split() {
+        let mut wm = TreeWM::new(SCREEN);
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+
+        // Window 3 shares the space of window 2 vertically
+        wm.split(Split::Vertical);
+        wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+        assert_eq!(vec![(1, geom(0, 0, 400, 600)),
+                        (2, geom(400, 0, 400, 300)),
+                        (3, geom(400, 300, 400, 300))],
+                   wm.get_window_layout().windows);
+
+        // Splitting in the direction of the container does not nest it
+        wm.split(Split::Vertical);
+        wm.add_window(WindowWithInfo::new_tiled(4, SOME_GEOM)).unwrap();
+        assert_eq!(vec![(1, geom(0, 0, 400, 600)),
+                        (2, geom(400, 0, 400, 200)),
+                        (3, geom(400, 200, 400, 200)),
+                        (4, geom(400, 400, 400, 200))],
+                   wm.get_window_layout().windows);
+
+        // Window 5 shares the space of window 4 horizontally
+        wm.split(Split::Horizontal);
+        wm.add_window(WindowWithInfo::new_tiled(5, SOME_GEOM)).unwrap();
+        assert_eq!(geom(400, 400, 200, 200), wm.get_window_info(4).unwrap().geometry);
+        assert_eq!(geom(600, 400, 200, 200), wm.get_window_info(5).unwrap().geometry);
+
+        // The container of a single window changes its direction
+        let mut wm = TreeWM::new(SCREEN);
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.split(Split::Vertical);
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        assert_eq!(vec![Node::Window(1), Node::Window(2)], wm.root.children);
+        assert_eq!(Split::Vertical, wm.root.split);
+    }
+
+    #[test]
+    fn test_collapsing_empty_containers() {
+        let mut wm = TreeWM::new(SCREEN);
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        wm.split(Split::Vertical);
+        wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+        wm.split(Split::Horizontal);
+        wm.add_window(WindowWithInfo::new_tiled(4, SOME_GEOM)).unwrap();
+
+        // The innermost container keeps window 3
+        wm.remove_window(4).unwrap();
+        assert_eq!(vec![1, 2, 3], wm.root.get_windows());
+        assert_eq!(geom(400, 300, 400, 300), wm.get_window_info(3).unwrap().geometry);
+
+        // Removing window 3 removes the now empty innermost container
+        wm.remove_window(3).unwrap();
+        let mut expected = Container::new(Split::Vertical);
+        expected.children.push(Node::Window(2));
+        assert_eq!(vec![Node::Window(1), Node::Container(expected)],
+                   wm.root.children);
+
+        // Removing window 2 removes the vertical container too
+        wm.remove_window(2).unwrap();
+        assert_eq!(vec![Node::Window(1)], wm.root.children);
+        assert_eq!(vec![(1, SCREEN_GEOM)], wm.get_window_layout().windows);
+    }
+
+    #[test]
+    fn test_moving_windows() {
+        let mut wm = TreeWM::new(SCREEN);
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        wm.split(Split::Vertical);
+        wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+
+        // Move window 1 into the vertical container, after window 3
+        wm.move_to_window(1, 3).unwrap();
+        assert_eq!(Split::Horizontal, wm.root.split);
+        assert_eq!(vec![(2, geom(0, 0, 800, 200)),
+                        (3, geom(0, 200, 800, 200)),
+                        (1, geom(0, 400, 800, 200))],
+                   wm.get_window_layout().windows);
+        assert!(wm.move_to_window(1, 4).is_err());
+
+        // Move window 3 up, before window 2
+        wm.focus_window(Some(3)).unwrap();
+        wm.move_in_direction(Direction::Up);
+        assert_eq!(vec![3, 2, 1], wm.root.get_windows());
+        // There is no window above window 3
+        wm.move_in_direction(Direction::Up);
+        assert_eq!(vec![3, 2, 1], wm.root.get_windows());
+
+        // Floating windows can't be moved in the tree
+        wm.add_window(WindowWithInfo::new_float(4, SOME_GEOM)).unwrap();
+        assert!(wm.move_to_window(4, 1).is_err());
+        assert!(wm.move_to_window(1, 4).is_err());
+    }
+
+    #[test]
+    fn test_tiling_support() {
+        let mut wm = TreeWM::new(SCREEN);
+        assert_eq!(None, wm.get_master_window());
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        wm.split(Split::Vertical);
+        wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+        assert_eq!(Some(1), wm.get_master_window());
+
+        // Swapping keeps the containers
+        wm.swap_with_master(3).unwrap();
+        assert_eq!(Some(3), wm.get_master_window());
+        assert_eq!(Some(3), wm.get_focused_window());
+        assert_eq!(vec![(3, geom(0, 0, 400, 600)),
+                        (2, geom(400, 0, 400, 300)),
+                        (1, geom(400, 300, 400, 300))],
+                   wm.get_window_layout().windows);
+
+        let layout = wm.get_window_layout();
+        wm.swap_windows(PrevOrNext::Next);
+        assert_eq!(vec![2, 3, 1], wm.root.get_windows());
+        assert_eq!(Some(3), wm.get_focused_window());
+        wm.swap_windows(PrevOrNext::Prev);
+        assert_eq!(layout, wm.get_window_layout());
+
+        // Wrap around
+        wm.swap_windows(PrevOrNext::Prev);
+        assert_eq!(vec![1, 2, 3], wm.root.get_windows());
+
+        wm.add_window(WindowWithInfo::new_float(4, SOME_GEOM)).unwrap();
+        assert!(wm.swap_with_master(4).is_err());
+        assert!(wm.swap_with_master(5).is_err());
+    }
+
+    #[test]
+    fn test_cycle_focus() {
+        let mut wm = TreeWM::new(SCREEN);
+        wm.cycle_focus(PrevOrNext::Next);
+        assert_eq!(None, wm.get_focused_window());
+
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_float(2, SOME_GEOM)).unwrap();
+        wm.focus_window(Some(1)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+
+        // The tiled windows come first, in the order of the tree
+        wm.cycle_focus(PrevOrNext::Next);
+        assert_eq!(Some(2), wm.get_focused_window());
+        wm.cycle_focus(PrevOrNext::Next);
+        assert_eq!(Some(1), wm.get_focused_window());
+        wm.cycle_focus(PrevOrNext::Prev);
+        assert_eq!(Some(2), wm.get_focused_window());
+
+        assert!(wm.focus_window(Some(4)).is_err());
+        wm.focus_window(None).unwrap();
+        assert_eq!(None, wm.get_focused_window());
+    }
+
+    #[test]
+    fn test_float_support() {
+        let mut wm = TreeWM::new(SCREEN);
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_float(3, SOME_GEOM)).unwrap();
+        assert_eq!(vec![3], wm.get_floating_windows());
+        assert!(wm.set_window_geometry(1, SCREEN_GEOM).is_err());
+
+        // The original geometry is restored, the focused float is on top
+        wm.toggle_floating(1).unwrap();
+        assert!(wm.is_floating(1));
+        wm.focus_window(Some(1)).unwrap();
+        assert_eq!(vec![(2, SCREEN_GEOM), (3, SOME_GEOM), (1, SOME_GEOM)],
+                   wm.get_window_layout().windows);
+
+        wm.set_window_geometry(1, geom(0, 0, 50, 50)).unwrap();
+        assert_eq!(geom(0, 0, 50, 50), wm.get_window_info(1).unwrap().geometry);
+
+        // A window that sinks is placed after the focused tiled window
+        wm.focus_window(Some(2)).unwrap();
+        wm.toggle_floating(3).unwrap();
+        assert!(!wm.is_floating(3));
+        assert_eq!(vec![2, 3], wm.root.get_windows());
+        assert!(wm.toggle_floating(4).is_err());
+    }
+
+    #[test]
+    fn test_serialisation() {
+        let mut wm = TreeWM::new(SCREEN);
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        wm.split(Split::Vertical);
+        wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_float(4, SOME_GEOM)).unwrap();
+
+        let encoded = json::encode(&wm).unwrap();
+        let decoded: TreeWM = json::decode(&encoded).unwrap();
+        assert_eq!(wm.root, decoded.root);
+        assert_eq!(wm.get_window_layout(), decoded.get_window_layout());
+    }
+}
@@ -0,0 +1,786 @@
+//! Extra: Undo and Redo
+//!
+//! A wrapper that remembers the state of the window manager before every
+//! operation of the user, so that the operation can be undone and redone.
+//! See the documentation of the [`UndoSupport`] trait for the precise
+//! requirements.
+//!
+//! [`UndoSupport`]: ../../cplwm_api/wm/trait.UndoSupport.html
+//!
+//! # Status
+//!
+//! COMPLETED: YES
+//!
+//! COMMENTS:
+//!
+//! ## General approach
+//!
+//! *UndoWM* wraps any window manager, like *GapWM* does, and keeps two
+//! stacks of copies of the wrapped window manager: the undo stack with the
+//! states before the operations and the redo stack with the states that were
+//! undone. *undo* pops a state from the undo stack, pushes the current state
+//! on the redo stack and replaces the wrapped window manager with the popped
+//! state, *redo* does the opposite. The undo stack is bounded by
+//! *max_history*, the oldest state is dropped when it is full.
+//!
+//! The operations that are remembered are the ones that change the layout:
+//! swapping windows, toggling floating, minimised, fullscreen, sticky and
+//! the scratchpad, and changing the gap, the layout and the master settings.
+//! A copy of the window manager is taken before the operation, when the
+//! operation did not change anything, the copy is dropped, so undo never
+//! seems to do nothing. Comparing the whole states would be as expensive as
+//! copying them, so *get_view* only compares what the user can see: the
+//! window layout and the information of the windows. The operations that
+//! change a setting, e.g. the gap, compare the setting as well, as it can
+//! change without changing the window layout, e.g. when there are no
+//! windows. Cycling the layout is always remembered for the same reason.
+//! Every remembered operation clears the redo stack.
+//!
+//! Adding, removing and focusing windows are not remembered, neither are
+//! switching workspaces or outputs, the properties and the urgency of the
+//! windows: they are caused by the applications or only change what the
+//! user looks at. The window managers that are borrowed with
+//! *get_workspace_mut* and *get_output_mut* are changed without the
+//! wrapper knowing it, so these changes can't be undone either. The outputs
+//! are set by the backend when the monitors change, as the old states have
+//! different outputs, *set_outputs* clears both stacks.
+//!
+//! ## Moving windows
+//!
+//! Dragging a floating window with the mouse calls *set_window_geometry*
+//! for every motion of the pointer. Only the first call that moves a window
+//! is remembered, the next calls for the same window are part of the same
+//! operation, until another window is moved, a window is focused or another
+//! operation is remembered. In this way, undo puts the window back where it
+//! was before the drag.
+//!
+//! ## Reconciling windows
+//!
+//! A state on one of the stacks can contain windows that were closed since
+//! and miss windows that were opened since. Before a state replaces the
+//! wrapped window manager, the closed windows are removed from it and the
+//! new windows are added to it with their current information, so undo and
+//! redo never bring back a dead window. The new windows end up on the
+//! current workspace of the state. The focus of the state is kept and the
+//! properties, urgency and screen are copied from the current window
+//! manager, as they can't be undone.
+
+// Add imports here
+use std::mem;
+
+use cplwm_api::types::{ColumnRatio, Direction, GapSize, Geometry, MasterCount, MasterPosition,
+                       MasterRatio, OutputIndex, PrevOrNext, Screen, TabBar, Window, WindowLayout,
+                       WindowProperties, WindowWithInfo, WorkspaceIndex};
+use cplwm_api::wm::WindowManager;
+use cplwm_api::wm::TilingSupport;
+use cplwm_api::wm::FloatSupport;
+use cplwm_api::wm::MinimiseSupport;
+use cplwm_api::wm::FullscreenSupport;
+use cplwm_api::wm::GapSupport;
+use cplwm_api::wm::LayoutSupport;
+use cplwm_api::wm::TabBarSupport;
+use cplwm_api::wm::ColumnRatioSupport;
+use cplwm_api::wm::MasterRatioSupport;
+use cplwm_api::wm::MultiMasterSupport;
+use cplwm_api::wm::MasterPositionSupport;
+use cplwm_api::wm::DirectionalSupport;
+use cplwm_api::wm::WindowPropertiesSupport;
+use cplwm_api::wm::ScratchpadSupport;
+use cplwm_api::wm::UrgencySupport;
+use cplwm_api::wm::StickySupport;
+use cplwm_api::wm::FocusHistorySupport;
+use cplwm_api::wm::UndoSupport;
+use cplwm_api::wm::MultiWorkspaceSupport;
+use cplwm_api::wm::MultiOutputSupport;
+use cplwm_api::wm::InvariantCheck;
+
+use i_multiple_outputs;
+
+/// Window manager aliase.
+pub type WMName = UndoWM<i_multiple_outputs::WMName>;
+
+/// The number of operations that can be undone by default.
+pub const DEFAULT_MAX_HISTORY: usize = 50;
+
+/// The UndoWM struct
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
+pub struct UndoWM<WM> {
+    /// The wrapped window manager.
+    pub wm: WM,
+    /// The states before the remembered operations, the most recent one
+    /// comes last.
+    pub undo_stack: Vec<WM>,
+    /// The states that were undone, the most recently undone one comes last.
+    pub redo_stack: Vec<WM>,
+    /// The maximum number of states on the undo stack.
+    pub max_history: usize,
+    /// The window that is being moved by *set_window_geometry*, its next
+    /// moves are part of the same operation.
+    pub moved_window: Option<Window>,
+}
+
+/// Supported functions
+impl<WM: WindowManager> UndoWM<WM> {
+    /// returns what the user can see of the given window manager: the
+    /// window layout and the information of every window, in the order of
+    /// *get_windows*.
+    fn get_view(wm: &WM) -> (WindowLayout, Vec<WindowWithInfo>) {
+        let windows = wm.get_windows()
+            .into_iter()
+            .filter_map(|window| wm.get_window_info(window).ok())
+            .collect();
+        (wm.get_window_layout(), windows)
+    }
+
+    /// pushes the state before an operation on the undo stack.
+    fn remember(&mut self, snapshot: WM) {
+        self.undo_stack.push(snapshot);
+        while self.undo_stack.len() > self.max_history {
+            self.undo_stack.remove(0);
+        }
+        self.redo_stack.clear();
+    }
+
+    /// runs the operation on the wrapped window manager and remembers the
+    /// state before it, unless the view and the given setting did not
+    /// change.
+    fn record_setting<K, S, T, F>(&mut self, setting: S, operation: F) -> T
+        where K: PartialEq,
+              S: Fn(&WM) -> K,
+              F: FnOnce(&mut WM) -> T
+    {
+        let snapshot = self.wm.clone();
+        let view = Self::get_view(&self.wm);
+        let value = setting(&self.wm);
+        let result = operation(&mut self.wm);
+        if Self::get_view(&self.wm) != view || setting(&self.wm) != value {
+            self.remember(snapshot);
+        }
+        self.moved_window = None;
+        result
+    }
+
+    /// runs the operation on the wrapped window manager and remembers the
+    /// state before it, unless the view did not change.
+    fn record<T, F>(&mut self, operation: F) -> T
+        where F: FnOnce(&mut WM) -> T
+    {
+        self.record_setting(|_| (), operation)
+    }
+}
+
+impl<WM: WindowPropertiesSupport + UrgencySupport> UndoWM<WM> {
+    /// makes the state contain the same windows as the wrapped window
+    /// manager, with their current properties and urgency.
+    fn reconcile(&self, state: &mut WM) {
+        for window in state.get_windows() {
+            if !self.wm.is_managed(window) {
+                // The window is managed by the state
+                let _ = state.remove_window(window);
+            }
+        }
+
+        let focused_window = state.get_focused_window();
+        for window in self.wm.get_windows() {
+            // unwrap() is used because the window is managed
+            let window_with_info = self.wm.get_window_info(window).unwrap();
+            if state.is_managed(window) {
+                let _ = state.set_window_properties(window, window_with_info.properties);
+            } else {
+                let _ = state.add_window(window_with_info);
+            }
+        }
+        // Adding a window focuses it, the focus of the state is restored
+        let _ = state.focus_window(focused_window);
+
+        for window in self.wm.get_windows() {
+            let _ = state.set_urgent(window, self.wm.is_urgent(window));
+        }
+
+        if state.get_screen() != self.wm.get_screen() {
+            state.resize_screen(self.wm.get_screen());
+        }
+    }
+}
+
+impl<WM: WindowManager> WindowManager for UndoWM<WM> {
+    /// The errors of the wrapped window manager are reused.
+    type Error = WM::Error;
+
+    /// The UndoWM constructor.
+    ///
+    /// The wrapped window manager is created with the given screen, there
+    /// is nothing to undo or redo.
+    fn new(screen: Screen) -> UndoWM<WM> {
+        UndoWM {
+            wm: WM::new(screen),
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            max_history: DEFAULT_MAX_HISTORY,
+            moved_window: None,
+        }
+    }
+
+    /// returns the layout of the wrapped window manager.
+    fn get_window_layout(&self) -> WindowLayout {
+        self.wm.get_window_layout()
+    }
+
+    /// checks the wrapped window manager.
+    fn is_managed(&self, window: Window) -> bool {
+        self.wm.is_managed(window)
+    }
+
+    /// returns the windows of the wrapped window manager.
+    fn get_windows(&self) -> Vec<Window> {
+        self.wm.get_windows()
+    }
+
+    /// returns the focused window of the wrapped window manager.
+    fn get_focused_window(&self) -> Option<Window> {
+        self.wm.get_focused_window()
+    }
+
+    /// adds the window to the wrapped window manager, this is not
+    /// remembered.
+    fn add_window(&mut self, window_with_info: WindowWithInfo) -> Result<(), Self::Error> {
+        self.wm.add_window(window_with_info)
+    }
+
+    /// removes the window from the wrapped window manager, this is not
+    /// remembered. The states on the stacks still contain the window, it is
+    /// removed from them when they are restored.
+    fn remove_window(&mut self, window: Window) -> Result<(), Self::Error> {
+        if self.moved_window == Some(window) {
+            self.moved_window = None;
+        }
+        self.wm.remove_window(window)
+    }
+
+    /// focuses the window in the wrapped window manager, this is not
+    /// remembered, but ends the move of a window.
+    fn focus_window(&mut self, window: Option<Window>) -> Result<(), Self::Error> {
+        self.moved_window = None;
+        self.wm.focus_window(window)
+    }
+
+    /// cycles the focus in the wrapped window manager, this is not
+    /// remembered.
+    fn cycle_focus(&mut self, dir: PrevOrNext) {
+        self.wm.cycle_focus(dir)
+    }
+
+    /// returns the information of the wrapped window manager.
+    fn get_window_info(&self, window: Window) -> Result<WindowWithInfo, Self::Error> {
+        self.wm.get_window_info(window)
+    }
+
+    /// returns the screen of the wrapped window manager.
+    fn get_screen(&self) -> Screen {
+        self.wm.get_screen()
+    }
+
+    /// resizes the wrapped window manager, the states on the stacks are
+    /// resized when they are restored.
+    fn resize_screen(&mut self, screen: Screen) {
+        self.wm.resize_screen(screen)
+    }
+}
+
+impl<WM: TilingSupport> TilingSupport for UndoWM<WM> {
+    /// returns the master window of the wrapped window manager.
+    fn get_master_window(&self) -> Option<Window> {
+        self.wm.get_master_window()
+    }
+
+    /// swaps the window with the master window and remembers it.
+    fn swap_with_master(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.record(|wm| wm.swap_with_master(window))
+    }
+
+    /// swaps the focused window and remembers it.
+    fn swap_windows(&mut self, dir: PrevOrNext) {
+        self.record(|wm| wm.swap_windows(dir))
+    }
+}
+
+impl<WM: FloatSupport> FloatSupport for UndoWM<WM> {
+    /// returns the floating windows of the wrapped window manager.
+    fn get_floating_windows(&self) -> Vec<Window> {
+        self.wm.get_floating_windows()
+    }
+
+    /// toggles the window floating and remembers it.
+    fn toggle_floating(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.record(|wm| wm.toggle_floating(window))
+    }
+
+    /// sets the geometry in the wrapped window manager. Only the first move
+    /// of a window is remembered, the following moves of the same window
+    /// are part of the same operation.
+    fn set_window_geometry(&mut self,
+                           window: Window,
+                           new_geometry: Geometry)
+                           -> Result<(), Self::Error> {
+        if self.moved_window == Some(window) {
+            return self.wm.set_window_geometry(window, new_geometry);
+        }
+        let snapshot = self.wm.clone();
+        let view = Self::get_view(&self.wm);
+        let result = self.wm.set_window_geometry(window, new_geometry);
+        if Self::get_view(&self.wm) != view {
+            self.remember(snapshot);
+            self.moved_window = Some(window);
+        }
+        result
+    }
+}
+
+impl<WM: MinimiseSupport> MinimiseSupport for UndoWM<WM> {
+    /// returns the minimised windows of the wrapped window manager.
+    fn get_minimised_windows(&self) -> Vec<Window> {
+        self.wm.get_minimised_windows()
+    }
+
+    /// checks the wrapped window manager.
+    fn is_minimised(&self, window: Window) -> bool {
+        self.wm.is_minimised(window)
+    }
+
+    /// (un)minimises the window and remembers it.
+    fn toggle_minimised(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.record_setting(|wm| wm.get_minimised_windows(),
+                            |wm| wm.toggle_minimised(window))
+    }
+}
+
+impl<WM: FullscreenSupport> FullscreenSupport for UndoWM<WM> {
+    /// returns the fullscreen window of the wrapped window manager.
+    fn get_fullscreen_window(&self) -> Option<Window> {
+        self.wm.get_fullscreen_window()
+    }
+
+    /// toggles the fullscreen of the window and remembers it.
+    fn toggle_fullscreen(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.record(|wm| wm.toggle_fullscreen(window))
+    }
+}
+
+impl<WM: GapSupport> GapSupport for UndoWM<WM> {
+    /// returns the gap of the wrapped window manager.
+    fn get_gap(&self) -> GapSize {
+        self.wm.get_gap()
+    }
+
+    /// sets the gap and remembers it.
+    fn set_gap(&mut self, gap: GapSize) {
+        self.record_setting(|wm| wm.get_gap(), |wm| wm.set_gap(gap))
+    }
+}
+
+impl<WM: LayoutSupport> LayoutSupport for UndoWM<WM> {
+    /// cycles the layout and remembers it, even when the window layout
+    /// stays the same, as another layout is active.
+    fn cycle_layout(&mut self, dir: PrevOrNext) {
+        let snapshot = self.wm.clone();
+        self.wm.cycle_layout(dir);
+        self.remember(snapshot);
+        self.moved_window = None;
+    }
+}
+
+impl<WM: TabBarSupport> TabBarSupport for UndoWM<WM> {
+    /// returns the tab bars of the wrapped window manager.
+    fn get_tab_bars(&self) -> Vec<TabBar> {
+        self.wm.get_tab_bars()
+    }
+}
+
+impl<WM: ColumnRatioSupport> ColumnRatioSupport for UndoWM<WM> {
+    /// returns the column ratio of the wrapped window manager.
+    fn get_column_ratio(&self) -> ColumnRatio {
+        self.wm.get_column_ratio()
+    }
+
+    /// sets the column ratio and remembers it.
+    fn set_column_ratio(&mut self, ratio: ColumnRatio) {
+        self.record_setting(|wm| wm.get_column_ratio(), |wm| wm.set_column_ratio(ratio))
+    }
+}
+
+impl<WM: MasterRatioSupport> MasterRatioSupport for UndoWM<WM> {
+    /// returns the master ratio of the wrapped window manager.
+    fn get_master_ratio(&self) -> MasterRatio {
+        self.wm.get_master_ratio()
+    }
+
+    /// sets the master ratio and remembers it.
+    fn set_master_ratio(&mut self, ratio: MasterRatio) {
+        self.record_setting(|wm| wm.get_master_ratio(), |wm| wm.set_master_ratio(ratio))
+    }
+}
+
+impl<WM: MultiMasterSupport> MultiMasterSupport for UndoWM<WM> {
+    /// returns the master windows of the wrapped window manager.
+    fn get_master_windows(&self) -> Vec<Window> {
+        self.wm.get_master_windows()
+    }
+
+    /// returns the master count of the wrapped window manager.
+    fn get_master_count(&self) -> MasterCount {
+        self.wm.get_master_count()
+    }
+
+    /// sets the master count and remembers it.
+    fn set_master_count(&mut self, count: MasterCount) {
+        self.record_setting(|wm| wm.get_master_count(), |wm| wm.set_master_count(count))
+    }
+}
+
+impl<WM: MasterPositionSupport> MasterPositionSupport for UndoWM<WM> {
+    /// returns the master position of the wrapped window manager.
+    fn get_master_position(&self) -> MasterPosition {
+        self.wm.get_master_position()
+    }
+
+    /// sets the master position and remembers it.
+    fn set_master_position(&mut self, position: Option<MasterPosition>) {
+        self.record_setting(|wm| wm.get_master_position(),
+                            |wm| wm.set_master_position(position))
+    }
+}
+
+impl<WM: DirectionalSupport> DirectionalSupport for UndoWM<WM> {
+    /// swaps the focused window in the direction and remembers it.
+    fn swap_direction(&mut self, dir: Direction) {
+        self.record(|wm| wm.swap_direction(dir))
+    }
+}
+
+impl<WM: WindowPropertiesSupport> WindowPropertiesSupport for UndoWM<WM> {
+    /// replaces the properties in the wrapped window manager, this is not
+    /// remembered.
+    fn set_window_properties(&mut self,
+                             window: Window,
+                             properties: WindowProperties)
+                             -> Result<(), Self::Error> {
+        self.wm.set_window_properties(window, properties)
+    }
+}
+
+impl<WM: ScratchpadSupport> ScratchpadSupport for UndoWM<WM> {
+    /// returns the scratchpad windows of the wrapped window manager.
+    fn get_scratchpad_windows(&self) -> Vec<Window> {
+        self.wm.get_scratchpad_windows()
+    }
+
+    /// moves the window to the scratchpad and remembers it.
+    fn move_to_scratchpad(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.record_setting(|wm| wm.get_scratchpad_windows(),
+                            |wm| wm.move_to_scratchpad(window))
+    }
+
+    /// toggles the scratchpad and remembers it.
+    fn toggle_scratchpad(&mut self) {
+        self.record_setting(|wm| wm.get_scratchpad_windows(), |wm| wm.toggle_scratchpad())
+    }
+}
+
+impl<WM: UrgencySupport> UrgencySupport for UndoWM<WM> {
+    /// returns the urgent windows of the wrapped window manager.
+    fn get_urgent_windows(&self) -> Vec<Window> {
+        self.wm.get_urgent_windows()
+    }
+
+    /// marks the window as urgent or not in the wrapped window manager,
+    /// this is not remembered.
+    fn set_urgent(&mut self, window: Window, urgent: bool) -> Result<(), Self::Error> {
+        self.wm.set_urgent(window, urgent)
+    }
+}
+
+impl<WM: StickySupport> StickySupport for UndoWM<WM> {
+    /// returns the sticky windows of the wrapped window manager.
+    fn get_sticky_windows(&self) -> Vec<Window> {
+        self.wm.get_sticky_windows()
+    }
+
+    /// toggles the stickiness of the window and remembers it.
+    fn toggle_sticky(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.record_setting(|wm| wm.get_sticky_windows(), |wm| wm.toggle_sticky(window))
+    }
+}
+
+impl<WM: FocusHistorySupport> FocusHistorySupport for UndoWM<WM> {
+    /// returns the focus history of the wrapped window manager.
+    fn get_focus_history(&self) -> Vec<Window> {
+        self.wm.get_focus_history()
+    }
+}
+
+impl<WM: WindowPropertiesSupport + UrgencySupport> UndoSupport for UndoWM<WM> {
+    /// replaces the wrapped window manager with the most recent state on
+    /// the undo stack, after reconciling the windows of that state.
+    fn undo(&mut self) {
+        if let Some(mut state) = self.undo_stack.pop() {
+            self.reconcile(&mut state);
+            let current = mem::replace(&mut self.wm, state);
+            self.redo_stack.push(current);
+            self.moved_window = None;
+        }
+    }
+
+    /// replaces the wrapped window manager with the most recent state on
+    /// the redo stack, after reconciling the windows of that state.
+    fn redo(&mut self) {
+        if let Some(mut state) = self.redo_stack.pop() {
+            self.reconcile(&mut state);
+            let current = mem::replace(&mut self.wm, state);
+            self.undo_stack.push(current);
+            self.moved_window = None;
+        }
+    }
+}
+
+impl<WM, W> MultiWorkspaceSupport<W> for UndoWM<WM>
+    where WM: MultiWorkspaceSupport<W>,
+          W: WindowManager
+{
+    /// Return the current workspace index of the wrapped window manager.
+    fn get_current_workspace_index(&self) -> WorkspaceIndex {
+        self.wm.get_current_workspace_index()
+    }
+
+    /// Get an immutable borrow of the workspace of the wrapped window
+    /// manager.
+    fn get_workspace(&self, index: WorkspaceIndex) -> Result<&W, Self::Error> {
+        self.wm.get_workspace(index)
+    }
+
+    /// Get a mutable borrow of the workspace of the wrapped window manager,
+    /// the changes to the workspace are not remembered.
+    fn get_workspace_mut(&mut self, index: WorkspaceIndex) -> Result<&mut W, Self::Error> {
+        self.wm.get_workspace_mut(index)
+    }
+
+    /// Switch the workspace of the wrapped window manager, this is not
+    /// remembered.
+    fn switch_workspace(&mut self, index: WorkspaceIndex) -> Result<(), Self::Error> {
+        self.wm.switch_workspace(index)
+    }
+}
+
+impl<WM, O> MultiOutputSupport<O> for UndoWM<WM>
+    where WM: MultiOutputSupport<O>,
+          O: WindowManager
+{
+    /// Return the geometries of the outputs of the wrapped window manager.
+    fn get_outputs(&self) -> Vec<Geometry> {
+        self.wm.get_outputs()
+    }
+
+    /// Replace the outputs of the wrapped window manager.
+    ///
+    /// The states on the stacks have the old outputs, so they are dropped.
+    fn set_outputs(&mut self, outputs: Vec<Geometry>) {
+        self.wm.set_outputs(outputs);
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.moved_window = None;
+    }
+
+    /// Return the current output index of the wrapped window manager.
+    fn get_current_output_index(&self) -> OutputIndex {
+        self.wm.get_current_output_index()
+    }
+
+    /// Get an immutable borrow of the output of the wrapped window manager.
+    fn get_output(&self, index: OutputIndex) -> Result<&O, Self::Error> {
+        self.wm.get_output(index)
+    }
+
+    /// Get a mutable borrow of the output of the wrapped window manager, the
+    /// changes to the output are not remembered.
+    fn get_output_mut(&mut self, index: OutputIndex) -> Result<&mut O, Self::Error> {
+        self.wm.get_output_mut(index)
+    }
+
+    /// Focus the output in the wrapped window manager, this is not
+    /// remembered.
+    fn focus_output(&mut self, index: OutputIndex) -> Result<(), Self::Error> {
+        self.wm.focus_output(index)
+    }
+}
+
+
+impl<WM: InvariantCheck> InvariantCheck for UndoWM<WM> {
+    /// checks the wrapped window manager, the states on the stacks are not
+    /// checked.
+    fn check_invariants(&self) -> Vec<String> {
+        self.wm.check_invariants()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WMName;
+
+    use cplwm_api::wm::WindowManager;
+    use cplwm_api::wm::TilingSupport;
+    use cplwm_api::wm::FloatSupport;
+    use cplwm_api::wm::MinimiseSupport;
+    use cplwm_api::wm::GapSupport;
+    use cplwm_api::wm::LayoutSupport;
+    use cplwm_api::wm::MasterRatioSupport;
+    use cplwm_api::wm::UndoSupport;
+    use cplwm_api::types::*;
+
+    // The generic tests of the traits that this window manager implements.
+    conformance_tests!(WMName: window_manager, tiling, floating, minimising, fullscreen);
+
+    // We define a static variable for the screen we will use in the tests.
+    static SCREEN: Screen = Screen {
+        width: 800,
+        height: 600,
+    };
+
+    // We define a static variable for the geometry of a fullscreen window.
+    static SCREEN_GEOM: Geometry = Geometry {
+        x: 0,
+        y: 0,
+        width: 800,
+        height: 600,
+    };
+
+    // We define a static variable for some random geometry that we will use
+    // when adding windows to a window manager.
+    static SOME_GEOM: Geometry = Geometry {
+        x: 10,
+        y: 10,
+        width: 100,
+        height: 100,
+    };
+
+    #[test]
+    fn test_undo_and_redo() {
+        let mut wm = WMName::new(SCREEN);
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+
+        // Nothing happens when there is nothing to undo or redo
+        wm.undo();
+        wm.redo();
+        assert_eq!(Some(1), wm.get_master_window());
+
+        wm.swap_with_master(2).unwrap();
+        wm.set_gap(10);
+        assert_eq!(Some(2), wm.get_master_window());
+        assert_eq!(10, wm.get_gap());
+
+        wm.undo();
+        assert_eq!(Some(2), wm.get_master_window());
+        assert_eq!(0, wm.get_gap());
+        wm.undo();
+        assert_eq!(Some(1), wm.get_master_window());
+
+        wm.redo();
+        assert_eq!(Some(2), wm.get_master_window());
+        assert_eq!(0, wm.get_gap());
+
+        // A new operation makes the undone gap impossible to redo
+        wm.toggle_minimised(1).unwrap();
+        wm.redo();
+        assert_eq!(0, wm.get_gap());
+        assert!(wm.is_minimised(1));
+        wm.undo();
+        assert!(!wm.is_minimised(1));
+
+        // Operations that change nothing are not remembered
+        wm.set_gap(0);
+        wm.undo();
+        assert_eq!(Some(1), wm.get_master_window());
+    }
+
+    #[test]
+    fn test_undo_reconciles_windows() {
+        let mut wm = WMName::new(SCREEN);
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        wm.toggle_floating(2).unwrap();
+
+        // Window 2 is closed and window 3 opened after the operations
+        wm.remove_window(2).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+        wm.focus_window(Some(1)).unwrap();
+
+        // The floating window 2 is not brought back
+        wm.undo();
+        assert_eq!(vec![1, 3], wm.get_windows());
+        assert!(!wm.is_managed(2));
+        assert!(wm.get_floating_windows().is_empty());
+        assert_eq!(Some(1), wm.get_focused_window());
+
+        // Window 3 is closed after the undo
+        wm.remove_window(3).unwrap();
+        wm.redo();
+        assert_eq!(vec![1], wm.get_windows());
+        assert_eq!(Some(1), wm.get_focused_window());
+        assert_eq!(vec![(1, SCREEN_GEOM)], wm.get_window_layout().windows);
+    }
+
+    #[test]
+    fn test_undo_window_move() {
+        let mut wm = WMName::new(SCREEN);
+        wm.add_window(WindowWithInfo::new_float(1, SOME_GEOM)).unwrap();
+
+        // A drag is undone at once
+        let mut geometry = SOME_GEOM;
+        for _ in 0..5 {
+            geometry.x += 10;
+            wm.set_window_geometry(1, geometry).unwrap();
+        }
+        assert_eq!(1, wm.undo_stack.len());
+        wm.undo();
+        assert_eq!(SOME_GEOM, wm.get_window_info(1).unwrap().geometry);
+
+        // Focusing the window ends the drag
+        wm.redo();
+        wm.focus_window(Some(1)).unwrap();
+        wm.set_window_geometry(1, SCREEN_GEOM).unwrap();
+        wm.undo();
+        assert_eq!(geometry, wm.get_window_info(1).unwrap().geometry);
+    }
+
+    #[test]
+    fn test_undo_settings_without_visible_change() {
+        let mut wm = WMName::new(SCREEN);
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        let layout = wm.get_window_layout();
+
+        // A single tiled window fills the screen, whatever the master ratio
+        wm.set_master_ratio(70);
+        assert_eq!(layout, wm.get_window_layout());
+        wm.undo();
+        assert_eq!(50, wm.get_master_ratio());
+
+        // Cycling the layout is remembered, even when the window stays put
+        wm.cycle_layout(PrevOrNext::Next);
+        assert_eq!(1, wm.undo_stack.len());
+        wm.undo();
+        assert_eq!(vec![(1, SCREEN_GEOM)], wm.get_window_layout().windows);
+    }
+
+    #[test]
+    fn test_bounded_history() {
+        let mut wm = WMName::new(SCREEN);
+        wm.max_history = 3;
+        for gap in 1..6 {
+            wm.set_gap(gap);
+        }
+        assert_eq!(3, wm.undo_stack.len());
+        for _ in 0..5 {
+            wm.undo();
+        }
+        assert_eq!(2, wm.get_gap());
+    }
+}
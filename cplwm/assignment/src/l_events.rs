@@ -0,0 +1,808 @@
+//! Extra: Events
+//!
+//! A wrapper that reports the changes of the state of the window manager as
+//! events, so the backend can pass them to the callbacks of the user. See
+//! the documentation of the [`EventSupport`] trait for the precise
+//! requirements.
+//!
+//! [`EventSupport`]: ../../cplwm_api/wm/trait.EventSupport.html
+//!
+//! # Status
+//!
+//! COMPLETED: YES
+//!
+//! COMMENTS:
+//!
+//! ## General approach
+//!
+//! *EventWM* wraps any window manager, like *UndoWM* does, and keeps the
+//! events that were not taken yet. Instead of emitting events in every
+//! function, every function that borrows the wrapped window manager mutably
+//! observes its state before and after the call and emits an event for
+//! every difference: the windows that were added and removed, the windows
+//! that started or stopped floating or were (un)minimised, and the focused
+//! and fullscreen window. In this way, the indirect changes are reported as
+//! well, e.g. the focus moving to another window when the focused window is
+//! removed, or the fullscreen window changing when another workspace
+//! becomes the current one.
+//!
+//! The floating windows are found with *get_window_info* and the minimised
+//! windows with *is_minimised*, because *get_floating_windows* and
+//! *get_minimised_windows* only concern the current workspace.
+//!
+//! The current workspace and the active layout are observed as well, so a
+//! status bar also learns about the workspace that becomes the current one
+//! because a window on it is focused, and about the layout that an undo
+//! brings back. The traits have no getter for the active layout and
+//! *WindowManager* does not know about workspaces, so the wrapped window
+//! manager has to implement *ObservableState*, which is implemented below
+//! for the window managers that *EventWM* wraps. These events come before
+//! the events of the windows. The changes made through the window managers
+//! borrowed with *get_workspace_mut* and *get_output_mut* are not reported.
+
+// Add imports here
+use std::mem;
+
+use cplwm_api::types::{ColumnRatio, Direction, FloatOrTile, GapSize, Geometry, MasterCount,
+                       MasterPosition, MasterRatio, OutputIndex, PrevOrNext, Screen, TabBar,
+                       WMEvent, Window, WindowLayout, WindowProperties, WindowWithInfo,
+                       WorkspaceIndex};
+use cplwm_api::wm::WindowManager;
+use cplwm_api::wm::TilingSupport;
+use cplwm_api::wm::FloatSupport;
+use cplwm_api::wm::MinimiseSupport;
+use cplwm_api::wm::FullscreenSupport;
+use cplwm_api::wm::GapSupport;
+use cplwm_api::wm::LayoutSupport;
+use cplwm_api::wm::TabBarSupport;
+use cplwm_api::wm::ColumnRatioSupport;
+use cplwm_api::wm::MasterRatioSupport;
+use cplwm_api::wm::MultiMasterSupport;
+use cplwm_api::wm::MasterPositionSupport;
+use cplwm_api::wm::DirectionalSupport;
+use cplwm_api::wm::WindowPropertiesSupport;
+use cplwm_api::wm::ScratchpadSupport;
+use cplwm_api::wm::UrgencySupport;
+use cplwm_api::wm::StickySupport;
+use cplwm_api::wm::FocusHistorySupport;
+use cplwm_api::wm::UndoSupport;
+use cplwm_api::wm::EventSupport;
+use cplwm_api::wm::MultiWorkspaceSupport;
+use cplwm_api::wm::MultiOutputSupport;
+use cplwm_api::wm::InvariantCheck;
+
+use e_fullscreen_windows::FullscreenWM;
+use f_gaps::GapWM;
+use g_multiple_workspaces::MultiWorkspaceWM;
+use h_different_tiling_layout::TilingLayout;
+use i_multiple_outputs::MultiOutputWM;
+use k_undo;
+use k_undo::UndoWM;
+
+/// Window manager aliase.
+pub type WMName = EventWM<k_undo::WMName>;
+
+/// The EventWM struct
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
+pub struct EventWM<WM> {
+    /// The wrapped window manager.
+    pub wm: WM,
+    /// The events that were not taken yet, the oldest one comes first.
+    pub events: Vec<WMEvent>,
+}
+
+/// The state of a window manager that the events report, but that its
+/// traits do not show.
+pub trait ObservableState {
+    /// returns the index of the current workspace, or None when the window
+    /// manager has no workspaces.
+    fn get_observed_workspace(&self) -> Option<WorkspaceIndex> {
+        None
+    }
+
+    /// returns the active layout, or None when the window manager has no
+    /// layouts.
+    fn get_observed_layout(&self) -> Option<TilingLayout> {
+        None
+    }
+}
+
+impl ObservableState for FullscreenWM {
+    /// returns the active layout of the tiled windows.
+    fn get_observed_layout(&self) -> Option<TilingLayout> {
+        let layout_wm = self.get_layout_wm();
+        layout_wm.layouts.get(layout_wm.index_layout).cloned()
+    }
+}
+
+impl<WM: ObservableState> ObservableState for GapWM<WM> {
+    /// returns the active layout of the wrapped window manager.
+    fn get_observed_layout(&self) -> Option<TilingLayout> {
+        self.wm.get_observed_layout()
+    }
+}
+
+impl<WM: ObservableState> ObservableState for MultiWorkspaceWM<WM> {
+    /// returns the index of the current workspace.
+    fn get_observed_workspace(&self) -> Option<WorkspaceIndex> {
+        Some(self.current_workspace)
+    }
+
+    /// returns the active layout of the current workspace.
+    fn get_observed_layout(&self) -> Option<TilingLayout> {
+        self.workspaces.get(self.current_workspace).and_then(|wm| wm.get_observed_layout())
+    }
+}
+
+impl<WM: ObservableState> ObservableState for MultiOutputWM<WM> {
+    /// returns the index of the current workspace of the current output.
+    fn get_observed_workspace(&self) -> Option<WorkspaceIndex> {
+        self.output_wms.get(self.current_output).and_then(|wm| wm.get_observed_workspace())
+    }
+
+    /// returns the active layout of the current output.
+    fn get_observed_layout(&self) -> Option<TilingLayout> {
+        self.output_wms.get(self.current_output).and_then(|wm| wm.get_observed_layout())
+    }
+}
+
+impl<WM: ObservableState> ObservableState for UndoWM<WM> {
+    /// returns the index of the current workspace of the wrapped window
+    /// manager.
+    fn get_observed_workspace(&self) -> Option<WorkspaceIndex> {
+        self.wm.get_observed_workspace()
+    }
+
+    /// returns the active layout of the wrapped window manager.
+    fn get_observed_layout(&self) -> Option<TilingLayout> {
+        self.wm.get_observed_layout()
+    }
+}
+
+/// The state of the wrapped window manager that is compared before and
+/// after a call.
+struct Observation {
+    /// The index of the current workspace.
+    workspace: Option<WorkspaceIndex>,
+    /// The active layout.
+    layout: Option<TilingLayout>,
+    /// All the managed windows.
+    windows: Vec<Window>,
+    /// The floating windows of all the workspaces.
+    floating_windows: Vec<Window>,
+    /// The minimised windows of all the workspaces.
+    minimised_windows: Vec<Window>,
+    /// The focused window.
+    focused_window: Option<Window>,
+    /// The fullscreen window.
+    fullscreen_window: Option<Window>,
+}
+
+/// Supported functions
+impl<WM> EventWM<WM>
+    where WM: WindowManager + FloatSupport + MinimiseSupport + FullscreenSupport + ObservableState
+{
+    /// returns the state of the wrapped window manager that is observed.
+    fn observe_state(&self) -> Observation {
+        let windows = self.wm.get_windows();
+        let floating_windows = windows.iter()
+            .cloned()
+            .filter(|w| match self.wm.get_window_info(*w) {
+                Ok(info) => info.float_or_tile == FloatOrTile::Float,
+                Err(_) => false,
+            })
+            .collect();
+        let minimised_windows = windows.iter()
+            .cloned()
+            .filter(|w| self.wm.is_minimised(*w))
+            .collect();
+        Observation {
+            workspace: self.wm.get_observed_workspace(),
+            layout: self.wm.get_observed_layout(),
+            windows: windows,
+            floating_windows: floating_windows,
+            minimised_windows: minimised_windows,
+            focused_window: self.wm.get_focused_window(),
+            fullscreen_window: self.wm.get_fullscreen_window(),
+        }
+    }
+
+    /// emits an event for every difference between the given state and the
+    /// current state of the wrapped window manager.
+    fn emit_changes(&mut self, before: Observation) {
+        let after = self.observe_state();
+        if after.workspace != before.workspace {
+            if let Some(index) = after.workspace {
+                self.events.push(WMEvent::WorkspaceSwitched(index));
+            }
+        }
+        if after.layout != before.layout {
+            self.events.push(WMEvent::LayoutChanged);
+        }
+        for window in before.windows.iter().filter(|w| !after.windows.contains(w)) {
+            self.events.push(WMEvent::WindowRemoved(*window));
+        }
+        for window in after.windows.iter().filter(|w| !before.windows.contains(w)) {
+            self.events.push(WMEvent::WindowAdded(*window));
+        }
+        for window in after.windows.iter().filter(|w| before.windows.contains(w)) {
+            let floating = after.floating_windows.contains(window);
+            if floating != before.floating_windows.contains(window) {
+                self.events.push(WMEvent::FloatingChanged(*window, floating));
+            }
+            let minimised = after.minimised_windows.contains(window);
+            if minimised != before.minimised_windows.contains(window) {
+                self.events.push(WMEvent::MinimisedChanged(*window, minimised));
+            }
+        }
+        if after.fullscreen_window != before.fullscreen_window {
+            self.events.push(WMEvent::FullscreenChanged(after.fullscreen_window));
+        }
+        if after.focused_window != before.focused_window {
+            self.events.push(WMEvent::FocusChanged(after.focused_window));
+        }
+    }
+
+    /// runs the operation on the wrapped window manager and emits the
+    /// events of the changes it made.
+    fn observe<T, F>(&mut self, operation: F) -> T
+        where F: FnOnce(&mut WM) -> T
+    {
+        let before = self.observe_state();
+        let result = operation(&mut self.wm);
+        self.emit_changes(before);
+        result
+    }
+}
+
+impl<WM> WindowManager for EventWM<WM>
+    where WM: WindowManager + FloatSupport + MinimiseSupport + FullscreenSupport + ObservableState
+{
+    /// The errors of the wrapped window manager are reused.
+    type Error = WM::Error;
+
+    /// The EventWM constructor.
+    ///
+    /// The wrapped window manager is created with the given screen, there
+    /// are no events yet.
+    fn new(screen: Screen) -> EventWM<WM> {
+        EventWM {
+            wm: WM::new(screen),
+            events: Vec::new(),
+        }
+    }
+
+    /// returns the layout of the wrapped window manager.
+    fn get_window_layout(&self) -> WindowLayout {
+        self.wm.get_window_layout()
+    }
+
+    /// checks the wrapped window manager.
+    fn is_managed(&self, window: Window) -> bool {
+        self.wm.is_managed(window)
+    }
+
+    /// returns the windows of the wrapped window manager.
+    fn get_windows(&self) -> Vec<Window> {
+        self.wm.get_windows()
+    }
+
+    /// returns the focused window of the wrapped window manager.
+    fn get_focused_window(&self) -> Option<Window> {
+        self.wm.get_focused_window()
+    }
+
+    /// adds the window to the wrapped window manager and emits the events.
+    fn add_window(&mut self, window_with_info: WindowWithInfo) -> Result<(), Self::Error> {
+        self.observe(|wm| wm.add_window(window_with_info))
+    }
+
+    /// removes the window from the wrapped window manager and emits the
+    /// events.
+    fn remove_window(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.observe(|wm| wm.remove_window(window))
+    }
+
+    /// focuses the window in the wrapped window manager and emits the
+    /// events.
+    fn focus_window(&mut self, window: Option<Window>) -> Result<(), Self::Error> {
+        self.observe(|wm| wm.focus_window(window))
+    }
+
+    /// cycles the focus in the wrapped window manager and emits the events.
+    fn cycle_focus(&mut self, dir: PrevOrNext) {
+        self.observe(|wm| wm.cycle_focus(dir))
+    }
+
+    /// returns the information of the wrapped window manager.
+    fn get_window_info(&self, window: Window) -> Result<WindowWithInfo, Self::Error> {
+        self.wm.get_window_info(window)
+    }
+
+    /// returns the screen of the wrapped window manager.
+    fn get_screen(&self) -> Screen {
+        self.wm.get_screen()
+    }
+
+    /// resizes the wrapped window manager and emits the events.
+    fn resize_screen(&mut self, screen: Screen) {
+        self.observe(|wm| wm.resize_screen(screen))
+    }
+}
+
+impl<WM> TilingSupport for EventWM<WM>
+    where WM: TilingSupport + FloatSupport + MinimiseSupport + FullscreenSupport + ObservableState
+{
+    /// returns the master window of the wrapped window manager.
+    fn get_master_window(&self) -> Option<Window> {
+        self.wm.get_master_window()
+    }
+
+    /// swaps the window with the master window and emits the events.
+    fn swap_with_master(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.observe(|wm| wm.swap_with_master(window))
+    }
+
+    /// swaps the focused window and emits the events.
+    fn swap_windows(&mut self, dir: PrevOrNext) {
+        self.observe(|wm| wm.swap_windows(dir))
+    }
+}
+
+impl<WM> FloatSupport for EventWM<WM>
+    where WM: FloatSupport + MinimiseSupport + FullscreenSupport + ObservableState
+{
+    /// returns the floating windows of the wrapped window manager.
+    fn get_floating_windows(&self) -> Vec<Window> {
+        self.wm.get_floating_windows()
+    }
+
+    /// toggles the window floating and emits the events.
+    fn toggle_floating(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.observe(|wm| wm.toggle_floating(window))
+    }
+
+    /// sets the geometry in the wrapped window manager and emits the
+    /// events.
+    fn set_window_geometry(&mut self,
+                           window: Window,
+                           new_geometry: Geometry)
+                           -> Result<(), Self::Error> {
+        self.observe(|wm| wm.set_window_geometry(window, new_geometry))
+    }
+}
+
+impl<WM> MinimiseSupport for EventWM<WM>
+    where WM: MinimiseSupport + FloatSupport + FullscreenSupport + ObservableState
+{
+    /// returns the minimised windows of the wrapped window manager.
+    fn get_minimised_windows(&self) -> Vec<Window> {
+        self.wm.get_minimised_windows()
+    }
+
+    /// checks the wrapped window manager.
+    fn is_minimised(&self, window: Window) -> bool {
+        self.wm.is_minimised(window)
+    }
+
+    /// (un)minimises the window and emits the events.
+    fn toggle_minimised(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.observe(|wm| wm.toggle_minimised(window))
+    }
+}
+
+impl<WM> FullscreenSupport for EventWM<WM>
+    where WM: FullscreenSupport + FloatSupport + MinimiseSupport + ObservableState
+{
+    /// returns the fullscreen window of the wrapped window manager.
+    fn get_fullscreen_window(&self) -> Option<Window> {
+        self.wm.get_fullscreen_window()
+    }
+
+    /// toggles the fullscreen of the window and emits the events.
+    fn toggle_fullscreen(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.observe(|wm| wm.toggle_fullscreen(window))
+    }
+}
+
+impl<WM> GapSupport for EventWM<WM>
+    where WM: GapSupport + FloatSupport + MinimiseSupport + FullscreenSupport + ObservableState
+{
+    /// returns the gap of the wrapped window manager.
+    fn get_gap(&self) -> GapSize {
+        self.wm.get_gap()
+    }
+
+    /// sets the gap of the wrapped window manager and emits the events.
+    fn set_gap(&mut self, gap: GapSize) {
+        self.observe(|wm| wm.set_gap(gap))
+    }
+}
+
+impl<WM> LayoutSupport for EventWM<WM>
+    where WM: LayoutSupport + FloatSupport + MinimiseSupport + FullscreenSupport + ObservableState
+{
+    /// cycles the layout and emits the events.
+    fn cycle_layout(&mut self, dir: PrevOrNext) {
+        self.observe(|wm| wm.cycle_layout(dir))
+    }
+}
+
+impl<WM> TabBarSupport for EventWM<WM>
+    where WM: TabBarSupport + FloatSupport + MinimiseSupport + FullscreenSupport + ObservableState
+{
+    /// returns the tab bars of the wrapped window manager.
+    fn get_tab_bars(&self) -> Vec<TabBar> {
+        self.wm.get_tab_bars()
+    }
+}
+
+impl<WM> ColumnRatioSupport for EventWM<WM>
+    where WM: ColumnRatioSupport + FloatSupport + MinimiseSupport + FullscreenSupport +
+              ObservableState
+{
+    /// returns the column ratio of the wrapped window manager.
+    fn get_column_ratio(&self) -> ColumnRatio {
+        self.wm.get_column_ratio()
+    }
+
+    /// sets the column ratio and emits the events.
+    fn set_column_ratio(&mut self, ratio: ColumnRatio) {
+        self.observe(|wm| wm.set_column_ratio(ratio))
+    }
+}
+
+impl<WM> MasterRatioSupport for EventWM<WM>
+    where WM: MasterRatioSupport + FloatSupport + MinimiseSupport + FullscreenSupport +
+              ObservableState
+{
+    /// returns the master ratio of the wrapped window manager.
+    fn get_master_ratio(&self) -> MasterRatio {
+        self.wm.get_master_ratio()
+    }
+
+    /// sets the master ratio and emits the events.
+    fn set_master_ratio(&mut self, ratio: MasterRatio) {
+        self.observe(|wm| wm.set_master_ratio(ratio))
+    }
+}
+
+impl<WM> MultiMasterSupport for EventWM<WM>
+    where WM: MultiMasterSupport + FloatSupport + MinimiseSupport + FullscreenSupport +
+              ObservableState
+{
+    /// returns the master windows of the wrapped window manager.
+    fn get_master_windows(&self) -> Vec<Window> {
+        self.wm.get_master_windows()
+    }
+
+    /// returns the master count of the wrapped window manager.
+    fn get_master_count(&self) -> MasterCount {
+        self.wm.get_master_count()
+    }
+
+    /// sets the master count and emits the events.
+    fn set_master_count(&mut self, count: MasterCount) {
+        self.observe(|wm| wm.set_master_count(count))
+    }
+}
+
+impl<WM> MasterPositionSupport for EventWM<WM>
+    where WM: MasterPositionSupport + FloatSupport + MinimiseSupport + FullscreenSupport +
+              ObservableState
+{
+    /// returns the master position of the wrapped window manager.
+    fn get_master_position(&self) -> MasterPosition {
+        self.wm.get_master_position()
+    }
+
+    /// sets the master position and emits the events.
+    fn set_master_position(&mut self, position: Option<MasterPosition>) {
+        self.observe(|wm| wm.set_master_position(position))
+    }
+}
+
+impl<WM> DirectionalSupport for EventWM<WM>
+    where WM: DirectionalSupport + FloatSupport + MinimiseSupport + FullscreenSupport +
+              ObservableState
+{
+    /// swaps the focused window in the direction and emits the events.
+    fn swap_direction(&mut self, dir: Direction) {
+        self.observe(|wm| wm.swap_direction(dir))
+    }
+}
+
+impl<WM> WindowPropertiesSupport for EventWM<WM>
+    where WM: WindowPropertiesSupport + FloatSupport + MinimiseSupport + FullscreenSupport +
+              ObservableState
+{
+    /// replaces the properties in the wrapped window manager and emits the
+    /// events.
+    fn set_window_properties(&mut self,
+                             window: Window,
+                             properties: WindowProperties)
+                             -> Result<(), Self::Error> {
+        self.observe(|wm| wm.set_window_properties(window, properties))
+    }
+}
+
+impl<WM> ScratchpadSupport for EventWM<WM>
+    where WM: ScratchpadSupport + FloatSupport + MinimiseSupport + FullscreenSupport +
+              ObservableState
+{
+    /// returns the scratchpad windows of the wrapped window manager.
+    fn get_scratchpad_windows(&self) -> Vec<Window> {
+        self.wm.get_scratchpad_windows()
+    }
+
+    /// moves the window to the scratchpad and emits the events.
+    fn move_to_scratchpad(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.observe(|wm| wm.move_to_scratchpad(window))
+    }
+
+    /// toggles the scratchpad and emits the events.
+    fn toggle_scratchpad(&mut self) {
+        self.observe(|wm| wm.toggle_scratchpad())
+    }
+}
+
+impl<WM> UrgencySupport for EventWM<WM>
+    where WM: UrgencySupport + FloatSupport + MinimiseSupport + FullscreenSupport + ObservableState
+{
+    /// returns the urgent windows of the wrapped window manager.
+    fn get_urgent_windows(&self) -> Vec<Window> {
+        self.wm.get_urgent_windows()
+    }
+
+    /// marks the window as urgent or not and emits the events.
+    fn set_urgent(&mut self, window: Window, urgent: bool) -> Result<(), Self::Error> {
+        self.observe(|wm| wm.set_urgent(window, urgent))
+    }
+}
+
+impl<WM> StickySupport for EventWM<WM>
+    where WM: StickySupport + FloatSupport + MinimiseSupport + FullscreenSupport + ObservableState
+{
+    /// returns the sticky windows of the wrapped window manager.
+    fn get_sticky_windows(&self) -> Vec<Window> {
+        self.wm.get_sticky_windows()
+    }
+
+    /// toggles the stickiness of the window and emits the events.
+    fn toggle_sticky(&mut self, window: Window) -> Result<(), Self::Error> {
+        self.observe(|wm| wm.toggle_sticky(window))
+    }
+}
+
+impl<WM> FocusHistorySupport for EventWM<WM>
+    where WM: FocusHistorySupport + FloatSupport + MinimiseSupport + FullscreenSupport +
+              ObservableState
+{
+    /// returns the focus history of the wrapped window manager.
+    fn get_focus_history(&self) -> Vec<Window> {
+        self.wm.get_focus_history()
+    }
+}
+
+impl<WM> UndoSupport for EventWM<WM>
+    where WM: UndoSupport + FloatSupport + MinimiseSupport + FullscreenSupport + ObservableState
+{
+    /// undoes the most recent operation and emits the events.
+    fn undo(&mut self) {
+        self.observe(|wm| wm.undo())
+    }
+
+    /// redoes the most recently undone operation and emits the events.
+    fn redo(&mut self) {
+        self.observe(|wm| wm.redo())
+    }
+}
+
+impl<WM> EventSupport for EventWM<WM>
+    where WM: WindowManager + FloatSupport + MinimiseSupport + FullscreenSupport + ObservableState
+{
+    /// returns the events that were not taken yet and forgets them.
+    fn take_events(&mut self) -> Vec<WMEvent> {
+        mem::replace(&mut self.events, Vec::new())
+    }
+}
+
+impl<WM, W> MultiWorkspaceSupport<W> for EventWM<WM>
+    where WM: MultiWorkspaceSupport<W> + FloatSupport + MinimiseSupport + FullscreenSupport +
+              ObservableState,
+          W: WindowManager
+{
+    /// Return the current workspace index of the wrapped window manager.
+    fn get_current_workspace_index(&self) -> WorkspaceIndex {
+        self.wm.get_current_workspace_index()
+    }
+
+    /// Get an immutable borrow of the workspace of the wrapped window
+    /// manager.
+    fn get_workspace(&self, index: WorkspaceIndex) -> Result<&W, Self::Error> {
+        self.wm.get_workspace(index)
+    }
+
+    /// Get a mutable borrow of the workspace of the wrapped window manager,
+    /// the changes to the workspace are not reported.
+    fn get_workspace_mut(&mut self, index: WorkspaceIndex) -> Result<&mut W, Self::Error> {
+        self.wm.get_workspace_mut(index)
+    }
+
+    /// Switch the workspace of the wrapped window manager and emit the
+    /// events.
+    fn switch_workspace(&mut self, index: WorkspaceIndex) -> Result<(), Self::Error> {
+        self.observe(|wm| wm.switch_workspace(index))
+    }
+}
+
+impl<WM, O> MultiOutputSupport<O> for EventWM<WM>
+    where WM: MultiOutputSupport<O> + FloatSupport + MinimiseSupport + FullscreenSupport +
+              ObservableState,
+          O: WindowManager
+{
+    /// Return the geometries of the outputs of the wrapped window manager.
+    fn get_outputs(&self) -> Vec<Geometry> {
+        self.wm.get_outputs()
+    }
+
+    /// Replace the outputs of the wrapped window manager and emit the
+    /// events.
+    fn set_outputs(&mut self, outputs: Vec<Geometry>) {
+        self.observe(|wm| wm.set_outputs(outputs))
+    }
+
+    /// Return the current output index of the wrapped window manager.
+    fn get_current_output_index(&self) -> OutputIndex {
+        self.wm.get_current_output_index()
+    }
+
+    /// Get an immutable borrow of the output of the wrapped window manager.
+    fn get_output(&self, index: OutputIndex) -> Result<&O, Self::Error> {
+        self.wm.get_output(index)
+    }
+
+    /// Get a mutable borrow of the output of the wrapped window manager, the
+    /// changes to the output are not reported.
+    fn get_output_mut(&mut self, index: OutputIndex) -> Result<&mut O, Self::Error> {
+        self.wm.get_output_mut(index)
+    }
+
+    /// Focus the output in the wrapped window manager and emit the events.
+    fn focus_output(&mut self, index: OutputIndex) -> Result<(), Self::Error> {
+        self.observe(|wm| wm.focus_output(index))
+    }
+}
+
+
+impl<WM> InvariantCheck for EventWM<WM>
+    where WM: InvariantCheck + FloatSupport + MinimiseSupport + FullscreenSupport + ObservableState
+{
+    /// checks the wrapped window manager.
+    fn check_invariants(&self) -> Vec<String> {
+        self.wm.check_invariants()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WMName;
+
+    use cplwm_api::wm::WindowManager;
+    use cplwm_api::wm::FloatSupport;
+    use cplwm_api::wm::MinimiseSupport;
+    use cplwm_api::wm::FullscreenSupport;
+    use cplwm_api::wm::LayoutSupport;
+    use cplwm_api::wm::UrgencySupport;
+    use cplwm_api::wm::UndoSupport;
+    use cplwm_api::wm::EventSupport;
+    use cplwm_api::wm::MultiWorkspaceSupport;
+    use cplwm_api::types::*;
+    use cplwm_api::types::WMEvent::*;
+
+    // The generic tests of the traits that this window manager implements.
+    conformance_tests!(WMName: window_manager, tiling, floating, minimising, fullscreen);
+
+    // We define a static variable for the screen we will use in the tests.
+    static SCREEN: Screen = Screen {
+        width: 800,
+        height: 600,
+    };
+
+    // We define a static variable for some random geometry that we will use
+    // when adding windows to a window manager.
+    static SOME_GEOM: Geometry = Geometry {
+        x: 10,
+        y: 10,
+        width: 100,
+        height: 100,
+    };
+
+    #[test]
+    fn test_window_events() {
+        let mut wm = WMName::new(SCREEN);
+        assert!(wm.take_events().is_empty());
+
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_float(2, SOME_GEOM)).unwrap();
+        assert_eq!(vec![WindowAdded(1),
+                        FocusChanged(Some(1)),
+                        WindowAdded(2),
+                        FocusChanged(Some(2))],
+                   wm.take_events());
+        assert!(wm.take_events().is_empty());
+
+        // Removing the focused window moves the focus
+        wm.remove_window(2).unwrap();
+        assert_eq!(vec![WindowRemoved(2), FocusChanged(Some(1))],
+                   wm.take_events());
+
+        // Errors emit no events
+        assert!(wm.remove_window(2).is_err());
+        assert!(wm.take_events().is_empty());
+    }
+
+    #[test]
+    fn test_state_events() {
+        let mut wm = WMName::new(SCREEN);
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        wm.take_events();
+
+        wm.toggle_floating(1).unwrap();
+        wm.toggle_minimised(2).unwrap();
+        wm.toggle_fullscreen(1).unwrap();
+        wm.cycle_layout(PrevOrNext::Next);
+        assert_eq!(vec![FloatingChanged(1, true),
+                        MinimisedChanged(2, true),
+                        FocusChanged(Some(1)),
+                        FullscreenChanged(Some(1)),
+                        LayoutChanged],
+                   wm.take_events());
+
+        // Undo and redo report the layout they bring back
+        wm.undo();
+        assert_eq!(vec![LayoutChanged], wm.take_events());
+        wm.redo();
+        assert_eq!(vec![LayoutChanged], wm.take_events());
+        wm.undo();
+        wm.take_events();
+        wm.undo();
+        wm.undo();
+        assert_eq!(vec![FullscreenChanged(None),
+                        MinimisedChanged(2, false),
+                        FocusChanged(Some(2))],
+                   wm.take_events());
+    }
+
+    #[test]
+    fn test_workspace_events() {
+        let mut wm = WMName::new(SCREEN);
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.toggle_minimised(1).unwrap();
+        wm.take_events();
+
+        // The minimised window on the hidden workspace stays minimised
+        wm.switch_workspace(1).unwrap();
+        wm.switch_workspace(1).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        wm.switch_workspace(0).unwrap();
+        assert_eq!(vec![WorkspaceSwitched(1),
+                        WindowAdded(2),
+                        FocusChanged(Some(2)),
+                        WorkspaceSwitched(0),
+                        FocusChanged(None)],
+                   wm.take_events());
+
+        // Focusing a window on another workspace switches to it
+        wm.focus_window(Some(2)).unwrap();
+        assert_eq!(vec![WorkspaceSwitched(1), FocusChanged(Some(2))],
+                   wm.take_events());
+        wm.set_urgent(1, true).unwrap();
+        wm.focus_urgent_window().unwrap();
+        assert_eq!(vec![WorkspaceSwitched(0),
+                        MinimisedChanged(1, false),
+                        FocusChanged(Some(1))],
+                   wm.take_events());
+    }
+}
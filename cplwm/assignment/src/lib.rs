@@ -69,6 +69,12 @@ extern crate rustc_serialize;
 
 // Declare the modules of which this project consists:
 
+// The generic tests of every window manager, declared first so that the
+// `conformance_tests!` macro can be used in the other modules.
+#[cfg(test)]
+#[macro_use]
+mod test_support;
+
 // Mandatory assignments
 pub mod a_fullscreen_wm;
 pub mod b_tiling_wm;
@@ -76,8 +82,14 @@ pub mod c_floating_windows;
 pub mod d_minimising_windows;
 // Optional assignments
 pub mod e_fullscreen_windows;
-// pub mod f_gaps;
-// pub mod g_multiple_workspaces;
-// pub mod h_different_tiling_layout;
+pub mod f_gaps;
+pub mod g_multiple_workspaces;
+pub mod h_different_tiling_layout;
 
 // Declare additional modules below or declare them in other modules.
+pub mod i_multiple_outputs;
+pub mod j_tree_wm;
+pub mod k_undo;
+pub mod l_events;
+pub mod m_layers;
+pub mod n_invariants;
@@ -0,0 +1,1406 @@
+//! Extra: Layered Window Managers
+//!
+//! Composable wrappers that add floating, minimising and fullscreen windows
+//! to any window manager, instead of copying the whole tiling logic for
+//! every new capability.
+//!
+//! # Status
+//!
+//! COMPLETED: YES
+//!
+//! COMMENTS:
+//!
+//! ## General approach
+//!
+//! Every layer adds one capability and wraps a window manager that only has
+//! to implement [`WindowManager`], like *GapWM* does:
+//!
+//! * *Floating* keeps the floating windows itself and passes the tiled
+//!   windows to the wrapped window manager, which lays them out.
+//! * *Minimising* removes the minimised windows from the wrapped window
+//!   manager and adds them again when they are unminimised, as suggested by
+//!   the documentation of [`MinimiseSupport`].
+//! * *Fullscreen* remembers the fullscreen window and replaces the layout
+//!   of the wrapped window manager while there is one.
+//!
+//! The other traits are forwarded when the wrapped window manager implements
+//! them, so the layers can be stacked onto any tiling window manager, e.g.
+//! `Minimising<Fullscreen<Floating<TillingWM>>>`. The tests at the bottom of
+//! this module run the generic tests of the traits against every order of
+//! the layers onto *TillingWM*, as far as the order implements the traits.
+//!
+//! ## Order of the layers
+//!
+//! A layer only sees the windows of the layers below it. Stacking
+//! *Minimising* on top of *Fullscreen* and *Floating* means that a minimised
+//! window comes back floating or fullscreen as it was before, because its
+//! information is asked to the layers below before removing it. A minimised
+//! tiled window is added at the end of the tiles again, as the wrapped
+//! window manager forgets its position, and floats with its last tile
+//! geometry when it is floated later on.
+//!
+//! *Floating* belongs at the bottom: the layers below it only see the
+//! tiles. On top of *Fullscreen* only a tiled window can be fullscreen, and
+//! *Floating* does not implement [`MinimiseSupport`] at all, since the
+//! wrapped window manager could not minimise its floating windows.
+//!
+//! ## The window managers built on the layers
+//!
+//! [`c_floating_windows`] is *Floating* on top of *TillingWM* and
+//! [`d_minimising_windows`] adds *Minimising* on top of that.
+//! [`e_fullscreen_windows`] stacks all three layers onto the tiling layouts
+//! of [`h_different_tiling_layout`] and only keeps the features that no
+//! layer has, like the scratchpad, in a thin wrapper around them.
+//!
+//! ## Focus
+//!
+//! *Floating* keeps the focused window itself, since it can be a floating
+//! window that the wrapped window manager does not know. The focus of the
+//! wrapped window manager is kept in sync: it is the focused window when
+//! that one is tiled and None otherwise. The fullscreen window must always
+//! be focused, so *Fullscreen* leaves the fullscreen mode as soon as
+//! another window gets the focus or the window disappears. It also leaves
+//! it when tiles are swapped or the fullscreen window is floated or tiled,
+//! to show where the windows went.
+//!
+//! [`c_floating_windows`]: ../c_floating_windows/index.html
+//! [`d_minimising_windows`]: ../d_minimising_windows/index.html
+//! [`e_fullscreen_windows`]: ../e_fullscreen_windows/index.html
+//! [`h_different_tiling_layout`]: ../h_different_tiling_layout/index.html
+//! [`WindowManager`]: ../../cplwm_api/wm/trait.WindowManager.html
+//! [`MinimiseSupport`]: ../../cplwm_api/wm/trait.MinimiseSupport.html
+
+// Add imports here
+use std::error;
+use std::fmt;
+
+use cplwm_api::types::{ColumnRatio, Direction, FloatOrTile, Geometry, MasterCount, MasterPosition,
+                       MasterRatio, PrevOrNext, Screen, TabBar, Window, WindowLayout,
+                       WindowProperties, WindowWithInfo};
+use cplwm_api::wm::WindowManager;
+use cplwm_api::wm::TilingSupport;
+use cplwm_api::wm::FloatSupport;
+use cplwm_api::wm::MinimiseSupport;
+use cplwm_api::wm::FullscreenSupport;
+use cplwm_api::wm::LayoutSupport;
+use cplwm_api::wm::TabBarSupport;
+use cplwm_api::wm::ColumnRatioSupport;
+use cplwm_api::wm::MasterRatioSupport;
+use cplwm_api::wm::MultiMasterSupport;
+use cplwm_api::wm::MasterPositionSupport;
+use cplwm_api::wm::DirectionalSupport;
+use cplwm_api::wm::WindowPropertiesSupport;
+use cplwm_api::wm::InvariantCheck;
+
+use b_tiling_wm::TillingWM;
+use n_invariants::{check_floating, check_fullscreen, check_minimising, check_window_manager,
+                   prefix_violations};
+
+/// Window manager aliase.
+pub type WMName = Minimising<Fullscreen<Floating<TillingWM>>>;
+
+/// The errors that the layers can return.
+#[derive(Debug)]
+pub enum LayerError<E> {
+    /// This window is not known by the window manager.
+    UnknownWindow(Window),
+    /// This window is already managed by the window manager.
+    ManagedWindow(Window),
+    /// This window is not a floating window.
+    NoFloatingWindow(Window),
+    /// This window is not a tiled window.
+    NoTiledWindow(Window),
+    /// An error returned by the wrapped window manager.
+    InnerError(E),
+}
+
+impl<E: error::Error> fmt::Display for LayerError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LayerError::UnknownWindow(ref window) => write!(f, "Unknown window: {}", window),
+            LayerError::ManagedWindow(ref window) => {
+                write!(f, "Window {} is already managed", window)
+            }
+            LayerError::NoFloatingWindow(ref window) => {
+                write!(f, "Window {} is not a floating window", window)
+            }
+            LayerError::NoTiledWindow(ref window) => {
+                write!(f, "Window {} is not a tiled window", window)
+            }
+            LayerError::InnerError(ref err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl<E: error::Error> error::Error for LayerError<E> {
+    fn description(&self) -> &str {
+        match *self {
+            LayerError::UnknownWindow(_) => "Unknown window",
+            LayerError::ManagedWindow(_) => "Window is already managed",
+            LayerError::NoFloatingWindow(_) => "Window is not a floating window",
+            LayerError::NoTiledWindow(_) => "Window is not a tiled window",
+            LayerError::InnerError(ref err) => err.description(),
+        }
+    }
+}
+
+/// removes the window from the wrapped window manager.
+///
+/// *TillingWM* only computes its tiles when a window is added or when the
+/// screen is resized, so the screen is resized to the same size afterwards
+/// to give the space of the window to the remaining tiles.
+fn remove_inner<WM: WindowManager>(wm: &mut WM,
+                                   window: Window)
+                                   -> Result<(), LayerError<WM::Error>> {
+    try!(wm.remove_window(window).map_err(LayerError::InnerError));
+    let screen = wm.get_screen();
+    wm.resize_screen(screen);
+    Ok(())
+}
+
+/// The Floating struct
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
+pub struct Floating<WM> {
+    /// The wrapped window manager, it manages the tiled windows.
+    pub wm: WM,
+    /// The floating windows, in the order in which they became floating.
+    pub floating_windows: Vec<WindowWithInfo>,
+    /// The floating geometry of every tiled window, used when it floats.
+    pub saved_geometries: Vec<(Window, Geometry)>,
+    /// The focused window, tiled or floating.
+    pub focused_window: Option<Window>,
+}
+
+impl<WM: WindowManager> Floating<WM> {
+    /// returns the index of the given window in *floating_windows*.
+    fn get_floating_index(&self, window: Window) -> Option<usize> {
+        self.floating_windows.iter().position(|w| w.window == window)
+    }
+
+    /// returns the saved floating geometry of the given tiled window.
+    fn take_saved_geometry(&mut self, window: Window) -> Option<Geometry> {
+        match self.saved_geometries.iter().position(|&(w, _)| w == window) {
+            None => None,
+            Some(i) => Some(self.saved_geometries.remove(i).1),
+        }
+    }
+
+    /// focuses the focused window in the wrapped window manager when it is
+    /// tiled, otherwise the wrapped window manager has no focused window.
+    fn sync_focus(&mut self) -> Result<(), LayerError<WM::Error>> {
+        let tiled_focus = self.focused_window.and_then(|w| {
+            if self.wm.is_managed(w) { Some(w) } else { None }
+        });
+        self.wm.focus_window(tiled_focus).map_err(LayerError::InnerError)
+    }
+}
+
+impl<WM: WindowManager> WindowManager for Floating<WM> {
+    type Error = LayerError<WM::Error>;
+
+    /// The Floating constructor.
+    fn new(screen: Screen) -> Floating<WM> {
+        Floating {
+            wm: WM::new(screen),
+            floating_windows: Vec::new(),
+            saved_geometries: Vec::new(),
+            focused_window: None,
+        }
+    }
+
+    /// returns the tiled windows followed by the floating windows.
+    fn get_windows(&self) -> Vec<Window> {
+        let mut windows = self.wm.get_windows();
+        windows.extend(self.floating_windows.iter().map(|w| w.window));
+        windows
+    }
+
+    /// returns the focused window.
+    fn get_focused_window(&self) -> Option<Window> {
+        self.focused_window
+    }
+
+    /// adds a floating window to *floating_windows* and passes a tiled
+    /// window to the wrapped window manager. The new window is focused.
+    fn add_window(&mut self, window_with_info: WindowWithInfo) -> Result<(), Self::Error> {
+        let window = window_with_info.window;
+        if self.is_managed(window) {
+            return Err(LayerError::ManagedWindow(window));
+        }
+        if window_with_info.float_or_tile == FloatOrTile::Float {
+            self.floating_windows.push(window_with_info);
+        } else {
+            self.saved_geometries.push((window, window_with_info.geometry));
+            try!(self.wm.add_window(window_with_info).map_err(LayerError::InnerError));
+        }
+        self.focused_window = Some(window);
+        self.sync_focus()
+    }
+
+    /// removes the window from this layer or from the wrapped window
+    /// manager. When the focused window is removed there is no focused
+    /// window anymore.
+    fn remove_window(&mut self, window: Window) -> Result<(), Self::Error> {
+        match self.get_floating_index(window) {
+            Some(i) => {
+                self.floating_windows.remove(i);
+            }
+            None => {
+                if !self.wm.is_managed(window) {
+                    return Err(LayerError::UnknownWindow(window));
+                }
+                try!(remove_inner(&mut self.wm, window));
+                self.take_saved_geometry(window);
+            }
+        }
+        if self.focused_window == Some(window) {
+            self.focused_window = None;
+        }
+        self.sync_focus()
+    }
+
+    /// returns the layout of the wrapped window manager with the floating
+    /// windows on top of it, the focused floating window is the last one.
+    fn get_window_layout(&self) -> WindowLayout {
+        let mut window_layout = self.wm.get_window_layout();
+        let mut focused_floating = None;
+        for floating_window in self.floating_windows.iter() {
+            let entry = (floating_window.window, floating_window.geometry);
+            if Some(floating_window.window) == self.focused_window {
+                focused_floating = Some(entry);
+            } else {
+                window_layout.windows.push(entry);
+            }
+        }
+        window_layout.windows.extend(focused_floating);
+        window_layout.focused_window = self.focused_window;
+        window_layout
+    }
+
+    /// focuses the given window, or no window when None is given.
+    fn focus_window(&mut self, window: Option<Window>) -> Result<(), Self::Error> {
+        if let Some(w) = window {
+            if !self.is_managed(w) {
+                return Err(LayerError::UnknownWindow(w));
+            }
+        }
+        self.focused_window = window;
+        self.sync_focus()
+    }
+
+    /// cycles the focus over the tiled windows followed by the floating
+    /// windows. When there is no focused window, the first one is focused.
+    fn cycle_focus(&mut self, dir: PrevOrNext) {
+        let windows = self.get_windows();
+        if windows.is_empty() {
+            return;
+        }
+        let last = windows.len() - 1;
+        let next = match self.focused_window.and_then(|f| windows.iter().position(|w| *w == f)) {
+            None => 0,
+            Some(i) => {
+                match dir {
+                    PrevOrNext::Prev => if i == 0 { last } else { i - 1 },
+                    PrevOrNext::Next => if i == last { 0 } else { i + 1 },
+                }
+            }
+        };
+        self.focused_window = Some(windows[next]);
+        // unwrap() is used because the focused window is managed
+        self.sync_focus().unwrap();
+    }
+
+    /// returns the information of a floating window or asks the wrapped
+    /// window manager for a tiled window.
+    fn get_window_info(&self, window: Window) -> Result<WindowWithInfo, Self::Error> {
+        match self.get_floating_index(window) {
+            Some(i) => Ok(self.floating_windows[i].clone()),
+            None => {
+                if !self.wm.is_managed(window) {
+                    return Err(LayerError::UnknownWindow(window));
+                }
+                self.wm.get_window_info(window).map_err(LayerError::InnerError)
+            }
+        }
+    }
+
+    /// returns the screen of the wrapped window manager.
+    fn get_screen(&self) -> Screen {
+        self.wm.get_screen()
+    }
+
+    /// resizes the screen of the wrapped window manager.
+    fn resize_screen(&mut self, screen: Screen) {
+        self.wm.resize_screen(screen)
+    }
+}
+
+impl<WM: TilingSupport> TilingSupport for Floating<WM> {
+    /// returns the master window of the wrapped window manager.
+    fn get_master_window(&self) -> Option<Window> {
+        self.wm.get_master_window()
+    }
+
+    /// swaps a tiled window with the master window, floating windows can't
+    /// be swapped.
+    fn swap_with_master(&mut self, window: Window) -> Result<(), Self::Error> {
+        if self.get_floating_index(window).is_some() {
+            return Err(LayerError::NoTiledWindow(window));
+        }
+        if !self.wm.is_managed(window) {
+            return Err(LayerError::UnknownWindow(window));
+        }
+        try!(self.wm.swap_with_master(window).map_err(LayerError::InnerError));
+        self.focused_window = self.wm.get_focused_window();
+        Ok(())
+    }
+
+    /// swaps the focused window with a neighbouring tile, only when the
+    /// focused window is tiled.
+    fn swap_windows(&mut self, dir: PrevOrNext) {
+        if self.wm.get_focused_window().is_some() {
+            self.wm.swap_windows(dir);
+            self.focused_window = self.wm.get_focused_window();
+        }
+    }
+}
+
+impl<WM: WindowManager> FloatSupport for Floating<WM> {
+    /// returns the floating windows.
+    fn get_floating_windows(&self) -> Vec<Window> {
+        self.floating_windows.iter().map(|w| w.window).collect()
+    }
+
+    /// moves the window between this layer and the wrapped window manager.
+    ///
+    /// A tiled window floats with its saved geometry, a floating window
+    /// saves its geometry before it is tiled. The focus is not changed.
+    fn toggle_floating(&mut self, window: Window) -> Result<(), Self::Error> {
+        match self.get_floating_index(window) {
+            Some(i) => {
+                // the window stays floating when the wrapped window manager
+                // refuses it
+                let mut window_with_info = self.floating_windows[i].clone();
+                window_with_info.float_or_tile = FloatOrTile::Tile;
+                let geometry = window_with_info.geometry;
+                try!(self.wm.add_window(window_with_info).map_err(LayerError::InnerError));
+                self.floating_windows.remove(i);
+                self.saved_geometries.push((window, geometry));
+            }
+            None => {
+                let mut window_with_info = try!(self.get_window_info(window));
+                try!(remove_inner(&mut self.wm, window));
+                if let Some(geometry) = self.take_saved_geometry(window) {
+                    window_with_info.geometry = geometry;
+                }
+                window_with_info.float_or_tile = FloatOrTile::Float;
+                self.floating_windows.push(window_with_info);
+            }
+        }
+        self.sync_focus()
+    }
+
+    /// changes the geometry of a floating window.
+    fn set_window_geometry(&mut self,
+                           window: Window,
+                           new_geometry: Geometry)
+                           -> Result<(), Self::Error> {
+        match self.get_floating_index(window) {
+            Some(i) => {
+                self.floating_windows[i].geometry = new_geometry;
+                Ok(())
+            }
+            None => {
+                if self.wm.is_managed(window) {
+                    Err(LayerError::NoFloatingWindow(window))
+                } else {
+                    Err(LayerError::UnknownWindow(window))
+                }
+            }
+        }
+    }
+}
+
+impl<WM: FullscreenSupport> FullscreenSupport for Floating<WM> {
+    /// returns the fullscreen window of the wrapped window manager.
+    fn get_fullscreen_window(&self) -> Option<Window> {
+        self.wm.get_fullscreen_window()
+    }
+
+    /// toggles the fullscreen mode of a tiled window in the wrapped window
+    /// manager.
+    fn toggle_fullscreen(&mut self, window: Window) -> Result<(), Self::Error> {
+        if self.get_floating_index(window).is_some() {
+            return Err(LayerError::NoTiledWindow(window));
+        }
+        if !self.wm.is_managed(window) {
+            return Err(LayerError::UnknownWindow(window));
+        }
+        try!(self.wm.toggle_fullscreen(window).map_err(LayerError::InnerError));
+        self.focused_window = self.wm.get_focused_window();
+        Ok(())
+    }
+}
+
+impl<WM: InvariantCheck> InvariantCheck for Floating<WM> {
+    /// checks the invariants of the window manager and the floating windows, and
+    /// the wrapped window manager.
+    fn check_invariants(&self) -> Vec<String> {
+        let mut violations = check_window_manager(self);
+        violations.extend(check_floating(self));
+        violations.extend(prefix_violations("wrapped", self.wm.check_invariants()));
+        violations
+    }
+}
+
+impl<WM: LayoutSupport> LayoutSupport for Floating<WM> {
+    /// cycles the layout of the wrapped window manager.
+    fn cycle_layout(&mut self, dir: PrevOrNext) {
+        self.wm.cycle_layout(dir)
+    }
+}
+
+impl<WM: TabBarSupport> TabBarSupport for Floating<WM> {
+    /// returns the tab bars of the wrapped window manager.
+    fn get_tab_bars(&self) -> Vec<TabBar> {
+        self.wm.get_tab_bars()
+    }
+}
+
+impl<WM: ColumnRatioSupport> ColumnRatioSupport for Floating<WM> {
+    /// returns the column ratio of the wrapped window manager.
+    fn get_column_ratio(&self) -> ColumnRatio {
+        self.wm.get_column_ratio()
+    }
+
+    /// sets the column ratio of the wrapped window manager.
+    fn set_column_ratio(&mut self, ratio: ColumnRatio) {
+        self.wm.set_column_ratio(ratio)
+    }
+}
+
+impl<WM: MasterRatioSupport> MasterRatioSupport for Floating<WM> {
+    /// returns the master ratio of the wrapped window manager.
+    fn get_master_ratio(&self) -> MasterRatio {
+        self.wm.get_master_ratio()
+    }
+
+    /// sets the master ratio of the wrapped window manager.
+    fn set_master_ratio(&mut self, ratio: MasterRatio) {
+        self.wm.set_master_ratio(ratio)
+    }
+}
+
+impl<WM: MultiMasterSupport> MultiMasterSupport for Floating<WM> {
+    /// returns the master windows of the wrapped window manager.
+    fn get_master_windows(&self) -> Vec<Window> {
+        self.wm.get_master_windows()
+    }
+
+    /// returns the master count of the wrapped window manager.
+    fn get_master_count(&self) -> MasterCount {
+        self.wm.get_master_count()
+    }
+
+    /// sets the master count of the wrapped window manager.
+    fn set_master_count(&mut self, count: MasterCount) {
+        self.wm.set_master_count(count)
+    }
+}
+
+impl<WM: MasterPositionSupport> MasterPositionSupport for Floating<WM> {
+    /// returns the master position of the wrapped window manager.
+    fn get_master_position(&self) -> MasterPosition {
+        self.wm.get_master_position()
+    }
+
+    /// sets the master position of the wrapped window manager.
+    fn set_master_position(&mut self, position: Option<MasterPosition>) {
+        self.wm.set_master_position(position)
+    }
+}
+
+impl<WM: DirectionalSupport> DirectionalSupport for Floating<WM> {
+    /// swaps the focused window with the window in the given direction of
+    /// the window layout.
+    ///
+    /// Two tiled windows are swapped by the wrapped window manager, which
+    /// finds the same tiled window in that direction. Two floating windows
+    /// swap their geometry.
+    fn swap_direction(&mut self, dir: Direction) {
+        let layout = self.get_window_layout();
+        let (focused_window, other_window) = match layout.focused_window {
+            None => return,
+            Some(focused_window) => {
+                match layout.get_window_in_direction(focused_window, dir) {
+                    None => return,
+                    Some(other_window) => (focused_window, other_window),
+                }
+            }
+        };
+        match (self.get_floating_index(focused_window), self.get_floating_index(other_window)) {
+            (Some(i), Some(j)) => {
+                let geometry = self.floating_windows[i].geometry;
+                self.floating_windows[i].geometry = self.floating_windows[j].geometry;
+                self.floating_windows[j].geometry = geometry;
+            }
+            (None, None) => self.wm.swap_direction(dir),
+            _ => (),
+        }
+    }
+}
+
+impl<WM: WindowPropertiesSupport> WindowPropertiesSupport for Floating<WM> {
+    /// replaces the properties of a floating window or asks the wrapped
+    /// window manager for a tiled window.
+    fn set_window_properties(&mut self,
+                             window: Window,
+                             properties: WindowProperties)
+                             -> Result<(), Self::Error> {
+        match self.get_floating_index(window) {
+            Some(i) => {
+                self.floating_windows[i].properties = properties;
+                Ok(())
+            }
+            None => {
+                if !self.wm.is_managed(window) {
+                    return Err(LayerError::UnknownWindow(window));
+                }
+                self.wm.set_window_properties(window, properties).map_err(LayerError::InnerError)
+            }
+        }
+    }
+}
+
+/// The Minimising struct
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
+pub struct Minimising<WM> {
+    /// The wrapped window manager, it manages the visible windows.
+    pub wm: WM,
+    /// The minimised windows, in the order in which they were minimised.
+    pub minimised_windows: Vec<WindowWithInfo>,
+}
+
+impl<WM: WindowManager> Minimising<WM> {
+    /// returns the index of the given window in *minimised_windows*.
+    fn get_minimised_index(&self, window: Window) -> Option<usize> {
+        self.minimised_windows.iter().position(|w| w.window == window)
+    }
+
+    /// adds the given minimised window to the wrapped window manager again,
+    /// like a new window, it does nothing when the window is not minimised.
+    fn unminimise(&mut self, window: Window) -> Result<(), LayerError<WM::Error>> {
+        if let Some(i) = self.get_minimised_index(window) {
+            let window_with_info = self.minimised_windows.remove(i);
+            try!(self.wm.add_window(window_with_info).map_err(LayerError::InnerError));
+        }
+        Ok(())
+    }
+}
+
+impl<WM: WindowManager> WindowManager for Minimising<WM> {
+    type Error = LayerError<WM::Error>;
+
+    /// The Minimising constructor.
+    fn new(screen: Screen) -> Minimising<WM> {
+        Minimising {
+            wm: WM::new(screen),
+            minimised_windows: Vec::new(),
+        }
+    }
+
+    /// returns the windows of the wrapped window manager followed by the
+    /// minimised windows.
+    fn get_windows(&self) -> Vec<Window> {
+        let mut windows = self.wm.get_windows();
+        windows.extend(self.minimised_windows.iter().map(|w| w.window));
+        windows
+    }
+
+    /// returns the focused window of the wrapped window manager.
+    fn get_focused_window(&self) -> Option<Window> {
+        self.wm.get_focused_window()
+    }
+
+    /// adds the window to the wrapped window manager.
+    fn add_window(&mut self, window_with_info: WindowWithInfo) -> Result<(), Self::Error> {
+        if self.is_managed(window_with_info.window) {
+            return Err(LayerError::ManagedWindow(window_with_info.window));
+        }
+        self.wm.add_window(window_with_info).map_err(LayerError::InnerError)
+    }
+
+    /// removes a minimised window or removes the window from the wrapped
+    /// window manager.
+    fn remove_window(&mut self, window: Window) -> Result<(), Self::Error> {
+        match self.get_minimised_index(window) {
+            Some(i) => {
+                self.minimised_windows.remove(i);
+                Ok(())
+            }
+            None => remove_inner(&mut self.wm, window),
+        }
+    }
+
+    /// returns the layout of the wrapped window manager, minimised windows
+    /// are not part of it.
+    fn get_window_layout(&self) -> WindowLayout {
+        self.wm.get_window_layout()
+    }
+
+    /// focuses the given window, a minimised window is unminimised first.
+    fn focus_window(&mut self, window: Option<Window>) -> Result<(), Self::Error> {
+        if let Some(w) = window {
+            try!(self.unminimise(w));
+        }
+        self.wm.focus_window(window).map_err(LayerError::InnerError)
+    }
+
+    /// cycles the focus in the wrapped window manager.
+    fn cycle_focus(&mut self, dir: PrevOrNext) {
+        self.wm.cycle_focus(dir)
+    }
+
+    /// returns the saved information of a minimised window or asks the
+    /// wrapped window manager.
+    fn get_window_info(&self, window: Window) -> Result<WindowWithInfo, Self::Error> {
+        match self.get_minimised_index(window) {
+            Some(i) => Ok(self.minimised_windows[i].clone()),
+            None => self.wm.get_window_info(window).map_err(LayerError::InnerError),
+        }
+    }
+
+    /// returns the screen of the wrapped window manager.
+    fn get_screen(&self) -> Screen {
+        self.wm.get_screen()
+    }
+
+    /// resizes the screen of the wrapped window manager.
+    fn resize_screen(&mut self, screen: Screen) {
+        self.wm.resize_screen(screen)
+    }
+}
+
+impl<WM: TilingSupport> TilingSupport for Minimising<WM> {
+    /// returns the master window of the wrapped window manager.
+    fn get_master_window(&self) -> Option<Window> {
+        self.wm.get_master_window()
+    }
+
+    /// swaps the window with the master window, a minimised window is
+    /// unminimised first.
+    fn swap_with_master(&mut self, window: Window) -> Result<(), Self::Error> {
+        try!(self.unminimise(window));
+        self.wm.swap_with_master(window).map_err(LayerError::InnerError)
+    }
+
+    /// swaps the focused window in the wrapped window manager.
+    fn swap_windows(&mut self, dir: PrevOrNext) {
+        self.wm.swap_windows(dir)
+    }
+}
+
+impl<WM: FloatSupport> FloatSupport for Minimising<WM> {
+    /// returns the floating windows of the wrapped window manager, the
+    /// minimised floating windows are not part of them.
+    fn get_floating_windows(&self) -> Vec<Window> {
+        self.wm.get_floating_windows()
+    }
+
+    /// toggles the floating mode of the window, a minimised window is
+    /// unminimised first.
+    fn toggle_floating(&mut self, window: Window) -> Result<(), Self::Error> {
+        try!(self.unminimise(window));
+        self.wm.toggle_floating(window).map_err(LayerError::InnerError)
+    }
+
+    /// changes the geometry of a floating window, also when it is
+    /// minimised.
+    fn set_window_geometry(&mut self,
+                           window: Window,
+                           new_geometry: Geometry)
+                           -> Result<(), Self::Error> {
+        match self.get_minimised_index(window) {
+            Some(i) => {
+                let minimised_window = &mut self.minimised_windows[i];
+                if minimised_window.float_or_tile == FloatOrTile::Float {
+                    minimised_window.geometry = new_geometry;
+                    Ok(())
+                } else {
+                    Err(LayerError::NoFloatingWindow(window))
+                }
+            }
+            None => {
+                self.wm
+                    .set_window_geometry(window, new_geometry)
+                    .map_err(LayerError::InnerError)
+            }
+        }
+    }
+}
+
+impl<WM: WindowManager> MinimiseSupport for Minimising<WM> {
+    /// returns the minimised windows, in the order in which they were
+    /// minimised.
+    fn get_minimised_windows(&self) -> Vec<Window> {
+        self.minimised_windows.iter().map(|w| w.window).collect()
+    }
+
+    /// checks *minimised_windows*.
+    fn is_minimised(&self, window: Window) -> bool {
+        self.get_minimised_index(window).is_some()
+    }
+
+    /// removes the window from the wrapped window manager, remembering its
+    /// information, or adds a minimised window to it again.
+    fn toggle_minimised(&mut self, window: Window) -> Result<(), Self::Error> {
+        if self.is_minimised(window) {
+            return self.unminimise(window);
+        }
+        if !self.wm.is_managed(window) {
+            return Err(LayerError::UnknownWindow(window));
+        }
+        let window_with_info = try!(self.wm
+            .get_window_info(window)
+            .map_err(LayerError::InnerError));
+        try!(remove_inner(&mut self.wm, window));
+        self.minimised_windows.push(window_with_info);
+        Ok(())
+    }
+}
+
+impl<WM: FullscreenSupport> FullscreenSupport for Minimising<WM> {
+    /// returns the fullscreen window of the wrapped window manager.
+    fn get_fullscreen_window(&self) -> Option<Window> {
+        self.wm.get_fullscreen_window()
+    }
+
+    /// toggles the fullscreen mode of the window, a minimised window is
+    /// unminimised first.
+    fn toggle_fullscreen(&mut self, window: Window) -> Result<(), Self::Error> {
+        try!(self.unminimise(window));
+        self.wm.toggle_fullscreen(window).map_err(LayerError::InnerError)
+    }
+}
+
+impl<WM: InvariantCheck> InvariantCheck for Minimising<WM> {
+    /// checks the invariants of the window manager and the minimised windows, and
+    /// the wrapped window manager.
+    fn check_invariants(&self) -> Vec<String> {
+        let mut violations = check_window_manager(self);
+        violations.extend(check_minimising(self));
+        violations.extend(prefix_violations("wrapped", self.wm.check_invariants()));
+        violations
+    }
+}
+
+impl<WM: LayoutSupport> LayoutSupport for Minimising<WM> {
+    /// cycles the layout of the wrapped window manager.
+    fn cycle_layout(&mut self, dir: PrevOrNext) {
+        self.wm.cycle_layout(dir)
+    }
+}
+
+impl<WM: TabBarSupport> TabBarSupport for Minimising<WM> {
+    /// returns the tab bars of the wrapped window manager.
+    fn get_tab_bars(&self) -> Vec<TabBar> {
+        self.wm.get_tab_bars()
+    }
+}
+
+impl<WM: ColumnRatioSupport> ColumnRatioSupport for Minimising<WM> {
+    /// returns the column ratio of the wrapped window manager.
+    fn get_column_ratio(&self) -> ColumnRatio {
+        self.wm.get_column_ratio()
+    }
+
+    /// sets the column ratio of the wrapped window manager.
+    fn set_column_ratio(&mut self, ratio: ColumnRatio) {
+        self.wm.set_column_ratio(ratio)
+    }
+}
+
+impl<WM: MasterRatioSupport> MasterRatioSupport for Minimising<WM> {
+    /// returns the master ratio of the wrapped window manager.
+    fn get_master_ratio(&self) -> MasterRatio {
+        self.wm.get_master_ratio()
+    }
+
+    /// sets the master ratio of the wrapped window manager.
+    fn set_master_ratio(&mut self, ratio: MasterRatio) {
+        self.wm.set_master_ratio(ratio)
+    }
+}
+
+impl<WM: MultiMasterSupport> MultiMasterSupport for Minimising<WM> {
+    /// returns the master windows of the wrapped window manager.
+    fn get_master_windows(&self) -> Vec<Window> {
+        self.wm.get_master_windows()
+    }
+
+    /// returns the master count of the wrapped window manager.
+    fn get_master_count(&self) -> MasterCount {
+        self.wm.get_master_count()
+    }
+
+    /// sets the master count of the wrapped window manager.
+    fn set_master_count(&mut self, count: MasterCount) {
+        self.wm.set_master_count(count)
+    }
+}
+
+impl<WM: MasterPositionSupport> MasterPositionSupport for Minimising<WM> {
+    /// returns the master position of the wrapped window manager.
+    fn get_master_position(&self) -> MasterPosition {
+        self.wm.get_master_position()
+    }
+
+    /// sets the master position of the wrapped window manager.
+    fn set_master_position(&mut self, position: Option<MasterPosition>) {
+        self.wm.set_master_position(position)
+    }
+}
+
+impl<WM: DirectionalSupport> DirectionalSupport for Minimising<WM> {
+    /// swaps the focused window in the wrapped window manager.
+    fn swap_direction(&mut self, dir: Direction) {
+        self.wm.swap_direction(dir)
+    }
+}
+
+impl<WM: WindowPropertiesSupport> WindowPropertiesSupport for Minimising<WM> {
+    /// replaces the properties of a minimised window or asks the wrapped
+    /// window manager.
+    fn set_window_properties(&mut self,
+                             window: Window,
+                             properties: WindowProperties)
+                             -> Result<(), Self::Error> {
+        match self.get_minimised_index(window) {
+            Some(i) => {
+                self.minimised_windows[i].properties = properties;
+                Ok(())
+            }
+            None => {
+                self.wm
+                    .set_window_properties(window, properties)
+                    .map_err(LayerError::InnerError)
+            }
+        }
+    }
+}
+
+/// The Fullscreen struct
+#[derive(RustcDecodable, RustcEncodable, Debug, Clone)]
+pub struct Fullscreen<WM> {
+    /// The wrapped window manager.
+    pub wm: WM,
+    /// The fullscreen window, if any.
+    pub fullscreen_window: Option<Window>,
+}
+
+impl<WM: WindowManager> Fullscreen<WM> {
+    /// leaves the fullscreen mode when the fullscreen window is no longer
+    /// the focused window of the wrapped window manager.
+    fn check_fullscreen(&mut self) {
+        if self.fullscreen_window.is_some() &&
+           self.fullscreen_window != self.wm.get_focused_window() {
+            self.fullscreen_window = None;
+        }
+    }
+}
+
+impl<WM: WindowManager> WindowManager for Fullscreen<WM> {
+    type Error = LayerError<WM::Error>;
+
+    /// The Fullscreen constructor.
+    fn new(screen: Screen) -> Fullscreen<WM> {
+        Fullscreen {
+            wm: WM::new(screen),
+            fullscreen_window: None,
+        }
+    }
+
+    /// returns the windows of the wrapped window manager.
+    fn get_windows(&self) -> Vec<Window> {
+        self.wm.get_windows()
+    }
+
+    /// returns the focused window of the wrapped window manager.
+    fn get_focused_window(&self) -> Option<Window> {
+        self.wm.get_focused_window()
+    }
+
+    /// adds the window to the wrapped window manager, it becomes the
+    /// fullscreen window when it wants to be fullscreen.
+    fn add_window(&mut self, window_with_info: WindowWithInfo) -> Result<(), Self::Error> {
+        let window = window_with_info.window;
+        let fullscreen = window_with_info.fullscreen;
+        if self.is_managed(window) {
+            return Err(LayerError::ManagedWindow(window));
+        }
+        try!(self.wm.add_window(window_with_info).map_err(LayerError::InnerError));
+        if fullscreen {
+            try!(self.wm.focus_window(Some(window)).map_err(LayerError::InnerError));
+            self.fullscreen_window = Some(window);
+        }
+        self.check_fullscreen();
+        Ok(())
+    }
+
+    /// removes the window from the wrapped window manager.
+    fn remove_window(&mut self, window: Window) -> Result<(), Self::Error> {
+        try!(remove_inner(&mut self.wm, window));
+        self.check_fullscreen();
+        Ok(())
+    }
+
+    /// returns only the fullscreen window with the size of the screen, or
+    /// the layout of the wrapped window manager when there is none.
+    fn get_window_layout(&self) -> WindowLayout {
+        match self.fullscreen_window {
+            None => self.wm.get_window_layout(),
+            Some(window) => {
+                WindowLayout {
+                    focused_window: Some(window),
+                    windows: vec![(window, self.get_screen().to_geometry())],
+                }
+            }
+        }
+    }
+
+    /// focuses the window in the wrapped window manager.
+    fn focus_window(&mut self, window: Option<Window>) -> Result<(), Self::Error> {
+        try!(self.wm.focus_window(window).map_err(LayerError::InnerError));
+        self.check_fullscreen();
+        Ok(())
+    }
+
+    /// cycles the focus in the wrapped window manager.
+    fn cycle_focus(&mut self, dir: PrevOrNext) {
+        self.wm.cycle_focus(dir);
+        self.check_fullscreen();
+    }
+
+    /// returns the information of the wrapped window manager with the
+    /// fullscreen mode of this layer.
+    fn get_window_info(&self, window: Window) -> Result<WindowWithInfo, Self::Error> {
+        let mut window_with_info = try!(self.wm
+            .get_window_info(window)
+            .map_err(LayerError::InnerError));
+        window_with_info.fullscreen = self.fullscreen_window == Some(window);
+        Ok(window_with_info)
+    }
+
+    /// returns the screen of the wrapped window manager.
+    fn get_screen(&self) -> Screen {
+        self.wm.get_screen()
+    }
+
+    /// resizes the screen of the wrapped window manager.
+    fn resize_screen(&mut self, screen: Screen) {
+        self.wm.resize_screen(screen)
+    }
+}
+
+impl<WM: TilingSupport> TilingSupport for Fullscreen<WM> {
+    /// returns the master window of the wrapped window manager.
+    fn get_master_window(&self) -> Option<Window> {
+        self.wm.get_master_window()
+    }
+
+    /// swaps the window with the master window in the wrapped window
+    /// manager, the fullscreen mode is left to show the swapped tiles.
+    fn swap_with_master(&mut self, window: Window) -> Result<(), Self::Error> {
+        try!(self.wm.swap_with_master(window).map_err(LayerError::InnerError));
+        self.fullscreen_window = None;
+        Ok(())
+    }
+
+    /// swaps the focused window in the wrapped window manager, the
+    /// fullscreen mode is left to show the swapped tiles.
+    fn swap_windows(&mut self, dir: PrevOrNext) {
+        self.wm.swap_windows(dir);
+        self.fullscreen_window = None;
+    }
+}
+
+impl<WM: FloatSupport> FloatSupport for Fullscreen<WM> {
+    /// returns the floating windows of the wrapped window manager.
+    fn get_floating_windows(&self) -> Vec<Window> {
+        self.wm.get_floating_windows()
+    }
+
+    /// toggles the floating mode in the wrapped window manager, the
+    /// fullscreen window leaves the fullscreen mode to show where it goes.
+    fn toggle_floating(&mut self, window: Window) -> Result<(), Self::Error> {
+        try!(self.wm.toggle_floating(window).map_err(LayerError::InnerError));
+        if self.fullscreen_window == Some(window) {
+            self.fullscreen_window = None;
+        }
+        self.check_fullscreen();
+        Ok(())
+    }
+
+    /// changes the geometry of a floating window in the wrapped window
+    /// manager.
+    fn set_window_geometry(&mut self,
+                           window: Window,
+                           new_geometry: Geometry)
+                           -> Result<(), Self::Error> {
+        self.wm.set_window_geometry(window, new_geometry).map_err(LayerError::InnerError)
+    }
+}
+
+impl<WM: MinimiseSupport> MinimiseSupport for Fullscreen<WM> {
+    /// returns the minimised windows of the wrapped window manager.
+    fn get_minimised_windows(&self) -> Vec<Window> {
+        self.wm.get_minimised_windows()
+    }
+
+    /// checks the wrapped window manager.
+    fn is_minimised(&self, window: Window) -> bool {
+        self.wm.is_minimised(window)
+    }
+
+    /// toggles the minimised mode in the wrapped window manager.
+    fn toggle_minimised(&mut self, window: Window) -> Result<(), Self::Error> {
+        try!(self.wm.toggle_minimised(window).map_err(LayerError::InnerError));
+        self.check_fullscreen();
+        Ok(())
+    }
+}
+
+impl<WM: WindowManager> FullscreenSupport for Fullscreen<WM> {
+    /// returns the fullscreen window.
+    fn get_fullscreen_window(&self) -> Option<Window> {
+        self.fullscreen_window
+    }
+
+    /// focuses the window and makes it the fullscreen window, or leaves the
+    /// fullscreen mode when it is already the fullscreen window.
+    fn toggle_fullscreen(&mut self, window: Window) -> Result<(), Self::Error> {
+        if !self.is_managed(window) {
+            return Err(LayerError::UnknownWindow(window));
+        }
+        if self.fullscreen_window == Some(window) {
+            self.fullscreen_window = None;
+        } else {
+            try!(self.wm.focus_window(Some(window)).map_err(LayerError::InnerError));
+            self.fullscreen_window = Some(window);
+        }
+        Ok(())
+    }
+}
+
+impl<WM: InvariantCheck> InvariantCheck for Fullscreen<WM> {
+    /// checks the invariants of the window manager and the fullscreen window, and
+    /// the wrapped window manager.
+    fn check_invariants(&self) -> Vec<String> {
+        let mut violations = check_window_manager(self);
+        violations.extend(check_fullscreen(self));
+        violations.extend(prefix_violations("wrapped", self.wm.check_invariants()));
+        violations
+    }
+}
+
+impl<WM: LayoutSupport> LayoutSupport for Fullscreen<WM> {
+    /// cycles the layout of the wrapped window manager.
+    fn cycle_layout(&mut self, dir: PrevOrNext) {
+        self.wm.cycle_layout(dir)
+    }
+}
+
+impl<WM: TabBarSupport> TabBarSupport for Fullscreen<WM> {
+    /// returns the tab bars of the wrapped window manager, there are none
+    /// while the fullscreen window hides the tiled windows.
+    fn get_tab_bars(&self) -> Vec<TabBar> {
+        match self.fullscreen_window {
+            None => self.wm.get_tab_bars(),
+            Some(_) => Vec::new(),
+        }
+    }
+}
+
+impl<WM: ColumnRatioSupport> ColumnRatioSupport for Fullscreen<WM> {
+    /// returns the column ratio of the wrapped window manager.
+    fn get_column_ratio(&self) -> ColumnRatio {
+        self.wm.get_column_ratio()
+    }
+
+    /// sets the column ratio of the wrapped window manager.
+    fn set_column_ratio(&mut self, ratio: ColumnRatio) {
+        self.wm.set_column_ratio(ratio)
+    }
+}
+
+impl<WM: MasterRatioSupport> MasterRatioSupport for Fullscreen<WM> {
+    /// returns the master ratio of the wrapped window manager.
+    fn get_master_ratio(&self) -> MasterRatio {
+        self.wm.get_master_ratio()
+    }
+
+    /// sets the master ratio of the wrapped window manager.
+    fn set_master_ratio(&mut self, ratio: MasterRatio) {
+        self.wm.set_master_ratio(ratio)
+    }
+}
+
+impl<WM: MultiMasterSupport> MultiMasterSupport for Fullscreen<WM> {
+    /// returns the master windows of the wrapped window manager.
+    fn get_master_windows(&self) -> Vec<Window> {
+        self.wm.get_master_windows()
+    }
+
+    /// returns the master count of the wrapped window manager.
+    fn get_master_count(&self) -> MasterCount {
+        self.wm.get_master_count()
+    }
+
+    /// sets the master count of the wrapped window manager.
+    fn set_master_count(&mut self, count: MasterCount) {
+        self.wm.set_master_count(count)
+    }
+}
+
+impl<WM: MasterPositionSupport> MasterPositionSupport for Fullscreen<WM> {
+    /// returns the master position of the wrapped window manager.
+    fn get_master_position(&self) -> MasterPosition {
+        self.wm.get_master_position()
+    }
+
+    /// sets the master position of the wrapped window manager.
+    fn set_master_position(&mut self, position: Option<MasterPosition>) {
+        self.wm.set_master_position(position)
+    }
+}
+
+impl<WM: DirectionalSupport> DirectionalSupport for Fullscreen<WM> {
+    /// swaps the focused window in the wrapped window manager, nothing
+    /// happens while the fullscreen window is the only visible window.
+    fn swap_direction(&mut self, dir: Direction) {
+        if self.fullscreen_window.is_none() {
+            self.wm.swap_direction(dir)
+        }
+    }
+}
+
+impl<WM: WindowPropertiesSupport> WindowPropertiesSupport for Fullscreen<WM> {
+    /// replaces the properties in the wrapped window manager.
+    fn set_window_properties(&mut self,
+                             window: Window,
+                             properties: WindowProperties)
+                             -> Result<(), Self::Error> {
+        self.wm.set_window_properties(window, properties).map_err(LayerError::InnerError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    // We have to import the layers from the super module.
+    use super::{Floating, Fullscreen, Minimising};
+    use b_tiling_wm::TillingWM;
+
+    // We have to repeat the imports we did in the super module.
+    use cplwm_api::wm::WindowManager;
+    use cplwm_api::wm::TilingSupport;
+    use cplwm_api::wm::FloatSupport;
+    use cplwm_api::wm::MinimiseSupport;
+    use cplwm_api::wm::FullscreenSupport;
+    use cplwm_api::types::*;
+
+    // The generic tests of the traits, for every order in which the layers can
+    // be stacked onto *TillingWM*. Every order gets its own module, as the
+    // tests are put in modules named after the traits. Only the suites of the
+    // traits that an order implements are run, e.g. *Floating* does not
+    // implement *MinimiseSupport*.
+    mod floating_layers {
+        use super::*;
+        conformance_tests!(Floating<TillingWM>: window_manager, tiling, floating);
+    }
+
+    mod minimising_floating_layers {
+        use super::*;
+        conformance_tests!(Minimising<Floating<TillingWM>>: window_manager, tiling, floating,
+                                                              minimising);
+    }
+
+    mod fullscreen_floating_layers {
+        use super::*;
+        conformance_tests!(Fullscreen<Floating<TillingWM>>: window_manager, tiling, floating,
+                                                              fullscreen);
+    }
+
+    mod fullscreen_minimising_floating_layers {
+        use super::*;
+        conformance_tests!(Fullscreen<Minimising<Floating<TillingWM>>>: window_manager, tiling,
+                                                                          floating, minimising,
+                                                                          fullscreen);
+    }
+
+    mod minimising_fullscreen_floating_layers {
+        use super::*;
+        conformance_tests!(Minimising<Fullscreen<Floating<TillingWM>>>: window_manager, tiling,
+                                                                          floating, minimising,
+                                                                          fullscreen);
+    }
+
+    mod minimising_layers {
+        use super::*;
+        conformance_tests!(Minimising<TillingWM>: window_manager, tiling);
+    }
+
+    mod fullscreen_layers {
+        use super::*;
+        conformance_tests!(Fullscreen<TillingWM>: window_manager, tiling);
+    }
+
+    mod fullscreen_minimising_layers {
+        use super::*;
+        conformance_tests!(Fullscreen<Minimising<TillingWM>>: window_manager, tiling);
+    }
+
+    mod minimising_fullscreen_layers {
+        use super::*;
+        conformance_tests!(Minimising<Fullscreen<TillingWM>>: window_manager, tiling);
+    }
+
+    mod floating_minimising_layers {
+        use super::*;
+        conformance_tests!(Floating<Minimising<TillingWM>>: window_manager, tiling, floating);
+    }
+
+    mod floating_fullscreen_layers {
+        use super::*;
+        conformance_tests!(Floating<Fullscreen<TillingWM>>: window_manager, tiling, floating);
+    }
+
+    mod floating_fullscreen_minimising_layers {
+        use super::*;
+        conformance_tests!(Floating<Fullscreen<Minimising<TillingWM>>>: window_manager, tiling,
+                                                                          floating);
+    }
+
+    mod floating_minimising_fullscreen_layers {
+        use super::*;
+        conformance_tests!(Floating<Minimising<Fullscreen<TillingWM>>>: window_manager, tiling,
+                                                                          floating);
+    }
+
+    mod fullscreen_floating_minimising_layers {
+        use super::*;
+        conformance_tests!(Fullscreen<Floating<Minimising<TillingWM>>>: window_manager, tiling,
+                                                                          floating, fullscreen);
+    }
+
+    mod minimising_floating_fullscreen_layers {
+        use super::*;
+        conformance_tests!(Minimising<Floating<Fullscreen<TillingWM>>>: window_manager, tiling,
+                                                                          floating, minimising);
+    }
+
+    // We define a static variable for the screen we will use in the tests.
+    static SCREEN: Screen = Screen {
+        width: 800,
+        height: 600,
+    };
+
+    // We define a static variable for the geometry of a fullscreen window.
+    static SCREEN_GEOM: Geometry = Geometry {
+        x: 0,
+        y: 0,
+        width: 800,
+        height: 600,
+    };
+
+    // We define a static variable for some random geometry.
+    static SOME_GEOM: Geometry = Geometry {
+        x: 10,
+        y: 10,
+        width: 100,
+        height: 100,
+    };
+
+    #[test]
+    fn test_minimised_fullscreen_window() {
+        // minimising on top of fullscreen brings the window back fullscreen
+        let mut wm = Minimising::<Fullscreen<Floating<TillingWM>>>::new(SCREEN);
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        wm.toggle_fullscreen(1).unwrap();
+        wm.toggle_minimised(1).unwrap();
+        assert_eq!(None, wm.get_fullscreen_window());
+        wm.toggle_minimised(1).unwrap();
+        assert_eq!(Some(1), wm.get_fullscreen_window());
+        assert_eq!(vec![(1, SCREEN_GEOM)], wm.get_window_layout().windows);
+    }
+
+    #[test]
+    fn test_layers_without_floating() {
+        let mut wm = Minimising::<TillingWM>::new(SCREEN);
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        wm.toggle_minimised(1).unwrap();
+        assert_eq!(vec![(2, SCREEN_GEOM)], wm.get_window_layout().windows);
+        wm.toggle_minimised(1).unwrap();
+        assert_eq!(vec![2, 1], wm.get_windows());
+
+        let mut wm = Fullscreen::<TillingWM>::new(SCREEN);
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        wm.toggle_fullscreen(1).unwrap();
+        assert_eq!(vec![(1, SCREEN_GEOM)], wm.get_window_layout().windows);
+        assert_eq!(Some(1), wm.get_focused_window());
+    }
+
+    #[test]
+    fn test_floating_on_top_of_fullscreen() {
+        let mut wm = Floating::<Fullscreen<TillingWM>>::new(SCREEN);
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_float(2, SOME_GEOM)).unwrap();
+
+        // the fullscreen layer only sees the tiles
+        assert!(wm.toggle_fullscreen(2).is_err());
+        wm.toggle_fullscreen(1).unwrap();
+        assert_eq!(Some(1), wm.get_fullscreen_window());
+        assert_eq!(Some(1), wm.get_focused_window());
+    }
+
+    #[test]
+    fn test_refused_tile_stays_floating() {
+        let mut wm = Floating::<TillingWM>::new(SCREEN);
+        wm.add_window(WindowWithInfo::new_float(1, SOME_GEOM)).unwrap();
+
+        // the wrapped window manager refuses a window that it already manages
+        wm.wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        assert!(wm.toggle_floating(1).is_err());
+        assert_eq!(vec![1], wm.get_floating_windows());
+        assert!(wm.saved_geometries.is_empty());
+    }
+
+    #[test]
+    fn test_leaving_the_fullscreen_mode() {
+        let mut wm = Fullscreen::<Floating<TillingWM>>::new(SCREEN);
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+
+        // swapping the tiles shows where they went
+        wm.toggle_fullscreen(2).unwrap();
+        wm.swap_with_master(2).unwrap();
+        assert_eq!(None, wm.get_fullscreen_window());
+        wm.toggle_fullscreen(2).unwrap();
+        wm.swap_windows(PrevOrNext::Next);
+        assert_eq!(None, wm.get_fullscreen_window());
+
+        // floating the fullscreen window shows where it floats
+        wm.toggle_fullscreen(1).unwrap();
+        wm.toggle_floating(1).unwrap();
+        assert_eq!(None, wm.get_fullscreen_window());
+        assert_eq!(Some(1), wm.get_focused_window());
+
+        // floating another window keeps the fullscreen mode
+        wm.toggle_fullscreen(2).unwrap();
+        wm.toggle_floating(1).unwrap();
+        assert_eq!(Some(2), wm.get_fullscreen_window());
+    }
+}
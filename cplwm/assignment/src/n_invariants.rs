@@ -0,0 +1,358 @@
+//! Extra: Invariant Checks
+//!
+//! Functions that check the invariants documented in `cplwm_api::wm`, used
+//! by the implementations of the [`InvariantCheck`] trait of the window
+//! managers of this crate. See the documentation of the [`InvariantCheck`]
+//! trait for the precise requirements.
+//!
+//! [`InvariantCheck`]: ../../cplwm_api/wm/trait.InvariantCheck.html
+//!
+//! # Status
+//!
+//! COMPLETED: YES
+//!
+//! COMMENTS:
+//!
+//! ## General approach
+//!
+//! There is a function for every trait that has invariants about the state
+//! of the window manager, it only uses the methods of that trait, so it
+//! works for every window manager that implements the trait. A window
+//! manager implements *check_invariants* by calling the functions of the
+//! traits it implements. The wrappers that don't keep any windows
+//! themselves, like *GapWM*, *UndoWM* and *EventWM*, only check the wrapped
+//! window manager.
+//!
+//! The window managers of the workspaces and the outputs are not visible
+//! through the methods of the other traits, so *MultiWorkspaceWM* and
+//! *MultiOutputWM* check each of them too, the violations are prefixed with
+//! the workspace or output, e.g. `output 0: workspace 2: ...`.
+//!
+//! The invariants about what happens after a method is called, e.g. that an
+//! added window is focused, can't be checked from the state alone, so they
+//! are left to the tests.
+
+// Add imports here
+use cplwm_api::types::{FloatOrTile, Window};
+use cplwm_api::wm::WindowManager;
+use cplwm_api::wm::TilingSupport;
+use cplwm_api::wm::FloatSupport;
+use cplwm_api::wm::MinimiseSupport;
+use cplwm_api::wm::FullscreenSupport;
+
+/// returns the windows that occur more than once in the given windows.
+fn get_duplicates(windows: &[Window]) -> Vec<Window> {
+    let mut duplicates = Vec::new();
+    for (i, window) in windows.iter().enumerate() {
+        if windows[..i].contains(window) && !duplicates.contains(window) {
+            duplicates.push(*window);
+        }
+    }
+    duplicates
+}
+
+/// checks the invariants of [`WindowManager`]: the windows and the window
+/// layout contain no duplicates, the focused window and the windows of the
+/// layout are managed, and the information of every managed window is
+/// known.
+///
+/// [`WindowManager`]: ../../cplwm_api/wm/trait.WindowManager.html
+pub fn check_window_manager<WM: WindowManager>(wm: &WM) -> Vec<String> {
+    let mut violations = Vec::new();
+    let windows = wm.get_windows();
+    for window in get_duplicates(&windows) {
+        violations.push(format!("get_windows contains window {} more than once", window));
+    }
+
+    let focused_window = wm.get_focused_window();
+    if let Some(window) = focused_window {
+        if !windows.contains(&window) {
+            violations.push(format!("the focused window {} is not managed", window));
+        }
+    }
+
+    let window_layout = wm.get_window_layout();
+    if window_layout.focused_window != focused_window {
+        violations.push(format!("the window layout focuses {:?}, but the focused window is {:?}",
+                                window_layout.focused_window,
+                                focused_window));
+    }
+    let layout_windows: Vec<Window> = window_layout.windows.iter().map(|&(w, _)| w).collect();
+    for window in get_duplicates(&layout_windows) {
+        violations.push(format!("the window layout contains window {} more than once", window));
+    }
+    for window in layout_windows.iter().filter(|w| !windows.contains(w)) {
+        violations.push(format!("the window layout contains window {} which is not managed",
+                                window));
+    }
+
+    for window in windows.iter() {
+        match wm.get_window_info(*window) {
+            Err(err) => {
+                violations.push(format!("get_window_info fails for managed window {}: {}",
+                                        window,
+                                        err))
+            }
+            Ok(window_with_info) => {
+                if window_with_info.window != *window {
+                    violations.push(format!("get_window_info for window {} returns window {}",
+                                            window,
+                                            window_with_info.window));
+                }
+            }
+        }
+    }
+    violations
+}
+
+/// checks the invariants of [`TilingSupport`]: the master window is
+/// managed.
+///
+/// [`TilingSupport`]: ../../cplwm_api/wm/trait.TilingSupport.html
+pub fn check_tiling<WM: TilingSupport>(wm: &WM) -> Vec<String> {
+    let mut violations = Vec::new();
+    if let Some(window) = wm.get_master_window() {
+        if !wm.is_managed(window) {
+            violations.push(format!("the master window {} is not managed", window));
+        }
+    }
+    violations
+}
+
+/// checks the invariants of [`FloatSupport`]: the floating windows are
+/// managed, occur once and float according to their information.
+///
+/// [`FloatSupport`]: ../../cplwm_api/wm/trait.FloatSupport.html
+pub fn check_floating<WM: FloatSupport>(wm: &WM) -> Vec<String> {
+    let mut violations = Vec::new();
+    let floating_windows = wm.get_floating_windows();
+    for window in get_duplicates(&floating_windows) {
+        violations.push(format!("get_floating_windows contains window {} more than once",
+                                window));
+    }
+    for window in floating_windows.iter() {
+        match wm.get_window_info(*window) {
+            Err(_) => violations.push(format!("the floating window {} is not managed", window)),
+            Ok(window_with_info) => {
+                if window_with_info.float_or_tile != FloatOrTile::Float {
+                    violations.push(format!("the floating window {} is tiled according to \
+                                             get_window_info",
+                                            window));
+                }
+            }
+        }
+    }
+    violations
+}
+
+/// checks the invariants of [`MinimiseSupport`]: the minimised windows are
+/// managed, occur once and are not visible in the window layout.
+///
+/// [`MinimiseSupport`]: ../../cplwm_api/wm/trait.MinimiseSupport.html
+pub fn check_minimising<WM: MinimiseSupport>(wm: &WM) -> Vec<String> {
+    let mut violations = Vec::new();
+    let minimised_windows = wm.get_minimised_windows();
+    for window in get_duplicates(&minimised_windows) {
+        violations.push(format!("get_minimised_windows contains window {} more than once",
+                                window));
+    }
+    let window_layout = wm.get_window_layout();
+    for window in minimised_windows.iter() {
+        if !wm.is_managed(*window) {
+            violations.push(format!("the minimised window {} is not managed", window));
+        }
+        if window_layout.windows.iter().any(|&(w, _)| w == *window) {
+            violations.push(format!("the minimised window {} is visible in the window layout",
+                                    window));
+        }
+    }
+    violations
+}
+
+/// checks the invariants of [`FullscreenSupport`]: the fullscreen window is
+/// managed and focused.
+///
+/// [`FullscreenSupport`]: ../../cplwm_api/wm/trait.FullscreenSupport.html
+pub fn check_fullscreen<WM: FullscreenSupport>(wm: &WM) -> Vec<String> {
+    let mut violations = Vec::new();
+    if let Some(window) = wm.get_fullscreen_window() {
+        if !wm.is_managed(window) {
+            violations.push(format!("the fullscreen window {} is not managed", window));
+        }
+        if wm.get_focused_window() != Some(window) {
+            violations.push(format!("the fullscreen window {} is not focused, {:?} is",
+                                    window,
+                                    wm.get_focused_window()));
+        }
+    }
+    violations
+}
+
+/// returns the given violations with the given prefix, used for the
+/// violations of a window manager wrapped by another one.
+pub fn prefix_violations(prefix: &str, violations: Vec<String>) -> Vec<String> {
+    violations.into_iter().map(|violation| format!("{}: {}", prefix, violation)).collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+
+    use a_fullscreen_wm;
+    use b_tiling_wm::TillingWM;
+    use c_floating_windows::FloatingWM;
+    use d_minimising_windows::MinimisingWM;
+    use e_fullscreen_windows::FullscreenWM;
+    use f_gaps;
+    use g_multiple_workspaces;
+    use i_multiple_outputs;
+    use j_tree_wm::TreeWM;
+    use k_undo;
+    use l_events;
+    use m_layers;
+    // We have to repeat the imports we did in the super module.
+    use cplwm_api::wm::WindowManager;
+    use cplwm_api::wm::TilingSupport;
+    use cplwm_api::wm::FloatSupport;
+    use cplwm_api::wm::MinimiseSupport;
+    use cplwm_api::wm::FullscreenSupport;
+    use cplwm_api::wm::InvariantCheck;
+    use cplwm_api::types::*;
+
+    // We define a static variable for the screen we will use in the tests.
+    static SCREEN: Screen = Screen {
+        width: 800,
+        height: 600,
+    };
+
+    // We define a static variable for some random geometry.
+    static SOME_GEOM: Geometry = Geometry {
+        x: 10,
+        y: 10,
+        width: 100,
+        height: 100,
+    };
+
+    fn assert_no_violations<WM: InvariantCheck>(wm: &WM) {
+        assert_eq!(Vec::<String>::new(), wm.check_invariants());
+    }
+
+    // Checks the window manager after every step of the operations of the
+    // given traits, none of them should violate an invariant.
+
+    fn check_window_manager<WM: InvariantCheck>() {
+        let mut wm = WM::new(SCREEN);
+        assert_no_violations(&wm);
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        assert_no_violations(&wm);
+        wm.cycle_focus(PrevOrNext::Next);
+        assert_no_violations(&wm);
+        wm.remove_window(2).unwrap();
+        assert_no_violations(&wm);
+        wm.focus_window(None).unwrap();
+        assert_no_violations(&wm);
+    }
+
+    fn check_floating<WM: InvariantCheck + TilingSupport + FloatSupport>() {
+        let mut wm = WM::new(SCREEN);
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_float(2, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+        assert_no_violations(&wm);
+        wm.swap_with_master(3).unwrap();
+        assert_no_violations(&wm);
+        wm.toggle_floating(3).unwrap();
+        wm.toggle_floating(2).unwrap();
+        assert_no_violations(&wm);
+        wm.remove_window(3).unwrap();
+        assert_no_violations(&wm);
+    }
+
+    fn check_minimising<WM: InvariantCheck + FloatSupport + MinimiseSupport>() {
+        let mut wm = WM::new(SCREEN);
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_float(2, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+        wm.toggle_minimised(1).unwrap();
+        wm.toggle_minimised(2).unwrap();
+        assert_no_violations(&wm);
+        wm.toggle_minimised(2).unwrap();
+        assert_no_violations(&wm);
+        wm.remove_window(1).unwrap();
+        assert_no_violations(&wm);
+    }
+
+    fn check_fullscreen<WM: InvariantCheck + FloatSupport + FullscreenSupport>() {
+        let mut wm = WM::new(SCREEN);
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_float(2, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+        wm.toggle_fullscreen(3).unwrap();
+        assert_no_violations(&wm);
+        wm.toggle_fullscreen(2).unwrap();
+        wm.remove_window(2).unwrap();
+        assert_no_violations(&wm);
+        wm.focus_window(Some(1)).unwrap();
+        assert_no_violations(&wm);
+    }
+
+    fn check_all<WM>()
+        where WM: InvariantCheck + TilingSupport + FloatSupport + MinimiseSupport +
+                  FullscreenSupport
+    {
+        check_window_manager::<WM>();
+        check_floating::<WM>();
+        check_minimising::<WM>();
+        check_fullscreen::<WM>();
+    }
+
+    #[test]
+    fn test_no_violations() {
+        check_window_manager::<a_fullscreen_wm::FullscreenWM>();
+        check_window_manager::<TillingWM>();
+        check_floating::<FloatingWM>();
+        check_floating::<TreeWM>();
+        check_floating::<MinimisingWM>();
+        check_minimising::<MinimisingWM>();
+        check_all::<FullscreenWM>();
+        check_all::<f_gaps::WMName>();
+        check_all::<g_multiple_workspaces::WMName>();
+        check_all::<i_multiple_outputs::WMName>();
+        check_all::<k_undo::WMName>();
+        check_all::<l_events::WMName>();
+        check_all::<m_layers::WMName>();
+    }
+
+    #[test]
+    fn test_violations() {
+        let mut wm = TillingWM::new(SCREEN);
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+        assert_no_violations(&wm);
+
+        // a window that is added twice
+        let duplicate = wm.windows[0].clone();
+        wm.windows.push(duplicate);
+        assert_eq!(vec!["get_windows contains window 1 more than once".to_owned(),
+                        "the window layout contains window 1 more than once".to_owned()],
+                   wm.check_invariants());
+
+        // a minimised window that is still visible
+        let mut wm = MinimisingWM::new(SCREEN);
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.minimised_windows.push(WindowWithInfo::new_tiled(1, SOME_GEOM));
+        assert_eq!(vec!["get_windows contains window 1 more than once".to_owned(),
+                        "the minimised window 1 is visible in the window layout".to_owned()],
+                   wm.check_invariants());
+
+        // the violations of a workspace are prefixed with the workspace
+        let mut wm = g_multiple_workspaces::WMName::new(SCREEN);
+        wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+        wm.workspaces[2].wm.wm.fullscreen_window = Some(3);
+        let violations = wm.check_invariants();
+        assert!(violations.contains(&"workspace 2: the fullscreen window 3 is not managed"
+            .to_owned()));
+        assert!(violations.iter().all(|violation| violation.starts_with("workspace 2: ")));
+    }
+}
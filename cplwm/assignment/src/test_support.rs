@@ -0,0 +1,450 @@
+//! Generic tests that every window manager of this crate should pass.
+//!
+//! Every test function only relies on the documentation of the traits in
+//! `cplwm_api::wm`, so it can be run against any window manager that
+//! implements the traits in its bounds. The invariants of the window
+//! manager are checked after every step with [`InvariantCheck`].
+//!
+//! The tests are grouped in suites, one for every trait: `window_manager`,
+//! `tiling`, `floating`, `minimising` and `fullscreen`. The
+//! [`conformance_tests!`] macro instantiates the given suites for a window
+//! manager in the tests module of that window manager, e.g.:
+//!
+//! ```ignore
+//! conformance_tests!(TillingWM: window_manager, tiling);
+//! ```
+//!
+//! The macro can be used once per module, as every suite becomes a module
+//! with the name of the suite.
+//!
+//! [`InvariantCheck`]: ../../cplwm_api/wm/trait.InvariantCheck.html
+//! [`conformance_tests!`]: ../macro.conformance_tests.html
+
+// Add imports here
+use cplwm_api::types::{FloatOrTile, Geometry, PrevOrNext, Screen, Window, WindowWithInfo};
+use cplwm_api::wm::WindowManager;
+use cplwm_api::wm::TilingSupport;
+use cplwm_api::wm::FloatSupport;
+use cplwm_api::wm::MinimiseSupport;
+use cplwm_api::wm::FullscreenSupport;
+use cplwm_api::wm::InvariantCheck;
+
+/// Instantiates the given suites of generic tests for the given window
+/// manager, see the [`test_support`] module.
+///
+/// [`test_support`]: test_support/index.html
+macro_rules! conformance_tests {
+    ($wm:ty: $($suite:ident),+) => {
+        $(conformance_tests!(@suite $wm, $suite);)+
+    };
+    (@suite $wm:ty, window_manager) => {
+        mod window_manager {
+            use super::*;
+            use $crate::test_support;
+
+            #[test]
+            fn test_adding_and_removing_some_windows() {
+                test_support::test_adding_and_removing_some_windows::<$wm>();
+            }
+
+            #[test]
+            fn test_focus_window() {
+                test_support::test_focus_window::<$wm>();
+            }
+
+            #[test]
+            fn test_cycle_focus() {
+                test_support::test_cycle_focus::<$wm>();
+            }
+
+            #[test]
+            fn test_get_window_info() {
+                test_support::test_get_window_info::<$wm>();
+            }
+
+            #[test]
+            fn test_get_resize_screen() {
+                test_support::test_get_resize_screen::<$wm>();
+            }
+        }
+    };
+    (@suite $wm:ty, tiling) => {
+        mod tiling {
+            use super::*;
+            use $crate::test_support;
+
+            #[test]
+            fn test_get_master_window() {
+                test_support::test_get_master_window::<$wm>();
+            }
+
+            #[test]
+            fn test_swap_with_master() {
+                test_support::test_swap_with_master::<$wm>();
+            }
+
+            #[test]
+            fn test_swap_windows() {
+                test_support::test_swap_windows::<$wm>();
+            }
+        }
+    };
+    (@suite $wm:ty, floating) => {
+        mod floating {
+            use super::*;
+            use $crate::test_support;
+
+            #[test]
+            fn test_floating_windows() {
+                test_support::test_floating_windows::<$wm>();
+            }
+
+            #[test]
+            fn test_set_window_geometry() {
+                test_support::test_set_window_geometry::<$wm>();
+            }
+        }
+    };
+    (@suite $wm:ty, minimising) => {
+        mod minimising {
+            use super::*;
+            use $crate::test_support;
+
+            #[test]
+            fn test_minimised_windows() {
+                test_support::test_minimised_windows::<$wm>();
+            }
+        }
+    };
+    (@suite $wm:ty, fullscreen) => {
+        mod fullscreen {
+            use super::*;
+            use $crate::test_support;
+
+            #[test]
+            fn test_fullscreen_window() {
+                test_support::test_fullscreen_window::<$wm>();
+            }
+        }
+    };
+}
+
+// We define a static variable for the screen we will use in the tests.
+static SCREEN: Screen = Screen {
+    width: 800,
+    height: 600,
+};
+
+// We define a static variable for the geometry of a fullscreen window.
+static SCREEN_GEOM: Geometry = Geometry {
+    x: 0,
+    y: 0,
+    width: 800,
+    height: 600,
+};
+
+// We define a static variable for some random geometry.
+static SOME_GEOM: Geometry = Geometry {
+    x: 10,
+    y: 10,
+    width: 100,
+    height: 100,
+};
+
+/// asserts that all the invariants of the window manager hold.
+pub fn assert_invariants<WM: InvariantCheck>(wm: &WM) {
+    assert_eq!(Vec::<String>::new(), wm.check_invariants());
+}
+
+/// returns whether the given window is visible in the window layout.
+fn is_visible<WM: WindowManager>(wm: &WM, window: Window) -> bool {
+    wm.get_window_layout().windows.iter().any(|&(w, _)| w == window)
+}
+
+/// adds and removes windows, the added windows are managed and focused.
+pub fn test_adding_and_removing_some_windows<WM: InvariantCheck>() {
+    let mut wm = WM::new(SCREEN);
+    assert!(wm.get_windows().is_empty());
+    assert_eq!(None, wm.get_focused_window());
+    assert!(wm.get_window_layout().windows.is_empty());
+    assert_invariants(&wm);
+
+    wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+    assert!(wm.is_managed(1));
+    assert_eq!(Some(1), wm.get_focused_window());
+    assert_invariants(&wm);
+
+    wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+    assert!(wm.is_managed(1));
+    assert!(wm.is_managed(2));
+    assert_eq!(2, wm.get_windows().len());
+    assert_eq!(Some(2), wm.get_focused_window());
+    assert!(is_visible(&wm, 2));
+    assert_invariants(&wm);
+
+    // the focused window stays focused when another one is removed
+    wm.remove_window(1).unwrap();
+    assert!(!wm.is_managed(1));
+    assert_eq!(Some(2), wm.get_focused_window());
+    assert_invariants(&wm);
+
+    wm.remove_window(2).unwrap();
+    assert!(wm.get_windows().is_empty());
+    assert_eq!(None, wm.get_focused_window());
+    assert!(wm.get_window_layout().windows.is_empty());
+    assert_invariants(&wm);
+}
+
+/// focuses windows and nothing.
+pub fn test_focus_window<WM: InvariantCheck>() {
+    let mut wm = WM::new(SCREEN);
+    wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+    wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+
+    wm.focus_window(Some(1)).unwrap();
+    assert_eq!(Some(1), wm.get_focused_window());
+    assert_eq!(Some(1), wm.get_window_layout().focused_window);
+    assert_invariants(&wm);
+
+    // some window managers, e.g. the fullscreen one, always focus a window
+    // when there are windows, so only check that the layout agrees
+    wm.focus_window(None).unwrap();
+    assert_eq!(wm.get_focused_window(), wm.get_window_layout().focused_window);
+    assert_invariants(&wm);
+
+    wm.focus_window(Some(2)).unwrap();
+    assert_eq!(Some(2), wm.get_focused_window());
+    assert_invariants(&wm);
+}
+
+/// cycles the focus, cycling back and forth doesn't change the focus.
+pub fn test_cycle_focus<WM: InvariantCheck>() {
+    let mut wm = WM::new(SCREEN);
+    // nothing happens without windows
+    wm.cycle_focus(PrevOrNext::Next);
+    assert_eq!(None, wm.get_focused_window());
+
+    // a single window is focused when nothing is focused
+    wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+    wm.focus_window(None).unwrap();
+    wm.cycle_focus(PrevOrNext::Next);
+    assert_eq!(Some(1), wm.get_focused_window());
+    wm.cycle_focus(PrevOrNext::Prev);
+    assert_eq!(Some(1), wm.get_focused_window());
+    assert_invariants(&wm);
+
+    wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+    wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+    wm.focus_window(Some(2)).unwrap();
+    wm.cycle_focus(PrevOrNext::Next);
+    assert!(wm.get_focused_window() != Some(2));
+    assert_invariants(&wm);
+    wm.cycle_focus(PrevOrNext::Prev);
+    assert_eq!(Some(2), wm.get_focused_window());
+    wm.cycle_focus(PrevOrNext::Prev);
+    wm.cycle_focus(PrevOrNext::Next);
+    assert_eq!(Some(2), wm.get_focused_window());
+
+    // any window can be focused when nothing is focused
+    wm.focus_window(None).unwrap();
+    wm.cycle_focus(PrevOrNext::Next);
+    assert!(wm.get_focused_window().is_some());
+    assert_invariants(&wm);
+}
+
+/// gets the information of a window, which fails for unknown windows.
+pub fn test_get_window_info<WM: InvariantCheck>() {
+    let mut wm = WM::new(SCREEN);
+    wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+    let window_with_info = wm.get_window_info(1).unwrap();
+    assert_eq!(1, window_with_info.window);
+    assert_eq!(FloatOrTile::Tile, window_with_info.float_or_tile);
+    assert!(!window_with_info.fullscreen);
+    assert!(wm.get_window_info(2).is_err());
+}
+
+/// resizes the screen.
+pub fn test_get_resize_screen<WM: InvariantCheck>() {
+    let mut wm = WM::new(SCREEN);
+    assert_eq!(SCREEN, wm.get_screen());
+    wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+
+    let screen = Screen {
+        width: 1024,
+        height: 768,
+    };
+    wm.resize_screen(screen);
+    assert_eq!(screen, wm.get_screen());
+    assert_invariants(&wm);
+}
+
+/// gets the master window, there is none without windows.
+pub fn test_get_master_window<WM: InvariantCheck + TilingSupport>() {
+    let mut wm = WM::new(SCREEN);
+    assert_eq!(None, wm.get_master_window());
+
+    wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+    wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+    assert!(wm.get_master_window().is_some());
+    assert_invariants(&wm);
+
+    wm.remove_window(1).unwrap();
+    wm.remove_window(2).unwrap();
+    assert_eq!(None, wm.get_master_window());
+}
+
+/// swaps windows with the master window.
+pub fn test_swap_with_master<WM: InvariantCheck + TilingSupport>() {
+    let mut wm = WM::new(SCREEN);
+    wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+    wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+    wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+
+    wm.swap_with_master(3).unwrap();
+    assert_eq!(Some(3), wm.get_master_window());
+    assert_invariants(&wm);
+
+    wm.swap_with_master(2).unwrap();
+    assert_eq!(Some(2), wm.get_master_window());
+    wm.swap_with_master(2).unwrap();
+    assert_eq!(Some(2), wm.get_master_window());
+    assert_invariants(&wm);
+}
+
+/// swaps the focused window, swapping back and forth doesn't change the
+/// layout and swapping never changes the focus.
+pub fn test_swap_windows<WM: InvariantCheck + TilingSupport>() {
+    let mut wm = WM::new(SCREEN);
+    wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+    wm.add_window(WindowWithInfo::new_tiled(2, SOME_GEOM)).unwrap();
+    wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+    wm.focus_window(Some(2)).unwrap();
+    let window_layout = wm.get_window_layout();
+
+    wm.swap_windows(PrevOrNext::Next);
+    assert_eq!(Some(2), wm.get_focused_window());
+    assert_invariants(&wm);
+    wm.swap_windows(PrevOrNext::Prev);
+    assert_eq!(window_layout, wm.get_window_layout());
+
+    wm.swap_windows(PrevOrNext::Prev);
+    wm.swap_windows(PrevOrNext::Next);
+    assert_eq!(window_layout, wm.get_window_layout());
+
+    wm.focus_window(None).unwrap();
+    wm.swap_windows(PrevOrNext::Next);
+    assert_eq!(None, wm.get_focused_window());
+    assert_invariants(&wm);
+}
+
+/// adds and toggles floating windows, which are above the tiled windows.
+pub fn test_floating_windows<WM: InvariantCheck + FloatSupport>() {
+    let mut wm = WM::new(SCREEN);
+    wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+    wm.add_window(WindowWithInfo::new_float(2, SOME_GEOM)).unwrap();
+    wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+    assert_eq!(vec![2], wm.get_floating_windows());
+    assert!(wm.is_floating(2));
+    assert!(!wm.is_floating(1));
+    assert_eq!(FloatOrTile::Float, wm.get_window_info(2).unwrap().float_or_tile);
+    assert_eq!(SOME_GEOM, wm.get_window_info(2).unwrap().geometry);
+    assert_invariants(&wm);
+
+    // the floating window is above the tiled ones
+    let windows: Vec<Window> = wm.get_window_layout().windows.iter().map(|&(w, _)| w).collect();
+    let floating_index = windows.iter().position(|w| *w == 2).unwrap();
+    assert!(windows.iter().position(|w| *w == 1).unwrap() < floating_index);
+    assert!(windows.iter().position(|w| *w == 3).unwrap() < floating_index);
+
+    wm.toggle_floating(1).unwrap();
+    assert!(wm.is_floating(1));
+    wm.toggle_floating(2).unwrap();
+    assert!(!wm.is_floating(2));
+    assert_eq!(FloatOrTile::Tile, wm.get_window_info(2).unwrap().float_or_tile);
+    assert_invariants(&wm);
+
+    // toggling twice doesn't change whether a window floats
+    wm.toggle_floating(3).unwrap();
+    wm.toggle_floating(3).unwrap();
+    assert!(!wm.is_floating(3));
+    assert_invariants(&wm);
+}
+
+/// moves a floating window.
+pub fn test_set_window_geometry<WM: InvariantCheck + FloatSupport>() {
+    let mut wm = WM::new(SCREEN);
+    wm.add_window(WindowWithInfo::new_float(1, SOME_GEOM)).unwrap();
+    let new_geometry = Geometry {
+        x: 20,
+        y: 30,
+        width: 200,
+        height: 150,
+    };
+    wm.set_window_geometry(1, new_geometry).unwrap();
+    assert_eq!(new_geometry, wm.get_window_info(1).unwrap().geometry);
+    assert!(wm.get_window_layout().windows.contains(&(1, new_geometry)));
+    assert_invariants(&wm);
+}
+
+/// minimises and unminimises windows, a floating window floats at the same
+/// place again.
+pub fn test_minimised_windows<WM: InvariantCheck + FloatSupport + MinimiseSupport>() {
+    let mut wm = WM::new(SCREEN);
+    wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+    wm.add_window(WindowWithInfo::new_float(2, SOME_GEOM)).unwrap();
+    wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+
+    wm.toggle_minimised(3).unwrap();
+    wm.toggle_minimised(2).unwrap();
+    assert_eq!(vec![3, 2], wm.get_minimised_windows());
+    assert!(wm.is_minimised(2));
+    assert!(wm.is_managed(2));
+    assert!(!is_visible(&wm, 2));
+    assert!(!is_visible(&wm, 3));
+    assert!(!wm.is_minimised(10));
+    assert_invariants(&wm);
+
+    wm.toggle_minimised(2).unwrap();
+    assert!(!wm.is_minimised(2));
+    assert!(is_visible(&wm, 2));
+    assert!(wm.is_floating(2));
+    assert_eq!(SOME_GEOM, wm.get_window_info(2).unwrap().geometry);
+    assert_eq!(vec![3], wm.get_minimised_windows());
+    assert_invariants(&wm);
+}
+
+/// makes windows fullscreen, the fullscreen window is the only visible
+/// one.
+pub fn test_fullscreen_window<WM: InvariantCheck + FloatSupport + FullscreenSupport>() {
+    let mut wm = WM::new(SCREEN);
+    wm.add_window(WindowWithInfo::new_tiled(1, SOME_GEOM)).unwrap();
+    wm.add_window(WindowWithInfo::new_float(2, SOME_GEOM)).unwrap();
+    wm.add_window(WindowWithInfo::new_tiled(3, SOME_GEOM)).unwrap();
+    assert_eq!(None, wm.get_fullscreen_window());
+
+    wm.toggle_fullscreen(1).unwrap();
+    assert_eq!(Some(1), wm.get_fullscreen_window());
+    assert_eq!(Some(1), wm.get_focused_window());
+    assert!(wm.get_window_info(1).unwrap().fullscreen);
+    assert_eq!(vec![(1, SCREEN_GEOM)], wm.get_window_layout().windows);
+    assert_invariants(&wm);
+
+    wm.toggle_fullscreen(1).unwrap();
+    assert_eq!(None, wm.get_fullscreen_window());
+    assert!(is_visible(&wm, 3));
+    assert_invariants(&wm);
+
+    // a floating window can be fullscreen too
+    wm.toggle_fullscreen(2).unwrap();
+    assert_eq!(vec![(2, SCREEN_GEOM)], wm.get_window_layout().windows);
+    wm.toggle_fullscreen(2).unwrap();
+    assert!(wm.is_floating(2));
+    assert_invariants(&wm);
+
+    // a window that wants to be fullscreen becomes fullscreen
+    let mut window_with_info = WindowWithInfo::new_tiled(4, SOME_GEOM);
+    window_with_info.fullscreen = true;
+    wm.add_window(window_with_info).unwrap();
+    assert_eq!(Some(4), wm.get_fullscreen_window());
+    assert_invariants(&wm);
+}
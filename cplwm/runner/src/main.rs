@@ -188,12 +188,13 @@ use std::fs::File;
 use std::process;
 
 use cplwm_api::wm::*;
-use cplwm_api::types::{PrevOrNext, WorkspaceIndex};
+use cplwm_api::types::{Direction, FloatOrTile, PrevOrNext, Window, WorkspaceIndex};
 
-use cplwm_x11::{X11Backend, X11Config, X11Error, X11Result};
+use cplwm_x11::{RuleActions, WindowMatcher, WindowRule, X11Backend, X11Config, X11Error,
+                X11Result};
 
 // Import the `WMName` of the window manager you want to run
-use cplwm_assignment::a_fullscreen_wm::WMName;
+use cplwm_assignment::l_events::WMName;
 
 /// The name of the window manager you want to run.
 ///
@@ -225,7 +226,27 @@ pub fn spawn(command: &str) -> X11Result<()> {
     run(process::Command::new("sh").arg("-c").arg(command).spawn())
 }
 
-/// Move the focused window to the given workspace.
+/// Move the given window to the given workspace, without switching to it.
+///
+/// Do nothing when the index is that of the currently active workspace.
+fn move_window_to_workspace(backend: &mut X11Backend<WM>,
+                            window: Window,
+                            index: WorkspaceIndex)
+                            -> X11Result<()> {
+    let current_index = backend.get_wm().get_current_workspace_index();
+    if index != current_index {
+        let wm = backend.get_wm_mut();
+        let window_with_info = try!(wm.get_window_info(window));
+        // Switch first, so the window is not lost when the index is invalid
+        try!(wm.switch_workspace(index));
+        try!(wm.remove_window(window));
+        try!(wm.add_window(window_with_info));
+        try!(wm.switch_workspace(current_index));
+    }
+    Ok(())
+}
+
+/// Move the focused window to the given workspace and switch to it.
 ///
 /// Do nothing when no window is focused or when the index is that of the
 /// currently active workspace.
@@ -234,11 +255,8 @@ fn move_focused_window_to_workspace(backend: &mut X11Backend<WM>,
                                     -> X11Result<()> {
     if let Some(w) = backend.get_wm().get_focused_window() {
         if index != backend.get_wm().get_current_workspace_index() {
-            let wm = backend.get_wm_mut();
-            let window_with_info = try!(wm.get_window_info(w));
-            try!(wm.remove_window(w));
-            try!(wm.switch_workspace(index));
-            try!(wm.add_window(window_with_info));
+            try!(move_window_to_workspace(backend, w, index));
+            try!(run(backend.get_wm_mut().switch_workspace(index)));
         }
     }
     Ok(())
@@ -285,13 +303,15 @@ pub fn main() {
             }
             Ok(())
         },
+        // Focus the previously used window
+        (Alt - XK_Tab) => |backend| run(backend.get_wm_mut().alt_tab()),
         // Focus the next window
-        (Alt - XK_Tab) => |backend| {
+        (Super - XK_Tab) => |backend| {
             backend.get_wm_mut().cycle_focus(PrevOrNext::Next);
             Ok(())
         },
         // Focus the previous window
-        (Alt - Shift - XK_Tab) => |backend| {
+        (Super - Shift - XK_Tab) => |backend| {
             backend.get_wm_mut().cycle_focus(PrevOrNext::Prev);
             Ok(())
         },
@@ -326,6 +346,28 @@ pub fn main() {
             }
             Ok(())
         },
+        // Move the focused window to the scratchpad
+        (Super - Shift - XK_s) => |backend| {
+            if let Some(w) = backend.get_wm().get_focused_window() {
+                try!(backend.get_wm_mut().move_to_scratchpad(w));
+            }
+            Ok(())
+        },
+        // Show or hide the scratchpad
+        (Super - XK_s) => |backend| { backend.get_wm_mut().toggle_scratchpad(); Ok(()) },
+        // Focus the most recent urgent window, on any workspace
+        (Super - XK_u) => |backend| run(backend.get_wm_mut().focus_urgent_window()),
+        // Make the focused window visible on every workspace, or not anymore
+        (Super - XK_a) => |backend| {
+            if let Some(w) = backend.get_wm().get_focused_window() {
+                try!(backend.get_wm_mut().toggle_sticky(w));
+            }
+            Ok(())
+        },
+        // Undo the last change to the layout
+        (Super - XK_z) => |backend| { backend.get_wm_mut().undo(); Ok(()) },
+        // Redo the last undone change to the layout
+        (Super - Shift - XK_z) => |backend| { backend.get_wm_mut().redo(); Ok(()) },
         // Toggle fullscreen
         (Super - XK_f) => |backend| {
             if let Some(w) = backend.get_wm().get_focused_window() {
@@ -333,6 +375,56 @@ pub fn main() {
             }
             Ok(())
         },
+        // Switch to the next layout
+        (Super - XK_space) => |backend| {
+            backend.get_wm_mut().cycle_layout(PrevOrNext::Next);
+            Ok(())
+        },
+        // Switch to the previous layout
+        (Super - Shift - XK_space) => |backend| {
+            backend.get_wm_mut().cycle_layout(PrevOrNext::Prev);
+            Ok(())
+        },
+        // Widen the columns of the scrolling layout
+        (Super - XK_bracketright) => |backend| {
+            backend.get_wm_mut().grow_columns();
+            Ok(())
+        },
+        // Narrow the columns of the scrolling layout
+        (Super - XK_bracketleft) => |backend| {
+            backend.get_wm_mut().shrink_columns();
+            Ok(())
+        },
+        // Grow the master area
+        (Super - XK_equal) => |backend| {
+            backend.get_wm_mut().grow_master();
+            Ok(())
+        },
+        // Shrink the master area
+        (Super - XK_minus) => |backend| {
+            backend.get_wm_mut().shrink_master();
+            Ok(())
+        },
+        // Add a window to the master area
+        (Super - XK_comma) => |backend| {
+            backend.get_wm_mut().increase_master_count();
+            Ok(())
+        },
+        // Remove a window from the master area
+        (Super - XK_period) => |backend| {
+            backend.get_wm_mut().decrease_master_count();
+            Ok(())
+        },
+        // Move the master area to the next side of the screen, clockwise
+        (Super - XK_r) => |backend| {
+            backend.get_wm_mut().cycle_master_position(PrevOrNext::Next);
+            Ok(())
+        },
+        // Mirror the layout
+        (Super - Control - XK_r) => |backend| {
+            backend.get_wm_mut().mirror_master_position();
+            Ok(())
+        },
         // Increase the gap
         (Super - XK_g) => |backend| {
             let current_gap = backend.get_wm().get_gap();
@@ -347,6 +439,36 @@ pub fn main() {
             }
             Ok(())
         },
+        // Focus the window in the given direction
+        (Super - XK_Left) => |backend| run(backend.get_wm_mut().focus_direction(Direction::Left)),
+        (Super - XK_Right) => |backend| {
+            run(backend.get_wm_mut().focus_direction(Direction::Right))
+        },
+        (Super - XK_Up) => |backend| run(backend.get_wm_mut().focus_direction(Direction::Up)),
+        (Super - XK_Down) => |backend| run(backend.get_wm_mut().focus_direction(Direction::Down)),
+        // Swap the focused window with the window in the given direction
+        (Super - Shift - XK_Left) => |backend| {
+            backend.get_wm_mut().swap_direction(Direction::Left);
+            Ok(())
+        },
+        (Super - Shift - XK_Right) => |backend| {
+            backend.get_wm_mut().swap_direction(Direction::Right);
+            Ok(())
+        },
+        (Super - Shift - XK_Up) => |backend| {
+            backend.get_wm_mut().swap_direction(Direction::Up);
+            Ok(())
+        },
+        (Super - Shift - XK_Down) => |backend| {
+            backend.get_wm_mut().swap_direction(Direction::Down);
+            Ok(())
+        },
+        // Focus the next output
+        (Super - XK_o) => |backend| {
+            let outputs = backend.get_wm().get_outputs().len();
+            let next = (backend.get_wm().get_current_output_index() + 1) % outputs;
+            run(backend.get_wm_mut().focus_output(next))
+        },
         // We could use a loop for this
         (Super - XK_1) => |backend| run(backend.get_wm_mut().switch_workspace(0)),
         (Super - XK_2) => |backend| run(backend.get_wm_mut().switch_workspace(1)),
@@ -365,6 +487,52 @@ pub fn main() {
         (Super - RMB) => |backend, ev| backend.mouse_resize_window(ev.subwindow)
     };
 
+    // Give every output (monitor) its own layout area
+    config.outputs_changed = Some(Box::new(|backend, outputs| {
+        backend.get_wm_mut().set_outputs(outputs);
+        Ok(())
+    }));
+
+    // Rules for new windows, the last matching rule wins
+    config.rules = vec![
+        // The volume control floats
+        WindowRule {
+            matcher: WindowMatcher { class: Some("Pavucontrol"), ..Default::default() },
+            actions: RuleActions { float_or_tile: Some(FloatOrTile::Float), ..Default::default() },
+        },
+        // So do the toolboxes of the GIMP
+        WindowRule {
+            matcher: WindowMatcher { role: Some("gimp-toolbox"), ..Default::default() },
+            actions: RuleActions { float_or_tile: Some(FloatOrTile::Float), ..Default::default() },
+        },
+        // The dashboard opens in the last workspace, without stealing focus
+        WindowRule {
+            matcher: WindowMatcher { title: Some("Dashboard"), ..Default::default() },
+            actions: RuleActions {
+                workspace: Some(3),
+                focus: Some(false),
+                ..Default::default()
+            },
+        },
+    ];
+    config.move_to_workspace = Some(Box::new(move_window_to_workspace));
+
+    // Log the changes of the window manager, a status bar could be updated
+    // here as well
+    config.event_hooks = vec![Box::new(|_, event| {
+                                  info!("{:?}", event);
+                                  Ok(())
+                              })];
+
+    // The window manager implements all the optional traits the backend
+    // can use
+    config.support.enable_window_properties();
+    config.support.enable_tab_bars();
+    config.support.enable_urgency();
+    config.support.enable_sticky();
+    config.support.enable_events();
+    config.support.enable_invariants();
+
     X11Backend::start(WM::new, config).unwrap();
 
     info!("The window manager has stopped");
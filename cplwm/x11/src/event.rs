@@ -3,6 +3,8 @@
 use std::mem::zeroed;
 use std::os::raw::{c_int, c_uint, c_ushort};
 
+use rustc_serialize::json;
+
 use cplwm_api::types::{Geometry, WindowWithInfo};
 use cplwm_api::wm::{FloatSupport, FullscreenSupport, MinimiseSupport, WindowManager};
 
@@ -17,10 +19,18 @@ impl<WM> X11Backend<WM>
 {
     /// Run the main event loop.
     ///
-    /// Calls [`handler`] for each event. When the handler modified the window
-    /// manager, [`apply_window_layout`] is called to apply the changes.
+    /// Calls [`handler`] for each event and passes the events of the window
+    /// manager to the event hooks with [`dispatch_events`]. In debug builds,
+    /// the invariants of the window manager are checked with
+    /// [`check_invariants`]. Both only do something when the corresponding
+    /// trait is enabled in the [`WMSupport`] of the config. When the handler
+    /// or the hooks modified the window manager, [`apply_window_layout`] is
+    /// called to apply the changes.
     ///
     /// [`handler`]: struct.X11Backend.html#method.handler
+    /// [`dispatch_events`]: struct.X11Backend.html#method.dispatch_events
+    /// [`check_invariants`]: struct.X11Backend.html#method.check_invariants
+    /// [`WMSupport`]: struct.WMSupport.html
     /// [`apply_window_layout`]: struct.X11Backend.html#method.apply_window_layout
     pub fn run(&mut self, config: X11Config<WM>) -> X11Result<()> {
         let mut event: xlib::XEvent = unsafe { zeroed() };
@@ -41,6 +51,10 @@ impl<WM> X11Backend<WM>
             // mostly do. So instead of swallowing errors, we crash, which
             // leads to quicker discovery of bugs.
             try!(self.handler(&event, &config));
+            try!(self.dispatch_events(&config));
+            if cfg!(debug_assertions) {
+                self.check_invariants(&event);
+            }
             // Only if the window manager was modified, i.e. a mutable borrow
             // occurred using `get_wm_mut`, do we have to apply the changes.
             if self.wm_modified {
@@ -51,6 +65,52 @@ impl<WM> X11Backend<WM>
     }
 
 
+    /// Pass the events of the window manager to the event hooks of the
+    /// config.
+    ///
+    /// The events are taken from the window manager without
+    /// [`get_wm_mut`], as taking them does not modify the window layout.
+    ///
+    /// [`get_wm_mut`]: struct.X11Backend.html#method.get_wm_mut
+    pub fn dispatch_events(&mut self, config: &X11Config<WM>) -> X11Result<()> {
+        if let Some(take_events) = self.support.events {
+            for event in take_events(&mut self.wm) {
+                debug!("WM event: {:?}", event);
+                for hook in &config.event_hooks {
+                    try!(hook(self, event));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Check the invariants of the window manager after the given event was
+    /// handled.
+    ///
+    /// When some of them don't hold, a report is logged with the violated
+    /// invariants, the event and the serialised state of the window
+    /// manager, so the state can be inspected or restored to reproduce the
+    /// problem. The window manager keeps running.
+    pub fn check_invariants(&self, event: &xlib::XEvent) {
+        let violations = match self.support.invariants {
+            Some(check_invariants) => check_invariants(&self.wm),
+            None => return,
+        };
+        if violations.is_empty() {
+            return;
+        }
+        error!("{} invariant(s) violated after {}:",
+               violations.len(),
+               event_name(event));
+        for violation in violations.iter() {
+            error!("  {}", violation);
+        }
+        match json::encode(&self.wm) {
+            Ok(state) => error!("Window manager state: {}", state),
+            Err(err) => error!("Encoding the state failed: {:?}", err),
+        }
+    }
+
     /// Main event handler.
     ///
     /// See the implementation for more information.
@@ -103,6 +163,15 @@ impl<WM> X11Backend<WM>
                         } else {
                             Some(xev.window)
                         };
+                        // A click on a tab bar focuses the window of the
+                        // clicked tab.
+                        let to_focus = match to_focus {
+                            Some(w) if self.is_tab_bar(w) => {
+                                self.get_tab_at(xev.x_root, xev.y_root)
+                                    .or(self.get_wm().get_focused_window())
+                            }
+                            _ => to_focus,
+                        };
                         if self.get_wm().get_focused_window() != to_focus {
                             try!(self.get_wm_mut().focus_window(to_focus));
                         }
@@ -177,12 +246,38 @@ impl<WM> X11Backend<WM>
                     center_geometry(&mut geometry, &screen);
                     let float_or_tile = self.wants_to_float_or_tile(xev.window);
                     let fullscreen = self.wants_to_be_fullscreen(xev.window);
+                    let mut window_with_info =
+                        WindowWithInfo::new(xev.window, geometry, float_or_tile, fullscreen);
+                    window_with_info.properties = self.get_window_properties(xev.window);
+                    // Apply the rules of the config, the border width must
+                    // be known before the window is added to the backend.
+                    let actions = self.get_window_rule_actions(xev.window,
+                                                               &window_with_info.properties,
+                                                               config);
+                    if let Some(border_width) = actions.border_width {
+                        self.border_widths.insert(xev.window, border_width);
+                    }
                     self.add_window(xev.window);
-                    try!(self.get_wm_mut()
-                        .add_window(WindowWithInfo::new(xev.window,
-                                                        geometry,
-                                                        float_or_tile,
-                                                        fullscreen)));
+                    try!(self.manage_window(window_with_info, &actions, config));
+                }
+            }
+            // A property of a window changed, e.g. its title. When it is one
+            // of the properties the window manager keeps track of, pass the
+            // new properties to the window manager.
+            xlib::PropertyNotify => {
+                let xev: xlib::XPropertyEvent = From::from(event);
+                if self.get_wm().is_managed(xev.window) &&
+                   (xev.atom == xlib::XA_WM_HINTS || xev.atom == self.get_atom("_NET_WM_STATE")) {
+                    try!(self.update_window_urgency(xev.window));
+                }
+                let tracked_atoms = [xlib::XA_WM_NAME,
+                                     xlib::XA_WM_CLASS,
+                                     xlib::XA_WM_TRANSIENT_FOR,
+                                     self.get_atom("_NET_WM_NAME"),
+                                     self.get_atom("_NET_WM_PID"),
+                                     self.get_atom("WM_WINDOW_ROLE")];
+                if self.get_wm().is_managed(xev.window) && tracked_atoms.contains(&xev.atom) {
+                    try!(self.update_window_properties(xev.window));
                 }
             }
             // The keyboard mapping was changed, regrab the keys.
@@ -206,14 +301,21 @@ impl<WM> X11Backend<WM>
             // A window is unmapped, i.e. removed from the window manager.
             // Hiding a window also generates this event, so ignore it when
             // the window manager caused it. When the window manager hides a
-            // window, it is stored in the `hidden` field.
+            // window, it is stored in the `hidden` field. A client that
+            // withdraws a hidden window sends a synthetic event instead, see
+            // section 4.1.4 in https://tronche.com/gui/x/icccm/sec-4.html
             xlib::UnmapNotify => {
                 let xev: xlib::XUnmapEvent = From::from(event);
                 if self.get_wm().is_managed(xev.window) {
                     // Only remove the window when we didn't hide it.
-                    if !self.hidden.contains(&xev.window) {
+                    if !self.hidden.contains(&xev.window) || xev.send_event == xlib::True {
                         try!(self.get_wm_mut().remove_window(xev.window));
                         self.remove_window(xev.window);
+                        // The window is withdrawn, so it must not be added
+                        // again when the window manager restarts, see
+                        // `find_visible_windows`. A destroyed window has no
+                        // properties left, so DestroyNotify doesn't need this.
+                        self.set_wm_state(xev.window, WindowState::Withdrawn);
                     }
                 }
                 // Be a good parent and reap your zombie children. Children,
@@ -240,6 +342,7 @@ impl<WM> X11Backend<WM>
                     return Ok(());
                 }
                 let geometry = try!(self.get_window_geometry(xev.window));
+                let border_width = self.get_border_width(xev.window);
                 if self.get_wm().is_floating(xev.window) {
                     let mask = xev.value_mask as c_ushort;
                     let new_geometry = Geometry {
@@ -258,12 +361,12 @@ impl<WM> X11Backend<WM>
                         // do this, some windows will keep sending these
                         // requests and slowly shrink.
                         width: if mask & xlib::CWWidth != 0 {
-                            xev.width as c_uint + 2 * WINDOW_BORDER_WIDTH
+                            xev.width as c_uint + 2 * border_width
                         } else {
                             geometry.width
                         },
                         height: if mask & xlib::CWHeight != 0 {
-                            xev.height as c_uint + 2 * WINDOW_BORDER_WIDTH
+                            xev.height as c_uint + 2 * border_width
                         } else {
                             geometry.height
                         },
@@ -280,9 +383,9 @@ impl<WM> X11Backend<WM>
                             window: xev.window,
                             x: geometry.x,
                             y: geometry.y,
-                            width: (geometry.width - 2 * WINDOW_BORDER_WIDTH) as c_int,
-                            height: (geometry.height - 2 * WINDOW_BORDER_WIDTH) as c_int,
-                            border_width: WINDOW_BORDER_WIDTH as c_int,
+                            width: inner_size(geometry.width, border_width),
+                            height: inner_size(geometry.height, border_width),
+                            border_width: border_width as c_int,
                             above: 0,
                             override_redirect: xlib::False,
                         }
@@ -305,6 +408,16 @@ impl<WM> X11Backend<WM>
                     let screen = self.get_screen();
                     // Update the window manager with the changed screen.
                     self.get_wm_mut().resize_screen(screen);
+                    // The outputs have probably changed as well.
+                    try!(self.update_outputs(config));
+                }
+            }
+            // A tab bar was exposed, redraw it. Only the last one of a
+            // series of expose events is handled.
+            xlib::Expose => {
+                let xev: xlib::XExposeEvent = From::from(event);
+                if xev.count == 0 {
+                    self.redraw_tab_bar(xev.window);
                 }
             }
             // Messages sent by client, i.e. applications
@@ -21,7 +21,6 @@ use cplwm_api::types::Window;
 use cplwm_api::wm::{FloatSupport, FullscreenSupport, MinimiseSupport, WindowManager};
 
 use std::os::raw::{c_int, c_long};
-use std::error;
 
 use super::*;
 
@@ -33,7 +32,9 @@ pub const SUPPORTED_ATOM_NAMES: &'static [&'static str] = &["_NET_ACTIVE_WINDOW"
                                                             "_NET_CLIENT_LIST_STACKING",
                                                             "_NET_WM_STATE",
                                                             "_NET_WM_STATE_FULLSCREEN",
-                                                            "_NET_WM_STATE_HIDDEN"];
+                                                            "_NET_WM_STATE_HIDDEN",
+                                                            "_NET_WM_STATE_STICKY",
+                                                            "_NET_WM_STATE_DEMANDS_ATTENTION"];
 
 /// The actions windows are allowed to perform.
 pub const ALLOWED_ACTIONS_ATOM_NAMES: &'static [&'static str] = &["_NET_WM_ACTION_MOVE",
@@ -140,6 +141,29 @@ impl<WM> X11Backend<WM>
                                       Some(focused_window.unwrap_or(0) as c_int).into_iter());
     }
 
+    /// Remove `_NET_WM_STATE_DEMANDS_ATTENTION` from the [`_NET_WM_STATE`]
+    /// property of the given window, if it is there. Called when the window
+    /// is focused.
+    ///
+    /// [`_NET_WM_STATE`]: https://developer.gnome.org/wm-spec/#idm140200472615568
+    pub fn clear_demands_attention(&self, window: Window) {
+        let net_wm_state_atom = self.get_atom("_NET_WM_STATE");
+        let net_wm_state_demands_attention_atom =
+            self.get_atom("_NET_WM_STATE_DEMANDS_ATTENTION") as c_int;
+        let existing_states = self.get_window_property32(window, net_wm_state_atom)
+            .unwrap_or_default();
+        if existing_states.contains(&net_wm_state_demands_attention_atom) {
+            self.change_window_property32(window,
+                                          net_wm_state_atom,
+                                          xlib::XA_ATOM,
+                                          xlib::PropModeReplace,
+                                          existing_states.into_iter()
+                                              .filter(|state| {
+                                                  *state != net_wm_state_demands_attention_atom
+                                              }));
+        }
+    }
+
     /// Private helper function for `handle_ewmh_client_message`.
     fn net_wm_state_toggler<F, E>(&mut self,
                                   window: Window,
@@ -150,7 +174,7 @@ impl<WM> X11Backend<WM>
                                   toggle_function: F)
                                   -> X11Result<()>
         where F: Fn(&mut X11Backend<WM>, Window) -> Result<(), E>,
-              E: Into<X11Error> + error::Error + 'static
+              X11Error: From<E>
     {
         if data.contains(&(net_wm_state_atom as c_long)) {
             let existing_state_pos = existing_states.iter()
@@ -189,7 +213,8 @@ impl<WM> X11Backend<WM>
     ///
     /// * [`_NET_ACTIVE_WINDOW`]
     /// * [`_NET_CLOSE_WINDOW`]
-    /// * [`_NET_WM_STATE`]: only `_NET_WM_STATE_FULLSCREEN` and `_NET_WM_STATE_HIDDEN`.
+    /// * [`_NET_WM_STATE`]: only `_NET_WM_STATE_FULLSCREEN`, `_NET_WM_STATE_HIDDEN`,
+    ///   `_NET_WM_STATE_STICKY` and `_NET_WM_STATE_DEMANDS_ATTENTION`.
     ///
     /// [`XClientMessageEvent`]: ../x11_dl/xlib/struct.XClientMessageEvent.html
     /// [`_NET_ACTIVE_WINDOW`]: https://developer.gnome.org/wm-spec/#idm140200472702304
@@ -251,6 +276,31 @@ impl<WM> X11Backend<WM>
                                            |backend, window| {
                                                backend.get_wm_mut().toggle_minimised(window)
                                            }));
+            if let Some((_, toggle_sticky)) = self.support.sticky {
+                let net_wm_state_sticky_atom = self.get_atom("_NET_WM_STATE_STICKY");
+                try!(self.net_wm_state_toggler(xev.window,
+                                               net_wm_state_sticky_atom,
+                                               &mut existing_states,
+                                               &data,
+                                               action,
+                                               |backend, window| {
+                                                   toggle_sticky(backend.get_wm_mut(), window)
+                                               }));
+            }
+            if let Some((_, set_urgent)) = self.support.urgency {
+                let net_wm_state_demands_attention_atom =
+                    self.get_atom("_NET_WM_STATE_DEMANDS_ATTENTION");
+                try!(self.net_wm_state_toggler(xev.window,
+                                               net_wm_state_demands_attention_atom,
+                                               &mut existing_states,
+                                               &data,
+                                               action,
+                                               |backend, window| {
+                                                   // The property is changed already
+                                                   let urgent = backend.is_window_urgent(window);
+                                                   set_urgent(backend.get_wm_mut(), window, urgent)
+                                               }));
+            }
         }
         Ok(())
     }
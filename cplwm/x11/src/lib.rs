@@ -67,6 +67,9 @@ mod input;
 mod macros;
 mod methods;
 mod mouse;
+mod rules;
+mod support;
+mod tab_bar;
 mod types;
 mod util;
 
@@ -76,15 +79,20 @@ pub use self::input::*;
 pub use self::macros::*;
 pub use self::methods::*;
 pub use self::mouse::*;
+pub use self::rules::*;
+pub use self::support::*;
+pub use self::tab_bar::*;
 pub use self::types::*;
 pub use self::util::*;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::ffi::CString;
 use std::os::raw::{c_int, c_long, c_uint};
 use std::ptr::{null, null_mut};
 
 use cplwm_api::wm::{FloatSupport, FullscreenSupport, MinimiseSupport, WindowManager};
-use cplwm_api::types::{Geometry, Screen, Window, WindowLayout, WindowWithInfo};
+use cplwm_api::types::{Geometry, Screen, TabBar, Window, WindowLayout, WindowProperties,
+                       WindowWithInfo};
 
 use x11_dl::xlib;
 
@@ -101,7 +109,8 @@ const ROOT_MASK: XEventMask =
 /// The event mask for client windows.
 ///
 /// This controls which client window events the event loop will receive.
-const CLIENT_MASK: XEventMask = xlib::StructureNotifyMask | xlib::EnterWindowMask;
+const CLIENT_MASK: XEventMask =
+    xlib::StructureNotifyMask | xlib::EnterWindowMask | xlib::PropertyChangeMask;
 
 /// The X11 Backend.
 ///
@@ -150,6 +159,22 @@ pub struct X11Backend<WM> {
     focused_border_color: xlib::XColor,
     /// Cached unfocused border color pixel.
     unfocused_border_color: xlib::XColor,
+    /// Cached urgent border color pixel.
+    urgent_border_color: xlib::XColor,
+    /// The windows that have the urgent border color. We need this to
+    /// restore the border of windows that are no longer urgent.
+    urgent: HashSet<Window>,
+    /// The border widths set by the rules of the config. Windows that are
+    /// not in here have a border of `WINDOW_BORDER_WIDTH`.
+    border_widths: HashMap<Window, c_uint>,
+    /// The tab bars that are drawn, each with the window it is drawn in.
+    tab_bars: Vec<(Window, TabBar)>,
+    /// The graphics context used to draw the tab bars.
+    tab_bar_gc: xlib::GC,
+    /// The font of the tab bars, null when it could not be loaded.
+    tab_bar_font: *mut xlib::XFontStruct,
+    /// The optional traits of the window manager, copied from the config.
+    support: WMSupport<WM>,
 }
 
 /// Access to the window manager.
@@ -173,6 +198,10 @@ impl<WM> Drop for X11Backend<WM> {
     /// Close the connection to the display when the backend is stopped.
     fn drop(&mut self) {
         unsafe {
+            if !self.tab_bar_font.is_null() {
+                (self.xlib.XFreeFont)(self.display, self.tab_bar_font);
+            }
+            (self.xlib.XFreeGC)(self.display, self.tab_bar_gc);
             (self.xlib.XCloseDisplay)(self.display);
         }
     }
@@ -232,6 +261,17 @@ impl<WM> X11Backend<WM>
             allocate_color(display, &xlib, config.focused_border_color, colormap).unwrap();
         let unfocused_border_color =
             allocate_color(display, &xlib, config.unfocused_border_color, colormap).unwrap();
+        let urgent_border_color =
+            allocate_color(display, &xlib, config.urgent_border_color, colormap).unwrap();
+
+        // The tab bars use the "fixed" font, which every X server has. When
+        // it can't be loaded anyway, the default font of the GC is used.
+        let tab_bar_gc = unsafe { (xlib.XCreateGC)(display, root_window, 0, null_mut()) };
+        let font_name = CString::new("fixed").unwrap();
+        let tab_bar_font = unsafe { (xlib.XLoadQueryFont)(display, font_name.as_ptr()) };
+        if !tab_bar_font.is_null() {
+            unsafe { (xlib.XSetFont)(display, tab_bar_gc, (*tab_bar_font).fid) };
+        }
 
         X11Backend {
             xlib: xlib,
@@ -246,6 +286,13 @@ impl<WM> X11Backend<WM>
             managed: Vec::new(),
             focused_border_color: focused_border_color,
             unfocused_border_color: unfocused_border_color,
+            urgent_border_color: urgent_border_color,
+            urgent: HashSet::new(),
+            border_widths: HashMap::new(),
+            tab_bars: Vec::new(),
+            tab_bar_gc: tab_bar_gc,
+            tab_bar_font: tab_bar_font,
+            support: config.support,
         }
     }
 
@@ -268,6 +315,10 @@ impl<WM> X11Backend<WM>
         // state of the WM.
         self.restore_state();
 
+        // Pass the outputs before adding the windows, so they are added to
+        // the right output.
+        try!(self.update_outputs(config));
+
         // In case the WM has been shut down and restarted, remove all
         // windows managed by the WM that are no longer visible.
         let visible_windows = self.find_visible_windows();
@@ -281,6 +332,11 @@ impl<WM> X11Backend<WM>
         // the WM has been shut down and restarted, add all windows that
         // have been added since the shutdown.
         for visible_window in visible_windows {
+            let properties = self.get_window_properties(visible_window);
+            let actions = self.get_window_rule_actions(visible_window, &properties, config);
+            if let Some(border_width) = actions.border_width {
+                self.border_widths.insert(visible_window, border_width);
+            }
             // Make sure we grabbed the input and events
             self.add_window(visible_window);
 
@@ -288,11 +344,13 @@ impl<WM> X11Backend<WM>
                 let geometry = try!(self.get_window_geometry(visible_window));
                 let float_or_tile = self.wants_to_float_or_tile(visible_window);
                 let fullscreen = self.wants_to_be_fullscreen(visible_window);
-                try!(self.get_wm_mut()
-                    .add_window(WindowWithInfo::new(visible_window,
-                                                    geometry,
-                                                    float_or_tile,
-                                                    fullscreen)));
+                let mut window_with_info =
+                    WindowWithInfo::new(visible_window, geometry, float_or_tile, fullscreen);
+                window_with_info.properties = properties;
+                try!(self.manage_window(window_with_info, &actions, config));
+            } else {
+                // The properties may have changed while we were restarting
+                try!(self.update_window_properties(visible_window));
             }
         }
 
@@ -311,7 +369,18 @@ impl<WM> X11Backend<WM>
         self.grab_buttons(&config.button_bindings);
 
         // EWMH support
-        self.set_net_supported(SUPPORTED_ATOM_NAMES.iter().map(|name| *name));
+        let sticky = self.support.sticky.is_some();
+        let urgency = self.support.urgency.is_some();
+        self.set_net_supported(SUPPORTED_ATOM_NAMES.iter()
+            .map(|name| *name)
+            .filter(|name| match *name {
+                "_NET_WM_STATE_STICKY" => sticky,
+                "_NET_WM_STATE_DEMANDS_ATTENTION" => urgency,
+                _ => true,
+            }));
+
+        // Report the windows that were added while starting.
+        try!(self.dispatch_events(config));
 
         // Apply the layout when the state was restored. Windows could have
         // moved in the meantime.
@@ -324,6 +393,141 @@ impl<WM> X11Backend<WM>
         Ok(())
     }
 
+    /// Execute the `outputs_changed` command of the config, if any, with the
+    /// current outputs.
+    fn update_outputs(&mut self, config: &X11Config<WM>) -> X11Result<()> {
+        if let Some(ref command) = config.outputs_changed {
+            let outputs = self.get_outputs();
+            try!(command(self, outputs));
+        }
+        Ok(())
+    }
+
+    /// Return the actions of the rules of the config that match the given
+    /// window with the given properties.
+    fn get_window_rule_actions(&self,
+                               window: Window,
+                               properties: &WindowProperties,
+                               config: &X11Config<WM>)
+                               -> RuleActions {
+        if config.rules.is_empty() {
+            return RuleActions::default();
+        }
+        let window_types = self.get_window_types(window);
+        let actions = get_rule_actions(&config.rules, properties, &window_types);
+        if actions != RuleActions::default() {
+            debug!("Rule actions for {}: {:?}", window, actions);
+        }
+        actions
+    }
+
+    /// Pass a new window to the window manager after applying the given
+    /// rule actions.
+    ///
+    /// The float, fullscreen and geometry actions change the given
+    /// `WindowWithInfo`, the workspace and focus actions are applied after
+    /// the window was added. The border width action is not applied here,
+    /// because it has to be known before the window is added to the backend.
+    fn manage_window(&mut self,
+                     window_with_info: WindowWithInfo,
+                     actions: &RuleActions,
+                     config: &X11Config<WM>)
+                     -> X11Result<()> {
+        let mut window_with_info = window_with_info;
+        let window = window_with_info.window;
+        if let Some(float_or_tile) = actions.float_or_tile {
+            window_with_info.float_or_tile = float_or_tile;
+        }
+        if let Some(fullscreen) = actions.fullscreen {
+            window_with_info.fullscreen = fullscreen;
+        }
+        if let Some(geometry) = actions.geometry {
+            window_with_info.geometry = geometry;
+        }
+        let prev_focused_window = self.get_wm().get_focused_window();
+        try!(self.get_wm_mut().add_window(window_with_info));
+        if let Some(index) = actions.workspace {
+            if let Some(ref command) = config.move_to_workspace {
+                try!(command(self, window, index));
+            }
+        }
+        match actions.focus {
+            Some(true) => try!(self.get_wm_mut().focus_window(Some(window))),
+            Some(false) => try!(self.get_wm_mut().focus_window(prev_focused_window)),
+            None => (),
+        }
+        if let Some((is_sticky, toggle_sticky)) = self.support.sticky {
+            if self.wants_to_be_sticky(window) && !is_sticky(self.get_wm(), window) {
+                try!(toggle_sticky(self.get_wm_mut(), window));
+            }
+        }
+        self.update_window_urgency(window)
+    }
+
+    /// Read the properties of the given managed window again and pass them
+    /// to the window manager when they changed.
+    ///
+    /// The window manager is only borrowed mutably when something changed,
+    /// so e.g. a terminal updating its title with the same title does not
+    /// cause the window layout to be applied again.
+    fn update_window_properties(&mut self, window: Window) -> X11Result<()> {
+        if let Some((get_properties, set_properties)) = self.support.window_properties {
+            let properties = self.get_window_properties(window);
+            let changed = match get_properties(self.get_wm(), window) {
+                Some(ref current_properties) => *current_properties != properties,
+                None => true,
+            };
+            if changed {
+                trace!("update_window_properties: {} {:?}", window, properties);
+                try!(set_properties(self.get_wm_mut(), window, properties));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check whether the given managed window demands attention and pass it
+    /// to the window manager when it changed.
+    ///
+    /// Like in `update_window_properties`, the window manager is only
+    /// borrowed mutably when something changed.
+    fn update_window_urgency(&mut self, window: Window) -> X11Result<()> {
+        if let Some((get_urgent_windows, set_urgent)) = self.support.urgency {
+            let urgent = self.is_window_urgent(window);
+            if urgent != get_urgent_windows(self.get_wm()).contains(&window) {
+                trace!("update_window_urgency: {} {}", window, urgent);
+                try!(set_urgent(self.get_wm_mut(), window, urgent));
+            }
+        }
+        Ok(())
+    }
+
+    /// Give the urgent windows of the window manager the urgent border
+    /// color, and the windows that are no longer urgent the color they
+    /// should have according to the given focused window.
+    fn update_urgent_borders(&mut self, focused_window: Option<Window>) {
+        let mut urgent = HashSet::new();
+        if let Some((get_urgent_windows, _)) = self.support.urgency {
+            urgent.extend(get_urgent_windows(self.get_wm()));
+        }
+        for window in self.urgent.difference(&urgent) {
+            // Setting the border color on an unmapped window causes an error.
+            if self.managed.contains(window) {
+                let color = if Some(*window) == focused_window {
+                    self.focused_border_color
+                } else {
+                    self.unfocused_border_color
+                };
+                self.set_window_border_color(*window, color);
+            }
+        }
+        for window in urgent.difference(&self.urgent) {
+            if self.managed.contains(window) {
+                self.set_window_border_color(*window, self.urgent_border_color);
+            }
+        }
+        self.urgent = urgent;
+    }
+
     /// Update the X server so that the new window layout is reflected.
     ///
     /// The new window layout is compared with the old one. Windows that are
@@ -363,6 +567,10 @@ impl<WM> X11Backend<WM>
             _ => (),
         }
 
+        // Urgent windows get a border color of their own, unless they are
+        // focused.
+        self.update_urgent_borders(new_window_layout.focused_window);
+
         // Update the stack order. Dumb: also restacks when windows were only
         // added and/or removed.
         if prev_windows != new_windows {
@@ -381,6 +589,9 @@ impl<WM> X11Backend<WM>
             }
         }
 
+        // The tab bars depend on the window layout as well
+        self.update_tab_bars();
+
         // Ignore any enter/leave events we may have generated while applying
         // the window layout.
         self.clear_events(xlib::EnterWindowMask | xlib::LeaveWindowMask);
@@ -409,7 +620,8 @@ impl<WM> X11Backend<WM>
         }
         self.set_client_list(self.managed.iter());
         self.set_allowed_actions(window, ALLOWED_ACTIONS_ATOM_NAMES.iter().map(|name| *name));
-        self.set_window_border_width(window, WINDOW_BORDER_WIDTH);
+        let border_width = self.get_border_width(window);
+        self.set_window_border_width(window, border_width);
         self.set_window_border_color(window, self.unfocused_border_color);
     }
 
@@ -430,6 +642,9 @@ impl<WM> X11Backend<WM>
             self.managed.remove(i);
             self.set_client_list(self.managed.iter());
         }
+        self.border_widths.remove(&window);
+        self.urgent.remove(&window);
+        self.hidden.remove(&window);
     }
 
     /// Ask the X server to reveal a window.
@@ -522,6 +737,7 @@ impl<WM> X11Backend<WM>
         }
 
         self.set_window_border_color(window, self.focused_border_color);
+        self.clear_demands_attention(window);
 
         // Advertise via EWMH that the window is focused
         self.set_active_window(Some(window));
@@ -532,9 +748,11 @@ impl<WM> X11Backend<WM>
         trace!("unfocus_window {}", window);
 
         // Setting the border color or ungrabbing on an unmapped window causes
-        // an error.
+        // an error. An urgent window keeps its border color.
         if self.managed.contains(&window) {
-            self.set_window_border_color(window, self.unfocused_border_color);
+            if !self.urgent.contains(&window) {
+                self.set_window_border_color(window, self.unfocused_border_color);
+            }
             self.set_button_grab(true, window, xlib::AnyButton as XButton, xlib::AnyModifier);
         }
 
@@ -600,6 +818,14 @@ impl<WM> X11Backend<WM>
 
     }
 
+    /// Return the border width of the given window.
+    ///
+    /// This is `WINDOW_BORDER_WIDTH`, unless a rule of the config set another
+    /// border width for the window.
+    pub fn get_border_width(&self, window: Window) -> c_uint {
+        *self.border_widths.get(&window).unwrap_or(&WINDOW_BORDER_WIDTH)
+    }
+
     /// Ask the X server to resize/move the window so it matches the given
     /// `Geometry`.
     pub fn set_window_geometry(&mut self, window: Window, new_geometry: Geometry) {
@@ -609,12 +835,13 @@ impl<WM> X11Backend<WM>
             return;
         }
         let Geometry { x, y, width, height } = new_geometry;
+        let border_width = self.get_border_width(window);
         let mut changes = xlib::XWindowChanges {
             x: x,
             y: y,
-            width: (width - 2 * WINDOW_BORDER_WIDTH) as c_int,
-            height: (height - 2 * WINDOW_BORDER_WIDTH) as c_int,
-            border_width: WINDOW_BORDER_WIDTH as c_int,
+            width: inner_size(width, border_width),
+            height: inner_size(height, border_width),
+            border_width: border_width as c_int,
             sibling: 0,
             stack_mode: 0,
         };
@@ -7,17 +7,18 @@ use std::env;
 use std::ffi::{CStr, CString};
 use std::fs;
 use std::mem::{transmute, zeroed};
-use std::os::raw::{c_int, c_long, c_uchar, c_uint, c_ulong};
+use std::os::raw::{c_char, c_int, c_long, c_uchar, c_uint, c_ulong};
 use std::slice;
 use std::sync::Mutex;
 
-use cplwm_api::types::{FloatOrTile, Screen, Window};
+use cplwm_api::types::{FloatOrTile, Geometry, Screen, Window, WindowProperties};
 use cplwm_api::wm::WindowManager;
 
 use super::*;
 
 use exec::execvp;
 use libc::wchar_t;
+use x11_dl::xinerama;
 use x11_dl::xlib;
 
 lazy_static! {
@@ -51,6 +52,15 @@ impl<WM: WindowManager> X11Backend<WM> {
         })
     }
 
+    /// Return the name of the given X11 atom.
+    ///
+    /// Return `None` when the atom does not exist or when its name could not
+    /// be converted to a valid `String`.
+    pub fn get_atom_name(&self, atom: xlib::Atom) -> Option<String> {
+        let name_ptr = unsafe { (self.xlib.XGetAtomName)(self.display, atom) };
+        self.take_x_string(name_ptr)
+    }
+
     /// Get the 32-bit items associated with the window's property.
     ///
     /// See [`XGetWindowProperty`].
@@ -263,6 +273,12 @@ impl<WM: WindowManager> X11Backend<WM> {
     ///
     /// This method is used to add existing visible windows to the window
     /// manager when it just started.
+    ///
+    /// Windows hidden by the window manager, e.g. minimised windows or
+    /// hidden scratchpad windows, are unmapped but have the `Iconic`
+    /// `WM_STATE`. They are considered visible too, so they survive a
+    /// restart. Windows withdrawn by their client get the `Withdrawn`
+    /// `WM_STATE` when they are removed, so they are not added again.
     pub fn find_visible_windows(&mut self) -> Vec<Window> {
         let mut root_return = 0;
         let mut parent_return = 0;
@@ -296,7 +312,8 @@ impl<WM: WindowManager> X11Backend<WM> {
                 trace!("Invisible window: {}", name);
                 return false;
             }
-            if window_attrs.map_state == xlib::IsViewable {
+            if window_attrs.map_state == xlib::IsViewable ||
+               self.get_wm_state(*window) == Some(WindowState::Iconic) {
                 true
             } else {
                 trace!("Invisible window: {}", name);
@@ -499,6 +516,120 @@ impl<WM: WindowManager> X11Backend<WM> {
         })
     }
 
+    /// Retrieve the instance and class of the given window, in that order.
+    ///
+    /// Uses [`XGetClassHint`], which reads the `WM_CLASS` property. Each part
+    /// is `None` when it is missing or when it could not be converted to a
+    /// valid `String`.
+    ///
+    /// [`XGetClassHint`]:
+    /// https://tronche.com/gui/x/xlib/ICC/client-to-window-manager/XGetClassHint.html
+    pub fn get_window_class(&self, window: Window) -> (Option<String>, Option<String>) {
+        let mut class_hint: xlib::XClassHint = unsafe { zeroed() };
+        let status = unsafe { (self.xlib.XGetClassHint)(self.display, window, &mut class_hint) };
+        if status == 0 {
+            return (None, None);
+        }
+        let instance = self.take_x_string(class_hint.res_name);
+        let class = self.take_x_string(class_hint.res_class);
+        (instance, class)
+    }
+
+    /// Retrieve the role of the given window, stored in its `WM_WINDOW_ROLE`
+    /// property.
+    ///
+    /// Return `None` when the window has no role or when it could not be
+    /// converted to a valid `String`.
+    pub fn get_window_role(&self, window: Window) -> Option<String> {
+        let wm_window_role_atom = self.get_atom("WM_WINDOW_ROLE");
+        let mut text_prop_return: xlib::XTextProperty = unsafe { zeroed() };
+        let status = unsafe {
+            (self.xlib.XGetTextProperty)(self.display,
+                                         window,
+                                         &mut text_prop_return,
+                                         wm_window_role_atom)
+        };
+        if status == 0 {
+            return None;
+        }
+        let maybe_role = self.take_x_string(text_prop_return.value as *mut c_char);
+        // The role is a string of 8-bit characters
+        if text_prop_return.format == 8 {
+            maybe_role
+        } else {
+            None
+        }
+    }
+
+    /// Retrieve the process id of the application of the given window,
+    /// stored in its `_NET_WM_PID` property.
+    pub fn get_window_pid(&self, window: Window) -> Option<c_uint> {
+        let net_wm_pid = self.get_atom("_NET_WM_PID");
+        self.get_window_property32(window, net_wm_pid)
+            .and_then(|props| props.first().map(|pid| *pid as c_uint))
+    }
+
+    /// Retrieve the window for which the given window is a transient window,
+    /// e.g. the main window of a dialog.
+    ///
+    /// Uses [`XGetTransientForHint`].
+    ///
+    /// [`XGetTransientForHint`]:
+    /// https://tronche.com/gui/x/xlib/ICC/client-to-window-manager/XGetTransientForHint.html
+    pub fn get_transient_for(&self, window: Window) -> Option<Window> {
+        let mut prop_window_return = 0;
+        let status = unsafe {
+            (self.xlib.XGetTransientForHint)(self.display, window, &mut prop_window_return)
+        };
+        if status != 0 && prop_window_return != 0 {
+            Some(prop_window_return)
+        } else {
+            None
+        }
+    }
+
+    /// Retrieve the names of the types in the [`_NET_WM_WINDOW_TYPE`]
+    /// property of the given window, e.g. `"_NET_WM_WINDOW_TYPE_DIALOG"`.
+    ///
+    /// [`_NET_WM_WINDOW_TYPE`]: https://developer.gnome.org/wm-spec/#idm140200472629520
+    pub fn get_window_types(&self, window: Window) -> Vec<String> {
+        let net_wm_window_type = self.get_atom("_NET_WM_WINDOW_TYPE");
+        self.get_window_property32(window, net_wm_window_type)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|atom| self.get_atom_name(*atom as xlib::Atom))
+            .collect()
+    }
+
+    /// Retrieve all the properties of the given window the window manager
+    /// keeps track of.
+    pub fn get_window_properties(&self, window: Window) -> WindowProperties {
+        let (instance, class) = self.get_window_class(window);
+        WindowProperties {
+            class: class,
+            instance: instance,
+            role: self.get_window_role(window),
+            title: self.get_window_title(window),
+            pid: self.get_window_pid(window),
+            transient_for: self.get_transient_for(window),
+        }
+    }
+
+    /// Convert a string allocated by Xlib to a `String` and free it.
+    ///
+    /// Return `None` when the pointer is null or when the string could not
+    /// be converted to a valid `String`.
+    fn take_x_string(&self, ptr: *mut c_char) -> Option<String> {
+        if ptr.is_null() {
+            return None;
+        }
+        let maybe_string = unsafe { CStr::from_ptr(ptr) }.to_str().ok().map(|s| s.to_owned());
+        unsafe {
+            (self.xlib.XFree)(transmute(ptr));
+        }
+        maybe_string
+    }
+
     /// Close the given window.
     ///
     /// When the window supports the [ICCCM protocol], the protocol is
@@ -585,11 +716,7 @@ impl<WM: WindowManager> X11Backend<WM> {
         }
 
         // Second condition
-        let mut prop_window_return = 0;
-        let status = unsafe {
-            (self.xlib.XGetTransientForHint)(self.display, window, &mut prop_window_return)
-        };
-        let is_transient = status != 0 && prop_window_return != 0;
+        let is_transient = self.get_transient_for(window).is_some();
         if is_transient {
             return FloatOrTile::Float;
         }
@@ -625,6 +752,46 @@ impl<WM: WindowManager> X11Backend<WM> {
         window_state_props.contains(&(net_wm_state_fullscreen as c_int))
     }
 
+    /// Check whether the given window wants to be sticky.
+    ///
+    /// This is done by checking whether `_NET_WM_STATE_STICKY` is in the
+    /// [`_NET_WM_STATE`] property of the window.
+    ///
+    /// [`_NET_WM_STATE`]: https://developer.gnome.org/wm-spec/#idm140200472615568
+    pub fn wants_to_be_sticky(&self, window: Window) -> bool {
+        let net_wm_state_sticky = self.get_atom("_NET_WM_STATE_STICKY");
+        let net_wm_state = self.get_atom("_NET_WM_STATE");
+        let window_state_props = self.get_window_property32(window, net_wm_state)
+            .unwrap_or_default();
+        window_state_props.contains(&(net_wm_state_sticky as c_int))
+    }
+
+    /// Check whether the given window demands attention.
+    ///
+    /// This is the case when the urgency flag is set in its [`WM_HINTS`] or
+    /// when `_NET_WM_STATE_DEMANDS_ATTENTION` is in the [`_NET_WM_STATE`]
+    /// property of the window.
+    ///
+    /// [`WM_HINTS`]: https://tronche.com/gui/x/icccm/sec-4.html#s-4.1.2.4
+    /// [`_NET_WM_STATE`]: https://developer.gnome.org/wm-spec/#idm140200472615568
+    pub fn is_window_urgent(&self, window: Window) -> bool {
+        let hints = unsafe { (self.xlib.XGetWMHints)(self.display, window) };
+        if !hints.is_null() {
+            let flags = unsafe { (*hints).flags };
+            unsafe {
+                (self.xlib.XFree)(transmute(hints));
+            }
+            if flags & xlib::XUrgencyHint != 0 {
+                return true;
+            }
+        }
+        let net_wm_state_demands_attention = self.get_atom("_NET_WM_STATE_DEMANDS_ATTENTION");
+        let net_wm_state = self.get_atom("_NET_WM_STATE");
+        let window_state_props = self.get_window_property32(window, net_wm_state)
+            .unwrap_or_default();
+        window_state_props.contains(&(net_wm_state_demands_attention as c_int))
+    }
+
     /// Set the window border width using `XSetWindowBorderWidth`.
     pub fn set_window_border_width(&self, window: Window, border_width: c_uint) {
         trace!("set_window_border_width: {}, {}", window, border_width);
@@ -655,4 +822,46 @@ impl<WM: WindowManager> X11Backend<WM> {
             height: unsafe { (*xscreen).height } as c_uint,
         }
     }
+
+    /// Return the outputs (monitors) covering the `Screen`.
+    ///
+    /// The outputs are queried using the Xinerama extension, which RandR
+    /// also provides. When Xinerama is not available or not active, a single
+    /// output covering the whole `Screen` is returned. Cloned outputs, i.e.
+    /// outputs with the same geometry, are only returned once.
+    ///
+    /// To test this with several outputs, start the window manager in e.g.
+    /// `Xephyr +xinerama -screen 800x600 -screen 800x600 :1`.
+    pub fn get_outputs(&self) -> Vec<Geometry> {
+        let mut outputs = Vec::new();
+        if let Ok(xinerama) = xinerama::Xlib::open() {
+            if unsafe { (xinerama.XineramaIsActive)(self.display) } != xlib::False {
+                let mut number = 0;
+                let screens_ptr =
+                    unsafe { (xinerama.XineramaQueryScreens)(self.display, &mut number) };
+                if !screens_ptr.is_null() {
+                    let screens = unsafe { slice::from_raw_parts(screens_ptr, number as usize) };
+                    for screen_info in screens {
+                        let output = Geometry {
+                            x: screen_info.x_org as c_int,
+                            y: screen_info.y_org as c_int,
+                            width: screen_info.width as c_uint,
+                            height: screen_info.height as c_uint,
+                        };
+                        if !outputs.contains(&output) {
+                            outputs.push(output);
+                        }
+                    }
+                    unsafe {
+                        (self.xlib.XFree)(transmute(screens_ptr));
+                    }
+                }
+            }
+        }
+        if outputs.is_empty() {
+            outputs.push(self.get_screen().to_geometry());
+        }
+        debug!("get_outputs: {:?}", outputs);
+        outputs
+    }
 }
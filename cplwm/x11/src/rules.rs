@@ -0,0 +1,257 @@
+//! Rules applied to new windows
+//!
+//! A [`WindowRule`] combines a [`WindowMatcher`], which describes the windows
+//! the rule applies to, e.g. all windows with class `"Pavucontrol"`, with
+//! [`RuleActions`], which describe what should happen with these windows
+//! when they are mapped, e.g. make them float.
+//!
+//! The rules are defined in the [`X11Config`], the backend applies them
+//! before passing a new window to the window manager. This module only does
+//! the matching, so it does not need an X server.
+//!
+//! [`WindowRule`]: struct.WindowRule.html
+//! [`WindowMatcher`]: struct.WindowMatcher.html
+//! [`RuleActions`]: struct.RuleActions.html
+//! [`X11Config`]: struct.X11Config.html
+
+use std::os::raw::c_uint;
+
+use cplwm_api::types::{FloatOrTile, Geometry, WindowProperties, WorkspaceIndex};
+
+
+/// The criteria a window must satisfy for a rule to apply to it.
+///
+/// A window matches when it satisfies all the criteria that are set, so a
+/// matcher without criteria matches every window.
+///
+/// * `class`, `instance` and `role` must be equal to the corresponding
+///   property of the window, see [`WindowProperties`].
+/// * `title` must be contained in the title of the window.
+/// * `window_type` must be one of the types in the `_NET_WM_WINDOW_TYPE`
+///   property of the window, e.g. `"_NET_WM_WINDOW_TYPE_DIALOG"`.
+///
+/// [`WindowProperties`]: ../cplwm_api/types/struct.WindowProperties.html
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct WindowMatcher {
+    /// The class of the window, the second part of `WM_CLASS`.
+    pub class: Option<&'static str>,
+    /// The instance of the window, the first part of `WM_CLASS`.
+    pub instance: Option<&'static str>,
+    /// A part of the title of the window.
+    pub title: Option<&'static str>,
+    /// The role of the window, `WM_WINDOW_ROLE`.
+    pub role: Option<&'static str>,
+    /// One of the types of the window, `_NET_WM_WINDOW_TYPE`.
+    pub window_type: Option<&'static str>,
+}
+
+impl WindowMatcher {
+    /// Check whether a window with the given properties and window types
+    /// matches.
+    pub fn matches(&self, properties: &WindowProperties, window_types: &[String]) -> bool {
+        // A criterion that is not set is always satisfied, one that is set is
+        // never satisfied by a missing property.
+        let equals = |criterion: Option<&'static str>, property: &Option<String>| {
+            criterion.map_or(true, |c| property.as_ref().map_or(false, |p| p == c))
+        };
+        equals(self.class, &properties.class) && equals(self.instance, &properties.instance) &&
+        equals(self.role, &properties.role) &&
+        self.title.map_or(true, |c| properties.title.as_ref().map_or(false, |t| t.contains(c))) &&
+        self.window_type.map_or(true, |c| window_types.iter().any(|t| t == c))
+    }
+}
+
+/// What should happen with a window a rule applies to.
+///
+/// Only the actions that are set are taken, for the others the backend does
+/// what it would do without rules.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RuleActions {
+    /// Let the window float or tile.
+    pub float_or_tile: Option<FloatOrTile>,
+    /// Display the window fullscreen or not.
+    pub fullscreen: Option<bool>,
+    /// Move the window to this workspace, see `move_to_workspace` of the
+    /// [`X11Config`](struct.X11Config.html).
+    pub workspace: Option<WorkspaceIndex>,
+    /// The initial geometry of the window, instead of the one requested by
+    /// the window, centered on the screen.
+    pub geometry: Option<Geometry>,
+    /// Focus the window when it is opened or leave the focus where it was.
+    pub focus: Option<bool>,
+    /// The width of the border of the window, e.g. 0 for no border.
+    pub border_width: Option<c_uint>,
+}
+
+impl RuleActions {
+    /// Override these actions with the actions that are set in `other`.
+    pub fn merge(&mut self, other: &RuleActions) {
+        self.float_or_tile = other.float_or_tile.or(self.float_or_tile);
+        self.fullscreen = other.fullscreen.or(self.fullscreen);
+        self.workspace = other.workspace.or(self.workspace);
+        self.geometry = other.geometry.or(self.geometry);
+        self.focus = other.focus.or(self.focus);
+        self.border_width = other.border_width.or(self.border_width);
+    }
+}
+
+/// A rule: the actions are taken for the windows the matcher matches.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct WindowRule {
+    /// The windows this rule applies to.
+    pub matcher: WindowMatcher,
+    /// What to do with these windows.
+    pub actions: RuleActions,
+}
+
+/// Return the actions of all the rules that match a window with the given
+/// properties and window types.
+///
+/// The rules are applied in order, so when several matching rules set the
+/// same action, the last one wins.
+pub fn get_rule_actions(rules: &[WindowRule],
+                        properties: &WindowProperties,
+                        window_types: &[String])
+                        -> RuleActions {
+    let mut actions = RuleActions::default();
+    for rule in rules {
+        if rule.matcher.matches(properties, window_types) {
+            actions.merge(&rule.actions);
+        }
+    }
+    actions
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn xterm_properties() -> WindowProperties {
+        WindowProperties {
+            class: Some("XTerm".to_owned()),
+            instance: Some("xterm".to_owned()),
+            role: None,
+            title: Some("vim - rules.rs".to_owned()),
+            pid: Some(42),
+            transient_for: None,
+        }
+    }
+
+    #[test]
+    fn test_window_matcher() {
+        let properties = xterm_properties();
+        let no_types = Vec::new();
+
+        // No criteria match every window
+        assert!(WindowMatcher::default().matches(&properties, &no_types));
+        assert!(WindowMatcher::default().matches(&WindowProperties::default(), &no_types));
+
+        // Class, instance and role are compared exactly
+        let mut matcher = WindowMatcher::default();
+        matcher.class = Some("XTerm");
+        assert!(matcher.matches(&properties, &no_types));
+        matcher.class = Some("XTer");
+        assert!(!matcher.matches(&properties, &no_types));
+        matcher.class = Some("XTerm");
+        matcher.instance = Some("xterm");
+        assert!(matcher.matches(&properties, &no_types));
+        matcher.instance = Some("uxterm");
+        assert!(!matcher.matches(&properties, &no_types));
+
+        // A missing property never matches
+        let mut matcher = WindowMatcher::default();
+        matcher.role = Some("toolbox");
+        assert!(!matcher.matches(&properties, &no_types));
+        let mut gimp_properties = WindowProperties::default();
+        gimp_properties.role = Some("toolbox".to_owned());
+        assert!(matcher.matches(&gimp_properties, &no_types));
+
+        // The title only has to contain the criterion
+        let mut matcher = WindowMatcher::default();
+        matcher.title = Some("vim");
+        assert!(matcher.matches(&properties, &no_types));
+        matcher.title = Some("emacs");
+        assert!(!matcher.matches(&properties, &no_types));
+        assert!(!matcher.matches(&WindowProperties::default(), &no_types));
+
+        // The window type must be one of the types of the window
+        let mut matcher = WindowMatcher::default();
+        matcher.window_type = Some("_NET_WM_WINDOW_TYPE_DIALOG");
+        assert!(!matcher.matches(&properties, &no_types));
+        let types = vec!["_NET_WM_WINDOW_TYPE_DIALOG".to_owned(),
+                         "_NET_WM_WINDOW_TYPE_NORMAL".to_owned()];
+        assert!(matcher.matches(&properties, &types));
+    }
+
+    #[test]
+    fn test_get_rule_actions() {
+        let properties = xterm_properties();
+        let no_types = Vec::new();
+        let geometry = Geometry {
+            x: 10,
+            y: 10,
+            width: 100,
+            height: 100,
+        };
+        let rules = vec![WindowRule {
+                             matcher: WindowMatcher {
+                                 class: Some("XTerm"),
+                                 ..Default::default()
+                             },
+                             actions: RuleActions {
+                                 float_or_tile: Some(FloatOrTile::Float),
+                                 workspace: Some(2),
+                                 ..Default::default()
+                             },
+                         },
+                         WindowRule {
+                             matcher: WindowMatcher {
+                                 title: Some("vim"),
+                                 ..Default::default()
+                             },
+                             actions: RuleActions {
+                                 float_or_tile: Some(FloatOrTile::Tile),
+                                 border_width: Some(0),
+                                 ..Default::default()
+                             },
+                         },
+                         WindowRule {
+                             matcher: WindowMatcher {
+                                 class: Some("Gimp"),
+                                 ..Default::default()
+                             },
+                             actions: RuleActions {
+                                 geometry: Some(geometry),
+                                 focus: Some(false),
+                                 ..Default::default()
+                             },
+                         }];
+
+        // No rules, no actions
+        assert_eq!(RuleActions::default(), get_rule_actions(&[], &properties, &no_types));
+
+        // The actions of the matching rules are combined, the last one wins
+        let actions = get_rule_actions(&rules, &properties, &no_types);
+        assert_eq!(RuleActions {
+                       float_or_tile: Some(FloatOrTile::Tile),
+                       workspace: Some(2),
+                       border_width: Some(0),
+                       ..Default::default()
+                   },
+                   actions);
+
+        // Rules that do not match are ignored
+        let mut gimp_properties = WindowProperties::default();
+        gimp_properties.class = Some("Gimp".to_owned());
+        let actions = get_rule_actions(&rules, &gimp_properties, &no_types);
+        assert_eq!(RuleActions {
+                       geometry: Some(geometry),
+                       focus: Some(false),
+                       ..Default::default()
+                   },
+                   actions);
+        let actions = get_rule_actions(&rules, &WindowProperties::default(), &no_types);
+        assert_eq!(RuleActions::default(), actions);
+    }
+}
@@ -0,0 +1,204 @@
+//! The optional traits of the window manager.
+//!
+//! The backend needs a window manager that implements `WindowManager`,
+//! `FloatSupport`, `FullscreenSupport` and `MinimiseSupport`. It can do more
+//! when the window manager implements some of the other traits, e.g. draw
+//! the borders of urgent windows in a distinct color, but these traits are
+//! optional: a window manager that doesn't implement them must still run.
+//!
+//! Rust can't check whether a type implements a trait while the program is
+//! running, so the backend can't just call the methods of these traits.
+//! Instead, the [`WMSupport`] in the [`X11Config`] keeps the methods of
+//! every optional trait the backend needs, or `None` when they are not
+//! enabled. The methods that enable a trait, e.g. [`enable_urgency`], only
+//! exist when the window manager implements the trait.
+//!
+//! [`WMSupport`]: struct.WMSupport.html
+//! [`X11Config`]: struct.X11Config.html
+//! [`enable_urgency`]: struct.WMSupport.html#method.enable_urgency
+
+use cplwm_api::types::{TabBar, WMEvent, Window, WindowProperties};
+use cplwm_api::wm::{EventSupport, InvariantCheck, StickySupport, TabBarSupport, UrgencySupport,
+                    WindowPropertiesSupport};
+
+use super::*;
+
+/// The type of `get_window_properties` in [`WMSupport`].
+///
+/// [`WMSupport`]: struct.WMSupport.html
+pub type GetWindowProperties<WM> = fn(&WM, Window) -> Option<WindowProperties>;
+
+/// The type of `set_window_properties` in [`WMSupport`].
+///
+/// [`WMSupport`]: struct.WMSupport.html
+pub type SetWindowProperties<WM> = fn(&mut WM, Window, WindowProperties) -> X11Result<()>;
+
+/// The type of `get_urgent_windows` in [`WMSupport`].
+///
+/// [`WMSupport`]: struct.WMSupport.html
+pub type GetUrgentWindows<WM> = fn(&WM) -> Vec<Window>;
+
+/// The type of `set_urgent` in [`WMSupport`].
+///
+/// [`WMSupport`]: struct.WMSupport.html
+pub type SetUrgent<WM> = fn(&mut WM, Window, bool) -> X11Result<()>;
+
+/// The type of `is_sticky` in [`WMSupport`].
+///
+/// [`WMSupport`]: struct.WMSupport.html
+pub type IsSticky<WM> = fn(&WM, Window) -> bool;
+
+/// The type of `toggle_sticky` in [`WMSupport`].
+///
+/// [`WMSupport`]: struct.WMSupport.html
+pub type ToggleSticky<WM> = fn(&mut WM, Window) -> X11Result<()>;
+
+/// The methods of the optional traits of the window manager that the
+/// backend uses, see the [module documentation](index.html).
+///
+/// By default, no optional trait is enabled.
+pub struct WMSupport<WM> {
+    /// `get_window_properties` and `set_window_properties` of
+    /// `WindowPropertiesSupport`, the backend passes the changed properties
+    /// of a window to the window manager.
+    pub window_properties: Option<(GetWindowProperties<WM>, SetWindowProperties<WM>)>,
+    /// `get_tab_bars` of `TabBarSupport`, the backend draws the tab bars.
+    pub tab_bars: Option<fn(&WM) -> Vec<TabBar>>,
+    /// `get_urgent_windows` and `set_urgent` of `UrgencySupport`, the
+    /// backend passes the urgency of the windows to the window manager and
+    /// draws the borders of urgent windows in the urgent border color.
+    pub urgency: Option<(GetUrgentWindows<WM>, SetUrgent<WM>)>,
+    /// `is_sticky` and `toggle_sticky` of `StickySupport`, the backend makes
+    /// the windows that ask for it sticky.
+    pub sticky: Option<(IsSticky<WM>, ToggleSticky<WM>)>,
+    /// `take_events` of `EventSupport`, the backend passes the events to the
+    /// event hooks of the config.
+    pub events: Option<fn(&mut WM) -> Vec<WMEvent>>,
+    /// `check_invariants` of `InvariantCheck`, the backend checks the
+    /// invariants after every event in debug builds.
+    pub invariants: Option<fn(&WM) -> Vec<String>>,
+}
+
+// Function pointers can always be copied, deriving would require `WM` to be
+// `Copy` as well.
+impl<WM> Clone for WMSupport<WM> {
+    fn clone(&self) -> WMSupport<WM> {
+        *self
+    }
+}
+
+impl<WM> Copy for WMSupport<WM> {}
+
+impl<WM> Default for WMSupport<WM> {
+    /// A `WMSupport` without any optional trait.
+    fn default() -> WMSupport<WM> {
+        WMSupport {
+            window_properties: None,
+            tab_bars: None,
+            urgency: None,
+            sticky: None,
+            events: None,
+            invariants: None,
+        }
+    }
+}
+
+// The methods of the optional traits return their own errors, the
+// functions below convert them to the types that `WMSupport` keeps.
+
+fn get_window_properties_fn<WM: WindowPropertiesSupport>(wm: &WM,
+                                                         window: Window)
+                                                         -> Option<WindowProperties> {
+    wm.get_window_properties(window).ok()
+}
+
+fn set_window_properties_fn<WM: WindowPropertiesSupport>(wm: &mut WM,
+                                                         window: Window,
+                                                         properties: WindowProperties)
+                                                         -> X11Result<()> {
+    try!(wm.set_window_properties(window, properties));
+    Ok(())
+}
+
+fn set_urgent_fn<WM: UrgencySupport>(wm: &mut WM, window: Window, urgent: bool) -> X11Result<()> {
+    try!(wm.set_urgent(window, urgent));
+    Ok(())
+}
+
+fn toggle_sticky_fn<WM: StickySupport>(wm: &mut WM, window: Window) -> X11Result<()> {
+    try!(wm.toggle_sticky(window));
+    Ok(())
+}
+
+impl<WM: WindowPropertiesSupport> WMSupport<WM> {
+    /// Enable `WindowPropertiesSupport`.
+    pub fn enable_window_properties(&mut self) {
+        self.window_properties = Some((get_window_properties_fn::<WM>,
+                                       set_window_properties_fn::<WM>));
+    }
+}
+
+impl<WM: TabBarSupport> WMSupport<WM> {
+    /// Enable `TabBarSupport`.
+    pub fn enable_tab_bars(&mut self) {
+        self.tab_bars = Some(WM::get_tab_bars);
+    }
+}
+
+impl<WM: UrgencySupport> WMSupport<WM> {
+    /// Enable `UrgencySupport`.
+    pub fn enable_urgency(&mut self) {
+        self.urgency = Some((WM::get_urgent_windows, set_urgent_fn::<WM>));
+    }
+}
+
+impl<WM: StickySupport> WMSupport<WM> {
+    /// Enable `StickySupport`.
+    pub fn enable_sticky(&mut self) {
+        self.sticky = Some((WM::is_sticky, toggle_sticky_fn::<WM>));
+    }
+}
+
+impl<WM: EventSupport> WMSupport<WM> {
+    /// Enable `EventSupport`.
+    pub fn enable_events(&mut self) {
+        self.events = Some(WM::take_events);
+    }
+}
+
+impl<WM: InvariantCheck> WMSupport<WM> {
+    /// Enable `InvariantCheck`.
+    pub fn enable_invariants(&mut self) {
+        self.invariants = Some(WM::check_invariants);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use cplwm_api::types::Screen;
+    use cplwm_api::wm::{FloatSupport, FullscreenSupport, MinimiseSupport, WindowManager};
+
+    // This only has to compile: a window manager without any of the
+    // optional traits can be started.
+    #[allow(dead_code)]
+    fn start_without_optional_traits<WM>(make_wm: fn(Screen) -> WM) -> X11Result<()>
+        where WM: WindowManager + FloatSupport + FullscreenSupport + MinimiseSupport
+    {
+        X11Backend::start(make_wm, X11Config::default())
+    }
+
+    #[test]
+    fn test_default_support() {
+        let config: X11Config<()> = X11Config::default();
+        let support = config.support;
+        assert!(support.window_properties.is_none());
+        assert!(support.tab_bars.is_none());
+        assert!(support.urgency.is_none());
+        assert!(support.sticky.is_none());
+        assert!(support.events.is_none());
+        assert!(support.invariants.is_none());
+    }
+}
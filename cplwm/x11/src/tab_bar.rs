@@ -0,0 +1,214 @@
+//! Tab bar-related methods.
+//!
+//! In a tabbed layout, e.g. the monocle layout, only one tiled window is
+//! visible. The window manager reports a [`TabBar`] listing the tiled
+//! windows, the backend draws it in a window of its own: a tab per window
+//! with its title, the active tab is highlighted. Clicking on a tab focuses
+//! its window.
+//!
+//! The tab bar windows are `override_redirect` windows, so they are not
+//! managed by the window manager, and they are kept below all other windows.
+//!
+//! [`TabBar`]: ../cplwm_api/types/struct.TabBar.html
+
+use std::cmp::max;
+use std::ffi::CString;
+use std::mem::zeroed;
+use std::os::raw::{c_int, c_uint};
+
+use cplwm_api::types::{Geometry, Window};
+use cplwm_api::wm::{FloatSupport, FullscreenSupport, MinimiseSupport, WindowManager};
+
+use super::*;
+
+use x11_dl::xlib;
+
+/// The space between the left edge of a tab and its title.
+const TAB_PADDING: c_int = 4;
+
+/// Tab bar-related methods.
+impl<WM> X11Backend<WM>
+    where WM: WindowManager + FloatSupport + FullscreenSupport + MinimiseSupport
+{
+    /// Make the tab bar windows match the tab bars of the window manager.
+    ///
+    /// Tab bar windows are created, moved or destroyed when needed, all of
+    /// them are redrawn, as the titles of the windows may have changed.
+    pub fn update_tab_bars(&mut self) {
+        let tab_bars = match self.support.tab_bars {
+            Some(get_tab_bars) => get_tab_bars(self.get_wm()),
+            None => Vec::new(),
+        };
+        // Destroy the windows of the tab bars that are gone
+        while self.tab_bars.len() > tab_bars.len() {
+            // unwrap() is used because the Vec is not empty
+            let (window, _) = self.tab_bars.pop().unwrap();
+            unsafe {
+                (self.xlib.XDestroyWindow)(self.display, window);
+            }
+        }
+        for (index, tab_bar) in tab_bars.into_iter().enumerate() {
+            if index < self.tab_bars.len() {
+                let window = self.tab_bars[index].0;
+                if self.tab_bars[index].1.geometry != tab_bar.geometry {
+                    self.move_tab_bar_window(window, tab_bar.geometry);
+                }
+                self.tab_bars[index].1 = tab_bar;
+            } else {
+                let window = self.create_tab_bar_window(tab_bar.geometry);
+                self.tab_bars.push((window, tab_bar));
+            }
+        }
+        for index in 0..self.tab_bars.len() {
+            self.draw_tab_bar(index);
+        }
+    }
+
+    /// Return `true` when the given window is the window of a tab bar.
+    pub fn is_tab_bar(&self, window: Window) -> bool {
+        self.tab_bars.iter().any(|&(w, _)| w == window)
+    }
+
+    /// Return the window of the tab at the given position on the screen, if
+    /// there is a tab there.
+    pub fn get_tab_at(&self, x: c_int, y: c_int) -> Option<Window> {
+        for &(_, ref tab_bar) in &self.tab_bars {
+            let geometry = tab_bar.geometry;
+            let inside = x >= geometry.x && y >= geometry.y &&
+                         x < geometry.x + geometry.width as c_int &&
+                         y < geometry.y + geometry.height as c_int;
+            if inside && !tab_bar.windows.is_empty() {
+                let tab_count = tab_bar.windows.len() as c_int;
+                let index = (x - geometry.x) * tab_count / geometry.width as c_int;
+                return tab_bar.windows.get(index as usize).cloned();
+            }
+        }
+        None
+    }
+
+    /// Redraw the tab bar shown in the given window, e.g. when it was
+    /// exposed. Does nothing when the window is not a tab bar.
+    pub fn redraw_tab_bar(&self, window: Window) {
+        if let Some(index) = self.tab_bars.iter().position(|&(w, _)| w == window) {
+            self.draw_tab_bar(index);
+        }
+    }
+
+    /// Create and map a window for a tab bar with the given geometry.
+    fn create_tab_bar_window(&self, geometry: Geometry) -> Window {
+        let window = unsafe {
+            (self.xlib.XCreateSimpleWindow)(self.display,
+                                            self.root_window,
+                                            geometry.x,
+                                            geometry.y,
+                                            max(1, geometry.width),
+                                            max(1, geometry.height),
+                                            0,
+                                            0,
+                                            self.unfocused_border_color.pixel)
+        };
+        trace!("create_tab_bar_window: {} {}", window, geometry);
+        let mut attributes: xlib::XSetWindowAttributes = unsafe { zeroed() };
+        attributes.override_redirect = xlib::True;
+        unsafe {
+            (self.xlib.XChangeWindowAttributes)(self.display,
+                                                window,
+                                                xlib::CWOverrideRedirect,
+                                                &mut attributes);
+            (self.xlib.XSelectInput)(self.display,
+                                     window,
+                                     xlib::ExposureMask | xlib::ButtonPressMask);
+            (self.xlib.XMapWindow)(self.display, window);
+            // The tiled windows don't overlap with the tab bar, so it can
+            // stay below all other windows, e.g. floating ones.
+            (self.xlib.XLowerWindow)(self.display, window);
+        }
+        window
+    }
+
+    /// Move and resize the given tab bar window.
+    fn move_tab_bar_window(&self, window: Window, geometry: Geometry) {
+        unsafe {
+            (self.xlib.XMoveResizeWindow)(self.display,
+                                          window,
+                                          geometry.x,
+                                          geometry.y,
+                                          max(1, geometry.width),
+                                          max(1, geometry.height));
+        }
+    }
+
+    /// Draw the tab bar with the given index in `tab_bars`.
+    ///
+    /// The tabs are equally wide, they are drawn from left to right, so a
+    /// title that is too long for its tab is covered by the next tab.
+    fn draw_tab_bar(&self, index: usize) {
+        let (window, ref tab_bar) = self.tab_bars[index];
+        if tab_bar.windows.is_empty() {
+            return;
+        }
+        let width = tab_bar.geometry.width;
+        let height = tab_bar.geometry.height;
+        let tab_count = tab_bar.windows.len() as c_uint;
+        let screen_number = unsafe { (self.xlib.XDefaultScreen)(self.display) };
+        let white = unsafe { (self.xlib.XWhitePixel)(self.display, screen_number) };
+        let black = unsafe { (self.xlib.XBlackPixel)(self.display, screen_number) };
+        // Center the text vertically, fall back to an estimate when the font
+        // could not be loaded
+        let baseline = if self.tab_bar_font.is_null() {
+            height as c_int * 3 / 4
+        } else {
+            let (ascent, descent) =
+                unsafe { ((*self.tab_bar_font).ascent, (*self.tab_bar_font).descent) };
+            (height as c_int + ascent - descent) / 2
+        };
+
+        for (i, tab_window) in tab_bar.windows.iter().enumerate() {
+            let i = i as c_uint;
+            let x = (i * width / tab_count) as c_int;
+            let tab_width = (i + 1) * width / tab_count - x as c_uint;
+            let (background, foreground) = if tab_bar.active_window == Some(*tab_window) {
+                (self.focused_border_color.pixel, white)
+            } else {
+                (self.unfocused_border_color.pixel, black)
+            };
+            let title = self.get_window_title(*tab_window).unwrap_or(tab_window.to_string());
+            unsafe {
+                (self.xlib.XSetForeground)(self.display, self.tab_bar_gc, background);
+                (self.xlib.XFillRectangle)(self.display,
+                                           window,
+                                           self.tab_bar_gc,
+                                           x,
+                                           0,
+                                           tab_width,
+                                           height);
+                (self.xlib.XSetForeground)(self.display, self.tab_bar_gc, foreground);
+                // Separate the tabs with a line
+                if i > 0 {
+                    (self.xlib.XDrawLine)(self.display,
+                                          window,
+                                          self.tab_bar_gc,
+                                          x,
+                                          0,
+                                          x,
+                                          height as c_int);
+                }
+            }
+            // A title with a nul byte can't be drawn, so leave it out
+            if let Ok(c_title) = CString::new(title) {
+                unsafe {
+                    (self.xlib.XDrawString)(self.display,
+                                            window,
+                                            self.tab_bar_gc,
+                                            x + TAB_PADDING,
+                                            baseline,
+                                            c_title.as_ptr(),
+                                            c_title.as_bytes().len() as c_int);
+                }
+            }
+        }
+        unsafe {
+            (self.xlib.XFlush)(self.display);
+        }
+    }
+}
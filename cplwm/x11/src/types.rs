@@ -9,7 +9,7 @@ use std::os::raw::{c_int, c_long, c_uint};
 
 use super::*;
 
-use cplwm_api::types::Window;
+use cplwm_api::types::{Geometry, WMEvent, Window, WorkspaceIndex};
 
 use x11_dl::xlib;
 
@@ -108,6 +108,29 @@ pub type ButtonBindings<WM> = HashMap<Button, ButtonCommand<WM>>;
 /// Colors can be hexadecimal, e.g. `"#ff00ff"` but also `"red"` or `"blue"`.
 pub type ColorName = &'static str;
 
+/// The type of a command that is executed with the outputs (monitors) of the
+/// screen, see [`get_outputs`].
+///
+/// [`get_outputs`]: struct.X11Backend.html#method.get_outputs
+pub type OutputsCommand<WM> = Box<Fn(&mut X11Backend<WM>, Vec<Geometry>) -> X11Result<()>>;
+
+/// The type of a command that moves a window to a workspace, see the
+/// `workspace` action of [`RuleActions`].
+///
+/// [`RuleActions`]: struct.RuleActions.html
+pub type WorkspaceCommand<WM> =
+    Box<Fn(&mut X11Backend<WM>, Window, WorkspaceIndex) -> X11Result<()>>;
+
+/// The type of a callback that is executed for every event of the window
+/// manager, see [`EventSupport`].
+///
+/// The callback runs after the event was handled, but before the changes are
+/// applied to the X server. Events caused by the callback itself are passed
+/// to the callbacks after the next event.
+///
+/// [`EventSupport`]: ../cplwm_api/wm/trait.EventSupport.html
+pub type EventHook<WM> = Box<Fn(&mut X11Backend<WM>, WMEvent) -> X11Result<()>>;
+
 /// User configuration of the X11 backend.
 pub struct X11Config<WM> {
     /// The key bindings chosen by the user.
@@ -128,13 +151,45 @@ pub struct X11Config<WM> {
     pub focused_border_color: ColorName,
     /// The color used for the border of the unfocused windows.
     pub unfocused_border_color: ColorName,
+    /// The color used for the border of the urgent windows, see
+    /// `UrgencySupport`.
+    pub urgent_border_color: ColorName,
+    /// The command executed with the outputs of the screen, at the start and
+    /// whenever the screen is reconfigured.
+    ///
+    /// Use this to pass the outputs to a window manager that supports
+    /// multiple outputs. When `None`, the window manager only gets the
+    /// `Screen`.
+    pub outputs_changed: Option<OutputsCommand<WM>>,
+    /// The rules applied to new windows, in order.
+    ///
+    /// See [`WindowRule`](struct.WindowRule.html).
+    pub rules: Vec<WindowRule>,
+    /// The command executed for a new window when a rule moves it to a
+    /// workspace.
+    ///
+    /// The backend does not know about workspaces, so when `None`, the
+    /// `workspace` action of the rules is ignored.
+    pub move_to_workspace: Option<WorkspaceCommand<WM>>,
+    /// The callbacks executed for every event of the window manager, in
+    /// order.
+    ///
+    /// Use these to log the changes or to update a status bar. They are only
+    /// called when `EventSupport` is enabled in `support`.
+    pub event_hooks: Vec<EventHook<WM>>,
+    /// The optional traits of the window manager that the backend uses.
+    ///
+    /// See [`WMSupport`](struct.WMSupport.html).
+    pub support: WMSupport<WM>,
 }
 
 impl<WM> Default for X11Config<WM> {
     /// A default `X11Config`.
     ///
-    /// No bindings are defined, and some colors are chosen for the background
-    /// and the borders.
+    /// No bindings, rules and event hooks are defined, some colors are
+    /// chosen for the background and the borders, the outputs are not
+    /// passed to the window manager, and no optional trait of the window
+    /// manager is used.
     fn default() -> X11Config<WM> {
         X11Config {
             key_bindings: Default::default(),
@@ -142,6 +197,12 @@ impl<WM> Default for X11Config<WM> {
             background_color: "#f4f4f4",
             focused_border_color: "#0f56c6",
             unfocused_border_color: "#c0d6f9",
+            urgent_border_color: "#d64937",
+            outputs_changed: None,
+            rules: Vec::new(),
+            move_to_workspace: None,
+            event_hooks: Vec::new(),
+            support: WMSupport::default(),
         }
     }
 }
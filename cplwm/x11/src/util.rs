@@ -241,6 +241,15 @@ pub fn valid_geometry(geometry: &Geometry) -> bool {
     0 < width && width < max && 0 < height && height < max
 }
 
+/// The width or height of the inside of a window of the given outer size,
+/// i.e. without its borders.
+///
+/// A border can be wider than half of a small window, then the window still
+/// gets one pixel instead of underflowing.
+pub fn inner_size(size: c_uint, border_width: c_uint) -> c_int {
+    max(1, size.saturating_sub(border_width.saturating_mul(2))) as c_int
+}
+
 /// If the geometry does not specify a position, center the window on the
 /// screen.
 ///